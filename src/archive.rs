@@ -0,0 +1,93 @@
+use crate::{Format, ParseOptions, ParserError, Transaction};
+use std::fs::File;
+use std::path::Path;
+
+/// Читает транзакции из всех файлов zip-архива заданного формата
+///
+/// Записи архива с расширением формата (`.csv`, `.txt` или `.bin`,
+/// без учёта регистра) разбираются в порядке сортировки их имён,
+/// а результаты объединяются. Остальные записи пропускаются.
+///
+/// # Аргументы
+/// * `path` - Путь к zip-архиву
+/// * `format` - Формат файлов внутри архива
+///
+/// # Возвращает
+/// * `Ok(Vec<Transaction>)` - Транзакции из всех подходящих файлов
+/// * `Err(ParserError)` - Ошибка чтения архива или парсинга файла
+pub fn read_zip<P: AsRef<Path>>(path: P, format: Format) -> Result<Vec<Transaction>, ParserError> {
+    let file = File::open(path)?;
+    let mut archive = zip::ZipArchive::new(file)
+        .map_err(|e| ParserError::Parse(format!("Invalid zip archive: {}", e)))?;
+
+    let suffix = format!(".{}", format.extension());
+    let mut names: Vec<String> = archive
+        .file_names()
+        .filter(|name| name.to_lowercase().ends_with(&suffix))
+        .map(String::from)
+        .collect();
+    names.sort();
+
+    let mut transactions = Vec::new();
+    for name in names {
+        let entry = archive
+            .by_name(&name)
+            .map_err(|e| ParserError::Parse(format!("Cannot read zip entry '{}': {}", name, e)))?;
+
+        let records = format
+            .parse_records(entry, &ParseOptions::default())
+            .map_err(|e| match e {
+                ParserError::Parse(msg) => ParserError::Parse(format!("{}: {}", name, msg)),
+                ParserError::Validation(msg) => {
+                    ParserError::Validation(format!("{}: {}", name, msg))
+                }
+                other => other,
+            })?;
+        transactions.extend(records);
+    }
+
+    Ok(transactions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use zip::write::SimpleFileOptions;
+
+    #[test]
+    fn test_read_zip_concatenates_sorted_entries() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let zip_path = dir.path().join("transactions.zip");
+
+        let header = "TX_ID,TX_TYPE,FROM_USER_ID,TO_USER_ID,AMOUNT,TIMESTAMP,STATUS,DESCRIPTION\n";
+        let mut writer = zip::ZipWriter::new(File::create(&zip_path).unwrap());
+        let options = SimpleFileOptions::default();
+
+        writer.start_file("b_second.csv", options).unwrap();
+        writeln!(
+            writer,
+            "{}1002,WITHDRAWAL,502,0,1000,1672538400000,PENDING,\"Second\"",
+            header
+        )
+        .unwrap();
+
+        writer.start_file("readme.md", options).unwrap();
+        writer.write_all(b"not a transaction file").unwrap();
+
+        writer.start_file("a_first.CSV", options).unwrap();
+        writeln!(
+            writer,
+            "{}1001,DEPOSIT,0,501,50000,1672531200000,SUCCESS,\"First\"",
+            header
+        )
+        .unwrap();
+        writer.finish().unwrap();
+
+        let transactions = read_zip(&zip_path, Format::Csv).unwrap();
+
+        assert_eq!(transactions.len(), 2);
+        assert_eq!(transactions[0].tx_id, 1001);
+        assert_eq!(transactions[1].tx_id, 1002);
+    }
+}
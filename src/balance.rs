@@ -0,0 +1,154 @@
+use crate::{Transaction, TransactionStatus, TransactionType};
+use std::collections::HashMap;
+
+/// Возвращает изменение баланса пользователя от одной транзакции
+///
+/// - депозит увеличивает баланс получателя (`to_user_id`)
+/// - вывод уменьшает баланс отправителя (`from_user_id`)
+/// - перевод уменьшает баланс отправителя и увеличивает баланс получателя
+///
+/// Для пользователя, не участвующего в транзакции, возвращается 0.
+/// Статус транзакции не учитывается.
+///
+/// # Аргументы
+/// * `transaction` - Транзакция
+/// * `user_id` - ID пользователя
+///
+/// # Пример
+/// ```
+/// use parser_lib::{signed_amount_for, Transaction, TransactionStatus, TransactionType};
+///
+/// let transfer = Transaction {
+///     tx_id: 1,
+///     tx_type: TransactionType::Transfer,
+///     from_user_id: 501,
+///     to_user_id: 502,
+///     amount: 100,
+///     timestamp: 0,
+///     status: TransactionStatus::Success,
+///     description: String::new(),
+///     currency: None,
+/// };
+///
+/// assert_eq!(signed_amount_for(&transfer, 501), -100);
+/// assert_eq!(signed_amount_for(&transfer, 502), 100);
+/// assert_eq!(signed_amount_for(&transfer, 503), 0);
+/// ```
+pub fn signed_amount_for(transaction: &Transaction, user_id: u64) -> i64 {
+    let mut delta = 0;
+    if matches!(
+        transaction.tx_type,
+        TransactionType::Transfer | TransactionType::Withdrawal
+    ) && transaction.from_user_id == user_id
+    {
+        delta -= transaction.amount;
+    }
+    if matches!(
+        transaction.tx_type,
+        TransactionType::Transfer | TransactionType::Deposit
+    ) && transaction.to_user_id == user_id
+    {
+        delta += transaction.amount;
+    }
+    delta
+}
+
+/// Вычисляет чистое изменение баланса каждого пользователя
+///
+/// Учитываются только успешные транзакции (`SUCCESS`). Системный
+/// участник с ID 0 в результат не попадает, поэтому сумма изменений
+/// по всем пользователям равна сумме депозитов минус сумма выводов,
+/// а переводы между пользователями в сумме дают ноль.
+///
+/// # Аргументы
+/// * `transactions` - Транзакции
+///
+/// # Возвращает
+/// Отображение ID пользователя в изменение его баланса
+///
+/// # Пример
+/// ```
+/// use parser_lib::{balance_delta, generate_sample};
+///
+/// let deltas = balance_delta(&generate_sample(20, 1));
+/// assert!(!deltas.contains_key(&0));
+/// ```
+pub fn balance_delta(transactions: &[Transaction]) -> HashMap<u64, i64> {
+    let mut deltas = HashMap::new();
+
+    for transaction in transactions {
+        if transaction.status != TransactionStatus::Success {
+            continue;
+        }
+        for user_id in [transaction.from_user_id, transaction.to_user_id] {
+            if user_id == 0 {
+                continue;
+            }
+            *deltas.entry(user_id).or_insert(0) += signed_amount_for(transaction, user_id);
+        }
+    }
+
+    deltas
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_transaction(
+        tx_type: TransactionType,
+        from: u64,
+        to: u64,
+        amount: i64,
+    ) -> Transaction {
+        Transaction {
+            tx_id: 1001,
+            tx_type,
+            from_user_id: from,
+            to_user_id: to,
+            amount,
+            timestamp: 1672531200000,
+            status: TransactionStatus::Success,
+            description: "Test".to_string(),
+            currency: None,
+        }
+    }
+
+    #[test]
+    fn test_transfer_nets_to_zero_across_pair() {
+        let transfer = create_test_transaction(TransactionType::Transfer, 501, 502, 15000);
+
+        assert_eq!(signed_amount_for(&transfer, 501), -15000);
+        assert_eq!(signed_amount_for(&transfer, 502), 15000);
+
+        let deltas = balance_delta(std::slice::from_ref(&transfer));
+        assert_eq!(deltas[&501], -15000);
+        assert_eq!(deltas[&502], 15000);
+        assert_eq!(deltas.values().sum::<i64>(), 0);
+    }
+
+    #[test]
+    fn test_balance_delta_deposits_withdrawals_and_failures() {
+        let mut failed = create_test_transaction(TransactionType::Deposit, 0, 501, 999);
+        failed.status = TransactionStatus::Failure;
+        let transactions = vec![
+            create_test_transaction(TransactionType::Deposit, 0, 501, 50000),
+            create_test_transaction(TransactionType::Withdrawal, 501, 0, 2000),
+            create_test_transaction(TransactionType::Transfer, 501, 502, 3000),
+            failed,
+        ];
+
+        let deltas = balance_delta(&transactions);
+
+        assert_eq!(deltas.len(), 2);
+        assert_eq!(deltas[&501], 50000 - 2000 - 3000);
+        assert_eq!(deltas[&502], 3000);
+    }
+
+    #[test]
+    fn test_self_transfer_is_zero() {
+        let transfer = create_test_transaction(TransactionType::Transfer, 501, 501, 100);
+
+        assert_eq!(signed_amount_for(&transfer, 501), 0);
+    }
+}
@@ -1,10 +1,25 @@
 use clap::Parser;
 use parser_lib::{
-    BinaryTransactions, CsvTransactions, ParseFromRead, TextTransactions, Transaction,
+    CompareOptions, Field, FieldDiff, Format, Lang, Message, Transaction, Transactions, compare,
+    sort_by_id,
 };
 use std::fs::File;
-use std::io::BufReader;
-use std::path::PathBuf;
+use std::io::{BufReader, Read};
+use std::path::{Path, PathBuf};
+
+/// Диагностическое сообщение режима `--verbose`
+///
+/// Сообщение выводится на языке `--lang`. С функцией `logging` оно
+/// передаётся в `log::info!`, иначе печатается в stderr.
+macro_rules! verbose {
+    ($lang:expr, $message:expr) => {{
+        let text = $message.render($lang);
+        #[cfg(feature = "logging")]
+        log::info!("{}", text);
+        #[cfg(not(feature = "logging"))]
+        eprintln!("{}", text);
+    }};
+}
 
 #[derive(Parser, Debug)]
 #[command(name = "ypbank_compare")]
@@ -14,161 +29,264 @@ struct Args {
     #[arg(long = "file1", value_name = "FILE")]
     file1: PathBuf,
 
+    /// Формат первого файла; по умолчанию определяется по расширению
     #[arg(
         long = "format1",
         value_name = "FORMAT",
         value_enum,
         ignore_case = true
     )]
-    format1: Format,
+    format1: Option<Format>,
 
     #[arg(long = "file2", value_name = "FILE")]
     file2: PathBuf,
 
+    /// Формат второго файла; по умолчанию определяется по расширению
     #[arg(
         long = "format2",
         value_name = "FORMAT",
         value_enum,
         ignore_case = true
     )]
-    format2: Format,
+    format2: Option<Format>,
 
     #[arg(short, long, default_value_t = false)]
     verbose: bool,
 
+    /// Поля через запятую, различия в которых не учитываются
+    /// (например, `timestamp,from_user_id`)
+    #[arg(long = "ignore", value_name = "FIELDS", value_delimiter = ',')]
+    ignore: Vec<Field>,
+
+    /// Синоним `--ignore description`
     #[arg(long = "ignore-description", default_value_t = false)]
     ignore_description: bool,
 
+    /// Синоним `--ignore status`
     #[arg(long = "ignore-status", default_value_t = false)]
     ignore_status: bool,
+
+    /// Для файлов одного формата сначала сравнить сырые байты; побайтово
+    /// совпадающие файлы не разбираются
+    #[arg(long = "byte-compare", default_value_t = false)]
+    byte_compare: bool,
+
+    /// Стабильно отсортировать обе стороны по TX_ID перед попарным сравнением
+    #[arg(long = "sort-before-compare", default_value_t = false)]
+    sort_before_compare: bool,
+
+    #[arg(long, value_enum, default_value_t = Lang::Ru, ignore_case = true)]
+    lang: Lang,
 }
 
-#[derive(clap::ValueEnum, Clone, Debug, PartialEq)]
-enum Format {
-    Csv,
-    Txt,
-    Bin,
+impl Args {
+    fn compare_options(&self) -> CompareOptions {
+        let mut options = CompareOptions::default();
+        options.ignored_fields.extend(self.ignore.iter().copied());
+        if self.ignore_description {
+            options = options.ignore(Field::Description);
+        }
+        if self.ignore_status {
+            options = options.ignore(Field::Status);
+        }
+        options
+    }
 }
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let args = Args::parse();
 
-    if args.verbose {
-        eprintln!("=== YPBank Comparer ===");
-        eprintln!("Сравниваем файлы:");
-        eprintln!(
-            "  Файл 1: {} (формат: {:?})",
-            args.file1.display(),
-            args.format1
-        );
-        eprintln!(
-            "  Файл 2: {} (формат: {:?})",
-            args.file2.display(),
-            args.format2
-        );
-        if args.ignore_description {
-            eprintln!("  Игнорируем различия в описаниях");
-        }
-        if args.ignore_status {
-            eprintln!("  Игнорируем различия в статусах");
+    #[cfg(feature = "logging")]
+    init_logging(args.verbose);
+
+    let format1 = resolve_format(&args.file1, args.format1, args.lang);
+    let format2 = resolve_format(&args.file2, args.format2, args.lang);
+
+    if verbose_enabled(args.verbose) {
+        let lang = args.lang;
+        verbose!(lang, Message::Banner("YPBank Comparer"));
+        verbose!(lang, Message::VerboseComparing);
+        verbose!(lang, Message::VerboseCompareFile(1, &args.file1, format1));
+        verbose!(lang, Message::VerboseCompareFile(2, &args.file2, format2));
+        let options = args.compare_options();
+        for field in Field::ALL {
+            if options.is_ignored(field) {
+                verbose!(lang, Message::VerboseIgnoredField(field));
+            }
         }
     }
 
     if !args.file1.exists() {
-        eprintln!("Ошибка: файл '{}' не найден", args.file1.display());
+        eprintln!("{}", Message::FileNotFound(&args.file1).render(args.lang));
         std::process::exit(1);
     }
     if !args.file2.exists() {
-        eprintln!("Ошибка: файл '{}' не найден", args.file2.display());
+        eprintln!("{}", Message::FileNotFound(&args.file2).render(args.lang));
         std::process::exit(1);
     }
 
-    let transactions1 = read_transactions(&args.file1, &args.format1)?;
-    let transactions2 = read_transactions(&args.file2, &args.format2)?;
+    if args.byte_compare {
+        if format1 != format2 {
+            if verbose_enabled(args.verbose) {
+                verbose!(args.lang, Message::VerboseFormatsDiffer);
+            }
+        } else if files_byte_identical(&args.file1, &args.file2)? {
+            let message = Message::BytesIdentical(&args.file1, &args.file2);
+            println!("{}", message.render(args.lang));
+            return Ok(());
+        } else {
+            println!("{}", Message::BytesDiffer.render(args.lang));
+        }
+    }
+
+    let mut transactions1 = read_transactions(&args.file1, &format1, args.verbose, args.lang)?;
+    let mut transactions2 = read_transactions(&args.file2, &format2, args.verbose, args.lang)?;
 
-    if args.verbose {
-        eprintln!("Прочитано транзакций:");
-        eprintln!("  Из файла 1: {}", transactions1.len());
-        eprintln!("  Из файла 2: {}", transactions2.len());
+    if args.sort_before_compare {
+        sort_by_id(&mut transactions1);
+        sort_by_id(&mut transactions2);
+        if verbose_enabled(args.verbose) {
+            verbose!(args.lang, Message::VerboseSorted);
+        }
     }
 
     match compare_transactions(&transactions1, &transactions2, &args) {
         Ok(true) => {
-            println!(
-                "Файлы '{}' и '{}' идентичны.",
-                args.file1.display(),
-                args.file2.display()
-            );
+            let message = Message::FilesIdentical(&args.file1, &args.file2);
+            println!("{}", message.render(args.lang));
             Ok(())
         }
         Ok(false) => {
             std::process::exit(2);
         }
         Err(e) => {
-            eprintln!("Ошибка при сравнении: {}", e);
+            eprintln!(
+                "{}",
+                Message::CompareFailed(e.to_string()).render(args.lang)
+            );
             std::process::exit(3);
         }
     }
 }
 
+/// Возвращает явно заданный формат файла или определяет его по расширению
+///
+/// Завершает процесс с кодом 1, если расширение не распознано.
+fn resolve_format(path: &Path, format: Option<Format>, lang: Lang) -> Format {
+    let inferred = || {
+        path.extension()
+            .and_then(|ext| ext.to_str())
+            .and_then(Format::from_extension)
+    };
+    match format.or_else(inferred) {
+        Some(format) => format,
+        None => {
+            eprintln!("{}", Message::UnknownExtension(path).render(lang));
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Проверяет побайтовое совпадение двух файлов
+///
+/// Файлы разного размера сравниваются без чтения содержимого.
+fn files_byte_identical(path1: &Path, path2: &Path) -> std::io::Result<bool> {
+    if std::fs::metadata(path1)?.len() != std::fs::metadata(path2)?.len() {
+        return Ok(false);
+    }
+
+    let mut reader1 = BufReader::new(File::open(path1)?);
+    let mut reader2 = BufReader::new(File::open(path2)?);
+    let mut buf1 = [0u8; 8192];
+    let mut buf2 = [0u8; 8192];
+    loop {
+        let read = reader1.read(&mut buf1)?;
+        if read == 0 {
+            return Ok(true);
+        }
+        reader2.read_exact(&mut buf2[..read])?;
+        if buf1[..read] != buf2[..read] {
+            return Ok(false);
+        }
+    }
+}
+
+/// Настраивает `env_logger`: `--verbose` включает уровень info,
+/// переменная `RUST_LOG` имеет приоритет
+#[cfg(feature = "logging")]
+fn init_logging(verbose: bool) {
+    let level = if verbose { "info" } else { "warn" };
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or(level)).init();
+}
+
+/// Проверяет, нужно ли формировать диагностические сообщения
+fn verbose_enabled(verbose: bool) -> bool {
+    #[cfg(feature = "logging")]
+    {
+        let _ = verbose;
+        log::log_enabled!(log::Level::Info)
+    }
+    #[cfg(not(feature = "logging"))]
+    verbose
+}
+
+/// Сравнивает списки через `parser_lib::compare` и печатает отчёт
 fn compare_transactions(
     txs1: &[Transaction],
     txs2: &[Transaction],
     args: &Args,
 ) -> Result<bool, Box<dyn std::error::Error>> {
-    if txs1.len() != txs2.len() {
-        println!("Файлы содержат разное количество транзакций:");
-        println!("  В '{}': {} транзакций", args.file1.display(), txs1.len());
-        println!("  В '{}': {} транзакций", args.file2.display(), txs2.len());
+    let report = compare(txs1, txs2, &args.compare_options());
+
+    if report.len_a != report.len_b {
+        println!("{}", Message::CountMismatch.render(args.lang));
+        println!(
+            "{}",
+            Message::FileCount(&args.file1, report.len_a).render(args.lang)
+        );
+        println!(
+            "{}",
+            Message::FileCount(&args.file2, report.len_b).render(args.lang)
+        );
         return Ok(false);
     }
 
-    if txs1.is_empty() {
-        println!("Оба файла пусты.");
+    if report.len_a == 0 {
+        println!("{}", Message::BothEmpty.render(args.lang));
         return Ok(true);
     }
 
-    let mut mismatches = Vec::new();
-    let mut identical_count = 0;
+    let identical_count = report.len_a - report.mismatches.len();
 
-    for (i, (tx1, tx2)) in txs1.iter().zip(txs2.iter()).enumerate() {
-        if transactions_equal(tx1, tx2, args) {
-            identical_count += 1;
-        } else {
-            mismatches.push((i, tx1, tx2));
-        }
-    }
-
-    if mismatches.is_empty() {
+    if report.identical {
         if args.verbose {
-            println!("Все {} транзакций совпадают.", identical_count);
+            println!("{}", Message::AllMatch(identical_count).render(args.lang));
         }
         Ok(true)
     } else {
-        println!(
-            "Найдено {} несоответствий из {} транзакций:",
-            mismatches.len(),
-            txs1.len()
-        );
+        let message = Message::MismatchesFound(report.mismatches.len(), report.len_a);
+        println!("{}:", message.render(args.lang));
 
-        for (i, tx1, tx2) in mismatches.iter().take(10) {
+        for mismatch in report.mismatches.iter().take(10) {
             println!(
-                "\nНесоответствие в транзакции #{} (ID: {}):",
-                i + 1,
-                tx1.tx_id
+                "{}",
+                Message::MismatchAt(mismatch.index + 1, mismatch.tx_id).render(args.lang)
             );
-            print_differences(tx1, tx2, args);
+            print_differences(&mismatch.diffs);
         }
 
-        if mismatches.len() > 10 {
-            println!("\n... и еще {} несоответствий.", mismatches.len() - 10);
+        if report.mismatches.len() > 10 {
+            let message = Message::MoreMismatches(report.mismatches.len() - 10);
+            println!("{}", message.render(args.lang));
         }
 
         if args.verbose {
-            println!("\nСтатистика:");
-            println!("  Идентичных транзакций: {}", identical_count);
-            println!("  Несовпадающих транзакций: {}", mismatches.len());
-            println!("  Всего транзакций: {}", txs1.len());
+            let message = Message::CompareStats {
+                identical: identical_count,
+                mismatched: report.mismatches.len(),
+                total: report.len_a,
+            };
+            println!("{}", message.render(args.lang));
         }
 
         Ok(false)
@@ -178,93 +296,45 @@ fn compare_transactions(
 fn read_transactions(
     file_path: &PathBuf,
     format: &Format,
+    verbose: bool,
+    lang: Lang,
 ) -> Result<Vec<Transaction>, Box<dyn std::error::Error>> {
     let file = File::open(file_path)?;
-    let mut reader = BufReader::new(file);
+    let Transactions(transactions) = Transactions::read(*format, BufReader::new(file))?;
 
-    match format {
-        Format::Csv => {
-            let csv_transactions: CsvTransactions = ParseFromRead::parse(&mut reader)?;
-            Ok(csv_transactions.0)
-        }
-        Format::Txt => {
-            let text_transactions: TextTransactions = ParseFromRead::parse(&mut reader)?;
-            Ok(text_transactions.0)
-        }
-        Format::Bin => {
-            let bin_transactions: BinaryTransactions = ParseFromRead::parse(&mut reader)?;
-            Ok(bin_transactions.0)
-        }
+    if verbose_enabled(verbose) {
+        let source = format!("'{}'", file_path.display());
+        verbose!(lang, Message::VerboseReadFrom(source, transactions.len()));
     }
-}
 
-fn transactions_equal(tx1: &Transaction, tx2: &Transaction, args: &Args) -> bool {
-    if tx1.tx_id != tx2.tx_id {
-        return false;
-    }
-    if tx1.tx_type != tx2.tx_type {
-        return false;
-    }
-    if tx1.from_user_id != tx2.from_user_id {
-        return false;
-    }
-    if tx1.to_user_id != tx2.to_user_id {
-        return false;
-    }
-    if tx1.amount != tx2.amount {
-        return false;
-    }
-    if tx1.timestamp != tx2.timestamp {
-        return false;
-    }
-    if !args.ignore_status && tx1.status != tx2.status {
-        return false;
-    }
-    if !args.ignore_description && tx1.description != tx2.description {
-        return false;
-    }
-    true
+    Ok(transactions)
 }
 
-fn print_differences(tx1: &Transaction, tx2: &Transaction, args: &Args) {
-    if tx1.tx_id != tx2.tx_id {
-        println!("  TX_ID: {} != {}", tx1.tx_id, tx2.tx_id);
-    }
-    if tx1.tx_type != tx2.tx_type {
-        println!("  TX_TYPE: {:?} != {:?}", tx1.tx_type, tx2.tx_type);
-    }
-    if tx1.from_user_id != tx2.from_user_id {
-        println!(
-            "  FROM_USER_ID: {} != {}",
-            tx1.from_user_id, tx2.from_user_id
-        );
-    }
-    if tx1.to_user_id != tx2.to_user_id {
-        println!("  TO_USER_ID: {} != {}", tx1.to_user_id, tx2.to_user_id);
-    }
-    if tx1.amount != tx2.amount {
-        println!("  AMOUNT: {} != {}", tx1.amount, tx2.amount);
-    }
-    if tx1.timestamp != tx2.timestamp {
-        println!("  TIMESTAMP: {} != {}", tx1.timestamp, tx2.timestamp);
-    }
-    if !args.ignore_status && tx1.status != tx2.status {
-        println!("  STATUS: {:?} != {:?}", tx1.status, tx2.status);
-    }
-    if !args.ignore_description && tx1.description != tx2.description {
-        println!(
-            "  DESCRIPTION: '{}' != '{}'",
-            tx1.description, tx2.description
-        );
+fn print_differences(diffs: &[FieldDiff]) {
+    for diff in diffs {
+        if diff.field == Field::Description {
+            println!(
+                "  {}: '{}' != '{}'",
+                diff.field.name(),
+                diff.left,
+                diff.right
+            );
+        } else {
+            println!("  {}: {} != {}", diff.field.name(), diff.left, diff.right);
+        }
     }
 }
 #[cfg(test)]
 mod tests {
     use super::*;
-    use parser_lib::{TransactionStatus, TransactionType};
+    use parser_lib::{TransactionStatus, TransactionType, diff_transactions};
     use std::io::Write;
     use tempfile::NamedTempFile;
 
+    fn transactions_equal(tx1: &Transaction, tx2: &Transaction, args: &Args) -> bool {
+        diff_transactions(tx1, tx2, &args.compare_options()).is_empty()
+    }
+
     fn create_test_transaction(id: u64) -> Transaction {
         Transaction {
             tx_id: id,
@@ -275,6 +345,7 @@ mod tests {
             timestamp: 1672531200000,
             status: TransactionStatus::Success,
             description: format!("Test transaction {}", id),
+            currency: None,
         }
     }
 
@@ -285,12 +356,16 @@ mod tests {
 
         let args = Args {
             file1: PathBuf::from("test1.csv"),
-            format1: Format::Csv,
+            format1: Some(Format::Csv),
             file2: PathBuf::from("test2.csv"),
-            format2: Format::Csv,
+            format2: Some(Format::Csv),
             verbose: false,
+            ignore: Vec::new(),
             ignore_description: false,
             ignore_status: false,
+            byte_compare: false,
+            sort_before_compare: false,
+            lang: Lang::Ru,
         };
 
         assert!(transactions_equal(&tx1, &tx2, &args));
@@ -305,12 +380,16 @@ mod tests {
 
         let args = Args {
             file1: PathBuf::from("test1.csv"),
-            format1: Format::Csv,
+            format1: Some(Format::Csv),
             file2: PathBuf::from("test2.csv"),
-            format2: Format::Csv,
+            format2: Some(Format::Csv),
             verbose: false,
+            ignore: Vec::new(),
             ignore_description: true,
             ignore_status: false,
+            byte_compare: false,
+            sort_before_compare: false,
+            lang: Lang::Ru,
         };
 
         assert!(transactions_equal(&tx1, &tx2, &args));
@@ -325,17 +404,69 @@ mod tests {
 
         let args = Args {
             file1: PathBuf::from("test1.csv"),
-            format1: Format::Csv,
+            format1: Some(Format::Csv),
             file2: PathBuf::from("test2.csv"),
-            format2: Format::Csv,
+            format2: Some(Format::Csv),
             verbose: false,
+            ignore: Vec::new(),
             ignore_description: false,
             ignore_status: true,
+            byte_compare: false,
+            sort_before_compare: false,
+            lang: Lang::Ru,
         };
 
         assert!(transactions_equal(&tx1, &tx2, &args));
     }
 
+    #[test]
+    fn test_transactions_equal_ignore_field_list() {
+        let tx1 = create_test_transaction(1001);
+        let mut tx2 = create_test_transaction(1001);
+        tx2.timestamp += 60000;
+        tx2.from_user_id = 77;
+
+        let args = Args::try_parse_from([
+            "comparer",
+            "--file1",
+            "test1.csv",
+            "--format1",
+            "csv",
+            "--file2",
+            "test2.csv",
+            "--format2",
+            "csv",
+            "--ignore",
+            "timestamp,from_user_id",
+        ])
+        .unwrap();
+
+        assert_eq!(args.ignore, vec![Field::Timestamp, Field::FromUserId]);
+        assert!(transactions_equal(&tx1, &tx2, &args));
+
+        tx2.amount += 1;
+        assert!(!transactions_equal(&tx1, &tx2, &args));
+    }
+
+    #[test]
+    fn test_ignore_rejects_unknown_field() {
+        let result = Args::try_parse_from([
+            "comparer",
+            "--file1",
+            "a.csv",
+            "--format1",
+            "csv",
+            "--file2",
+            "b.csv",
+            "--format2",
+            "csv",
+            "--ignore",
+            "fee",
+        ]);
+
+        assert!(result.is_err());
+    }
+
     #[test]
     fn test_transactions_not_equal() {
         let tx1 = create_test_transaction(1001);
@@ -343,12 +474,16 @@ mod tests {
 
         let args = Args {
             file1: PathBuf::from("test1.csv"),
-            format1: Format::Csv,
+            format1: Some(Format::Csv),
             file2: PathBuf::from("test2.csv"),
-            format2: Format::Csv,
+            format2: Some(Format::Csv),
             verbose: false,
+            ignore: Vec::new(),
             ignore_description: false,
             ignore_status: false,
+            byte_compare: false,
+            sort_before_compare: false,
+            lang: Lang::Ru,
         };
 
         assert!(!transactions_equal(&tx1, &tx2, &args));
@@ -366,7 +501,8 @@ mod tests {
             "1001,DEPOSIT,0,501,50000,1672531200000,SUCCESS,\"Test\""
         )?;
 
-        let transactions = read_transactions(&file.path().to_path_buf(), &Format::Csv)?;
+        let transactions =
+            read_transactions(&file.path().to_path_buf(), &Format::Csv, false, Lang::Ru)?;
         assert_eq!(transactions.len(), 1);
         assert_eq!(transactions[0].tx_id, 1001);
 
@@ -385,7 +521,8 @@ mod tests {
         writeln!(file, "STATUS: SUCCESS")?;
         writeln!(file, "DESCRIPTION: \"Test\"")?;
 
-        let transactions = read_transactions(&file.path().to_path_buf(), &Format::Txt)?;
+        let transactions =
+            read_transactions(&file.path().to_path_buf(), &Format::Txt, false, Lang::Ru)?;
         assert_eq!(transactions.len(), 1);
         assert_eq!(transactions[0].tx_id, 1001);
 
@@ -401,45 +538,57 @@ mod tests {
 
         let args = Args {
             file1: PathBuf::from("test1.csv"),
-            format1: Format::Csv,
+            format1: Some(Format::Csv),
             file2: PathBuf::from("test2.csv"),
-            format2: Format::Csv,
+            format2: Some(Format::Csv),
             verbose: false,
+            ignore: Vec::new(),
             ignore_description: false,
             ignore_status: false,
+            byte_compare: false,
+            sort_before_compare: false,
+            lang: Lang::Ru,
         };
 
-        print_differences(&tx1, &tx2, &args);
+        print_differences(&diff_transactions(&tx1, &tx2, &args.compare_options()));
     }
 
     #[test]
     fn test_compare_empty_lists() {
         let args = Args {
             file1: PathBuf::from("test1.csv"),
-            format1: Format::Csv,
+            format1: Some(Format::Csv),
             file2: PathBuf::from("test2.csv"),
-            format2: Format::Csv,
+            format2: Some(Format::Csv),
             verbose: false,
+            ignore: Vec::new(),
             ignore_description: false,
             ignore_status: false,
+            byte_compare: false,
+            sort_before_compare: false,
+            lang: Lang::Ru,
         };
 
         let empty: Vec<Transaction> = Vec::new();
         let result = compare_transactions(&empty, &empty, &args);
         assert!(result.is_ok());
-        assert_eq!(result.unwrap(), true);
+        assert!(result.unwrap());
     }
 
     #[test]
     fn test_compare_different_lengths() {
         let args = Args {
             file1: PathBuf::from("test1.csv"),
-            format1: Format::Csv,
+            format1: Some(Format::Csv),
             file2: PathBuf::from("test2.csv"),
-            format2: Format::Csv,
+            format2: Some(Format::Csv),
             verbose: false,
+            ignore: Vec::new(),
             ignore_description: false,
             ignore_status: false,
+            byte_compare: false,
+            sort_before_compare: false,
+            lang: Lang::Ru,
         };
 
         let tx1 = create_test_transaction(1001);
@@ -450,19 +599,23 @@ mod tests {
 
         let result = compare_transactions(&list1, &list2, &args);
         assert!(result.is_ok());
-        assert_eq!(result.unwrap(), false);
+        assert!(!result.unwrap());
     }
 
     #[test]
     fn test_compare_identical_lists() {
         let args = Args {
             file1: PathBuf::from("test1.csv"),
-            format1: Format::Csv,
+            format1: Some(Format::Csv),
             file2: PathBuf::from("test2.csv"),
-            format2: Format::Csv,
+            format2: Some(Format::Csv),
             verbose: false,
+            ignore: Vec::new(),
             ignore_description: false,
             ignore_status: false,
+            byte_compare: false,
+            sort_before_compare: false,
+            lang: Lang::Ru,
         };
 
         let tx1 = create_test_transaction(1001);
@@ -473,19 +626,23 @@ mod tests {
 
         let result = compare_transactions(&list1, &list2, &args);
         assert!(result.is_ok());
-        assert_eq!(result.unwrap(), true);
+        assert!(result.unwrap());
     }
 
     #[test]
     fn test_compare_lists_with_differences() {
         let args = Args {
             file1: PathBuf::from("test1.csv"),
-            format1: Format::Csv,
+            format1: Some(Format::Csv),
             file2: PathBuf::from("test2.csv"),
-            format2: Format::Csv,
+            format2: Some(Format::Csv),
             verbose: false,
+            ignore: Vec::new(),
             ignore_description: false,
             ignore_status: false,
+            byte_compare: false,
+            sort_before_compare: false,
+            lang: Lang::Ru,
         };
 
         let tx1 = create_test_transaction(1001);
@@ -497,6 +654,58 @@ mod tests {
 
         let result = compare_transactions(&list1, &list2, &args);
         assert!(result.is_ok());
-        assert_eq!(result.unwrap(), false);
+        assert!(!result.unwrap());
+    }
+
+    #[cfg(feature = "logging")]
+    #[test]
+    fn test_info_logged_per_file_read() {
+        use std::sync::Mutex;
+
+        static RECORDS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+        struct CaptureLogger;
+
+        impl log::Log for CaptureLogger {
+            fn enabled(&self, _metadata: &log::Metadata) -> bool {
+                true
+            }
+
+            fn log(&self, record: &log::Record) {
+                RECORDS
+                    .lock()
+                    .unwrap()
+                    .push(format!("{} {}", record.level(), record.args()));
+            }
+
+            fn flush(&self) {}
+        }
+
+        static LOGGER: CaptureLogger = CaptureLogger;
+        log::set_logger(&LOGGER).unwrap();
+        log::set_max_level(log::LevelFilter::Info);
+
+        let mut csv_file = NamedTempFile::new().unwrap();
+        writeln!(
+            csv_file,
+            "TX_ID,TX_TYPE,FROM_USER_ID,TO_USER_ID,AMOUNT,TIMESTAMP,STATUS,DESCRIPTION"
+        )
+        .unwrap();
+        writeln!(
+            csv_file,
+            "1001,DEPOSIT,0,501,50000,1672531200000,SUCCESS,\"Test\""
+        )
+        .unwrap();
+
+        let path = csv_file.path().to_path_buf();
+        read_transactions(&path, &Format::Csv, false, Lang::Ru).unwrap();
+        read_transactions(&path, &Format::Csv, false, Lang::Ru).unwrap();
+
+        let records = RECORDS.lock().unwrap();
+        let reads: Vec<&String> = records
+            .iter()
+            .filter(|r| r.starts_with("INFO Прочитано транзакций") && r.ends_with(": 1"))
+            .collect();
+        assert_eq!(reads.len(), 2, "{:?}", records);
     }
 }
@@ -0,0 +1,149 @@
+use clap::Parser;
+use parser_lib::BinaryRecord;
+use std::io::Cursor;
+use std::path::PathBuf;
+
+/// Смещение поля длины описания от начала записи
+const DESC_LEN_OFFSET: usize = 50;
+/// Размер фиксированной части записи без магического числа и поля размера
+const FIXED_RECORD_SIZE: u64 = 46;
+/// Количество байт вокруг места ошибки, выводимых в дампе
+const ERROR_CONTEXT: usize = 32;
+
+#[derive(Parser, Debug)]
+#[command(name = "inspect")]
+#[command(about = "Диагностика бинарных файлов YPBank: значения полей и hex-дамп записей", long_about = None)]
+#[command(version = env!("CARGO_PKG_VERSION"))]
+struct Args {
+    #[arg(short, long, value_name = "FILE")]
+    input: PathBuf,
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = Args::parse();
+
+    let data = std::fs::read(&args.input).map_err(|e| {
+        format!(
+            "Не удалось прочитать файл '{}': {}",
+            args.input.display(),
+            e
+        )
+    })?;
+
+    println!("=== Диагностика бинарного файла ===");
+    println!("Файл: {}", args.input.display());
+    println!("Размер: {} байт", data.len());
+
+    let mut offset = 0;
+    let mut index = 0;
+
+    while offset < data.len() {
+        index += 1;
+        let mut cursor = Cursor::new(&data[offset..]);
+
+        match BinaryRecord::from_read(&mut cursor) {
+            Ok(record) => {
+                let consumed = cursor.position() as usize;
+                let (declared, computed) = record_sizes(&data, offset);
+
+                println!("\nЗапись {}: смещение {} (0x{:08x})", index, offset, offset);
+                println!("  TX_ID: {}", record.tx_id);
+                println!("  TX_TYPE: {:?}", record.tx_type);
+                println!("  FROM_USER_ID: {}", record.from_user_id);
+                println!("  TO_USER_ID: {}", record.to_user_id);
+                println!("  AMOUNT: {}", record.amount);
+                println!("  TIMESTAMP: {}", record.timestamp);
+                println!("  STATUS: {:?}", record.status);
+                println!("  DESCRIPTION: {:?}", record.description);
+                if let Some(currency) = &record.currency {
+                    println!("  CURRENCY: {}", currency);
+                }
+                println!(
+                    "  Размер записи: заявлен {}, вычислен {}",
+                    format_size(declared),
+                    format_size(computed)
+                );
+                hex_dump(&data, offset, offset + consumed);
+
+                offset += consumed;
+            }
+            Err(e) => {
+                let failure_offset = offset + cursor.position() as usize;
+                let (declared, computed) = record_sizes(&data, offset);
+
+                eprintln!("\nЗапись {}: смещение {} (0x{:08x})", index, offset, offset);
+                eprintln!("Ошибка: {}", e);
+                eprintln!(
+                    "Место ошибки: смещение {} (0x{:08x})",
+                    failure_offset, failure_offset
+                );
+                eprintln!(
+                    "  Размер записи: заявлен {}, вычислен {}",
+                    format_size(declared),
+                    format_size(computed)
+                );
+
+                let start = failure_offset.saturating_sub(ERROR_CONTEXT);
+                let end = (failure_offset + ERROR_CONTEXT).min(data.len());
+                println!("\nБайты вокруг места ошибки:");
+                hex_dump(&data, start, end);
+
+                std::process::exit(1);
+            }
+        }
+    }
+
+    println!("\nВсего записей: {}", index);
+
+    Ok(())
+}
+
+/// Возвращает заявленный размер записи и размер, вычисленный по длинам
+/// описания и, если заявленный размер больше, блока валюты
+fn record_sizes(data: &[u8], offset: usize) -> (Option<u32>, Option<u64>) {
+    let read_u32 = |at: usize| {
+        data.get(at..at + 4)
+            .map(|bytes| u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    };
+
+    let declared = read_u32(offset + 4);
+    let computed = read_u32(offset + DESC_LEN_OFFSET).map(|len| {
+        let size = FIXED_RECORD_SIZE + len as u64;
+        match declared {
+            Some(declared) if declared as u64 > size => read_u32(offset + 8 + size as usize)
+                .map_or(size, |currency_len| size + 4 + currency_len as u64),
+            _ => size,
+        }
+    });
+
+    (declared, computed)
+}
+
+fn format_size<T: std::fmt::Display>(size: Option<T>) -> String {
+    match size {
+        Some(size) => size.to_string(),
+        None => "<нет данных>".to_string(),
+    }
+}
+
+/// Печатает hex-дамп диапазона `start..end` с абсолютными смещениями
+fn hex_dump(data: &[u8], start: usize, end: usize) {
+    for line_start in (start..end).step_by(16) {
+        let line_end = (line_start + 16).min(end);
+        let bytes = &data[line_start..line_end];
+
+        let hex: Vec<String> = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+        let ascii: String = bytes
+            .iter()
+            .map(|&b| {
+                if b.is_ascii_graphic() || b == b' ' {
+                    b as char
+                } else {
+                    '.'
+                }
+            })
+            .collect();
+
+        println!("  {:08x}  {:<47}  |{}|", line_start, hex.join(" "), ascii);
+    }
+}
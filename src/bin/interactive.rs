@@ -0,0 +1,164 @@
+use clap::Parser;
+use parser_lib::{
+    Format, ParseOptions, TextParser, Transaction, TransactionStatus, TransactionType,
+    filter_by_type,
+};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, IsTerminal, Write};
+use std::path::PathBuf;
+
+const COMMANDS_HELP: &str = "\
+Команды:
+  count                  количество записей в текущей выборке
+  show N                 вывести запись N (с единицы) в текстовом формате
+  filter type=TYPE       оставить записи типа deposit, transfer или withdrawal
+  filter status=STATUS   оставить записи со статусом success, failure, pending или reversed
+  reset                  сбросить фильтры
+  stats                  количество и сумма по типам транзакций
+  help                   список команд
+  quit                   выход";
+
+#[derive(Parser, Debug)]
+#[command(name = "interactive")]
+#[command(about = "Интерактивный просмотр файла транзакций: файл загружается один раз, команды читаются из stdin", long_about = None)]
+#[command(after_help = COMMANDS_HELP)]
+#[command(version = env!("CARGO_PKG_VERSION"))]
+struct Args {
+    #[arg(short, long, value_name = "FILE")]
+    input: PathBuf,
+
+    #[arg(short, long, value_name = "FORMAT", value_enum, ignore_case = true)]
+    format: Format,
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = Args::parse();
+
+    let file = File::open(&args.input)
+        .map_err(|e| format!("Не удалось открыть файл '{}': {}", args.input.display(), e))?;
+    let all = args
+        .format
+        .parse_records(BufReader::new(file), &ParseOptions::default())?;
+    let mut view = all.clone();
+
+    let stdin = io::stdin();
+    let interactive = stdin.is_terminal();
+    if interactive {
+        println!(
+            "Загружено записей: {}. Введите help для списка команд.",
+            all.len()
+        );
+    }
+
+    let mut lines = stdin.lock().lines();
+    loop {
+        if interactive {
+            print!("> ");
+            io::stdout().flush()?;
+        }
+
+        let Some(line) = lines.next() else {
+            break;
+        };
+        let line = line?;
+        let mut words = line.split_whitespace();
+
+        match (words.next(), words.next()) {
+            (None, _) => {}
+            (Some("quit"), _) => break,
+            (Some("help"), _) => println!("{}", COMMANDS_HELP),
+            (Some("count"), _) => println!("Записей: {}", view.len()),
+            (Some("reset"), _) => {
+                view = all.clone();
+                println!("Записей: {}", view.len());
+            }
+            (Some("show"), Some(number)) => show(&view, number)?,
+            (Some("filter"), Some(condition)) => match apply_filter(&view, condition) {
+                Ok(filtered) => {
+                    view = filtered;
+                    println!("Записей: {}", view.len());
+                }
+                Err(e) => eprintln!("Ошибка: {}", e),
+            },
+            (Some("stats"), _) => print_stats(&view),
+            (Some(command), _) => {
+                eprintln!("Ошибка: неизвестная команда '{}'. Введите help", command)
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Выводит запись с номером `number` (с единицы) в текстовом формате
+fn show(view: &[Transaction], number: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let index = match number.parse::<usize>() {
+        Ok(n) if (1..=view.len()).contains(&n) => n - 1,
+        _ => {
+            eprintln!(
+                "Ошибка: номер записи должен быть от 1 до {}, получено '{}'",
+                view.len(),
+                number
+            );
+            return Ok(());
+        }
+    };
+
+    let mut stdout = io::stdout().lock();
+    TextParser::write_records(std::slice::from_ref(&view[index]), &mut stdout)?;
+    Ok(())
+}
+
+/// Применяет условие вида `type=transfer` или `status=success`
+fn apply_filter(view: &[Transaction], condition: &str) -> Result<Vec<Transaction>, String> {
+    let (key, value) = condition
+        .split_once('=')
+        .ok_or_else(|| format!("ожидается KEY=VALUE, получено '{}'", condition))?;
+
+    match key.to_lowercase().as_str() {
+        "type" => {
+            let tx_type = match value.to_lowercase().as_str() {
+                "deposit" => TransactionType::Deposit,
+                "transfer" => TransactionType::Transfer,
+                "withdrawal" => TransactionType::Withdrawal,
+                _ => return Err(format!("неизвестный тип '{}'", value)),
+            };
+            Ok(filter_by_type(view, tx_type))
+        }
+        "status" => {
+            let status = match value.to_lowercase().as_str() {
+                "success" => TransactionStatus::Success,
+                "failure" => TransactionStatus::Failure,
+                "pending" => TransactionStatus::Pending,
+                "reversed" => TransactionStatus::Reversed,
+                _ => return Err(format!("неизвестный статус '{}'", value)),
+            };
+            Ok(view
+                .iter()
+                .filter(|tx| tx.status == status)
+                .cloned()
+                .collect())
+        }
+        _ => Err(format!("неизвестное поле фильтра '{}'", key)),
+    }
+}
+
+/// Печатает количество записей и сумму по каждому типу транзакций
+fn print_stats(view: &[Transaction]) {
+    println!("Записей: {}", view.len());
+    for tx_type in [
+        TransactionType::Deposit,
+        TransactionType::Transfer,
+        TransactionType::Withdrawal,
+    ] {
+        let selected = filter_by_type(view, tx_type);
+        // Сумма в i128: сложение крупных сумм i64 не переполняется
+        let total: i128 = selected.iter().map(|tx| i128::from(tx.amount)).sum();
+        println!(
+            "  {:?}: {} записей, сумма {}",
+            tx_type,
+            selected.len(),
+            total
+        );
+    }
+}
@@ -0,0 +1,112 @@
+use clap::{Parser, ValueEnum};
+use parser_lib::{
+    CompareOptions, Field, Format, ParseOptions, ParserError, Transaction, diff_transactions,
+    generate_sample,
+};
+
+#[derive(Parser, Debug)]
+#[command(name = "selftest")]
+#[command(about = "Проверка конвертации A -> B -> A для всех пар форматов на сгенерированных данных", long_about = None)]
+#[command(version = env!("CARGO_PKG_VERSION"))]
+struct Args {
+    /// Количество сгенерированных транзакций
+    #[arg(short = 'n', long, default_value_t = 50)]
+    count: usize,
+
+    /// Начальное значение генератора
+    #[arg(long, default_value_t = 1)]
+    seed: u64,
+}
+
+fn main() {
+    let args = Args::parse();
+    let formats = Format::value_variants();
+    let original = generate_sample(args.count, args.seed);
+
+    println!(
+        "Проверка конвертации A -> B -> A на {} записях (seed {})",
+        original.len(),
+        args.seed
+    );
+
+    print!("{:>6}", "A \\ B");
+    for to in formats {
+        print!("{:>6}", to.extension());
+    }
+    println!();
+
+    let mut failures = Vec::new();
+    for from in formats {
+        print!("{:>6}", from.extension());
+        for to in formats {
+            match check_pair(&original, *from, *to) {
+                Ok(()) => print!("{:>6}", "OK"),
+                Err(reason) => {
+                    print!("{:>6}", "FAIL");
+                    failures.push((*from, *to, reason));
+                }
+            }
+        }
+        println!();
+    }
+
+    for (from, to, reason) in &failures {
+        eprintln!(
+            "{} -> {} -> {}: {}",
+            from.extension(),
+            to.extension(),
+            from.extension(),
+            reason
+        );
+    }
+
+    if !failures.is_empty() {
+        eprintln!("Ошибок: {}", failures.len());
+        std::process::exit(1);
+    }
+    println!("Все пары форматов прошли проверку");
+}
+
+/// Конвертирует записи из `from` в `to` и обратно и сравнивает с исходными
+///
+/// Поля, которые теряет один из форматов пары, не сравниваются.
+fn check_pair(original: &[Transaction], from: Format, to: Format) -> Result<(), String> {
+    let source = convert(original, from).map_err(|e| format!("{:?}: {}", from, e))?;
+    let there = convert(&source, to).map_err(|e| format!("{:?}: {}", to, e))?;
+    let back = convert(&there, from).map_err(|e| format!("{:?}: {}", from, e))?;
+
+    if back.len() != original.len() {
+        return Err(format!(
+            "record count changed: {} -> {}",
+            original.len(),
+            back.len()
+        ));
+    }
+
+    let mut options = CompareOptions::default();
+    for name in from.lossy_fields().iter().chain(to.lossy_fields()) {
+        let field: Field = name.parse().map_err(|e: ParserError| e.to_string())?;
+        options = options.ignore(field);
+    }
+
+    for (index, (expected, actual)) in original.iter().zip(&back).enumerate() {
+        if let Some(diff) = diff_transactions(expected, actual, &options).first() {
+            return Err(format!(
+                "record {}: {} differs: '{}' vs '{}'",
+                index + 1,
+                diff.field.name(),
+                diff.left,
+                diff.right
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Записывает транзакции в формат и читает их обратно
+fn convert(records: &[Transaction], format: Format) -> Result<Vec<Transaction>, ParserError> {
+    let mut buffer = Vec::new();
+    format.write_records(records, &mut buffer)?;
+    format.parse_records(buffer.as_slice(), &ParseOptions::default())
+}
@@ -1,6 +1,6 @@
 use parser_lib::{
-    BinaryParser, BinaryRecord, BinaryTransactions, ParseFromRead, Transaction, TransactionStatus,
-    TransactionType,
+    BinaryParser, BinaryRecord, Format, Transaction, TransactionStatus, TransactionType,
+    Transactions,
 };
 use std::io::Cursor;
 use std::slice::from_ref;
@@ -18,6 +18,7 @@ fn main() -> Result<(), parser_lib::ParserError> {
         timestamp: 1672531200000,
         status: TransactionStatus::Success,
         description: "Initial deposit".to_string(),
+        currency: None,
     };
 
     let mut buffer = Vec::new();
@@ -49,6 +50,7 @@ fn main() -> Result<(), parser_lib::ParserError> {
             timestamp: 1672531200000,
             status: TransactionStatus::Success,
             description: "First deposit".to_string(),
+            currency: None,
         },
         BinaryRecord {
             tx_id: 1002,
@@ -59,6 +61,7 @@ fn main() -> Result<(), parser_lib::ParserError> {
             timestamp: 1672534800000,
             status: TransactionStatus::Failure,
             description: "Failed transfer".to_string(),
+            currency: None,
         },
         BinaryRecord {
             tx_id: 1003,
@@ -69,6 +72,7 @@ fn main() -> Result<(), parser_lib::ParserError> {
             timestamp: 1672538400000,
             status: TransactionStatus::Pending,
             description: "ATM withdrawal".to_string(),
+            currency: None,
         },
     ];
 
@@ -115,6 +119,7 @@ fn main() -> Result<(), parser_lib::ParserError> {
         timestamp: 1672531200000,
         status: TransactionStatus::Success,
         description: String::new(),
+        currency: None,
     };
 
     let mut buffer3 = Vec::new();
@@ -128,7 +133,7 @@ fn main() -> Result<(), parser_lib::ParserError> {
         println!("   Размер записи: {} байт", buffer3.len());
     }
 
-    println!("\n6. Тест трейта ParseFromRead для BinaryTransactions:");
+    println!("\n6. Тест чтения через Transactions::read:");
     let test_transaction = Transaction {
         tx_id: 7777,
         tx_type: TransactionType::Deposit,
@@ -138,16 +143,16 @@ fn main() -> Result<(), parser_lib::ParserError> {
         timestamp: 1672531200000,
         status: TransactionStatus::Success,
         description: "Test ParseFromRead".to_string(),
+        currency: None,
     };
 
     let mut test_buffer = Vec::new();
     BinaryParser::write_records(from_ref(&test_transaction), &mut test_buffer)?;
 
-    let mut cursor = Cursor::new(&test_buffer);
-    let bin_transactions: BinaryTransactions = ParseFromRead::parse(&mut cursor)?;
+    let bin_transactions = Transactions::read(Format::Bin, Cursor::new(&test_buffer))?;
 
     println!(
-        "   Прочитано через трейт: {} транзакций",
+        "   Прочитано через Transactions: {} транзакций",
         bin_transactions.0.len()
     );
     if !bin_transactions.0.is_empty() {
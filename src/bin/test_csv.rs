@@ -1,5 +1,5 @@
 use parser_lib::{
-    CsvParser, CsvTransactions, ParseFromRead, Transaction, TransactionStatus, TransactionType,
+    CsvParser, Format, Transaction, TransactionStatus, TransactionType, Transactions,
 };
 use std::io::Cursor;
 use std::slice::from_ref;
@@ -59,6 +59,7 @@ fn main() -> Result<(), parser_lib::ParserError> {
         timestamp: 1672531200000,
         status: TransactionStatus::Success,
         description: r#"Payment with "quotes" and, comma inside"#.to_string(),
+        currency: None,
     };
 
     let mut buffer2 = Vec::new();
@@ -90,6 +91,7 @@ fn main() -> Result<(), parser_lib::ParserError> {
             timestamp: 1672642800000,
             status: TransactionStatus::Success,
             description: "Salary deposit".to_string(),
+            currency: None,
         },
         Transaction {
             tx_id: 3002,
@@ -100,6 +102,7 @@ fn main() -> Result<(), parser_lib::ParserError> {
             timestamp: 1672646400000,
             status: TransactionStatus::Pending,
             description: "Rent payment".to_string(),
+            currency: None,
         },
     ];
 
@@ -127,15 +130,14 @@ fn main() -> Result<(), parser_lib::ParserError> {
         );
     }
 
-    println!("\n7. Тест трейта ParseFromRead для CsvTransactions:");
+    println!("\n7. Тест чтения через Transactions::read:");
     let csv_test_data = r#"TX_ID,TX_TYPE,FROM_USER_ID,TO_USER_ID,AMOUNT,TIMESTAMP,STATUS,DESCRIPTION
 7777,DEPOSIT,0,888,9999,1672531200000,SUCCESS,"Test ParseFromRead""#;
 
-    let mut cursor = Cursor::new(csv_test_data);
-    let csv_transactions: CsvTransactions = ParseFromRead::parse(&mut cursor)?;
+    let csv_transactions = Transactions::read(Format::Csv, Cursor::new(csv_test_data))?;
 
     println!(
-        "   Прочитано через трейт: {} транзакций",
+        "   Прочитано через Transactions: {} транзакций",
         csv_transactions.0.len()
     );
     if !csv_transactions.0.is_empty() {
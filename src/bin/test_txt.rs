@@ -1,5 +1,5 @@
 use parser_lib::{
-    ParseFromRead, TextParser, TextTransactions, Transaction, TransactionStatus, TransactionType,
+    Format, TextParser, Transaction, TransactionStatus, TransactionType, Transactions,
 };
 use std::io::Cursor;
 use std::slice::from_ref;
@@ -174,6 +174,7 @@ DESCRIPTION: "Record number 12""#;
         timestamp: 1672531200000,
         status: TransactionStatus::Success,
         description: r#"Payment with "quotes" inside"#.to_string(),
+        currency: None,
     };
 
     let mut buffer2 = Vec::new();
@@ -249,7 +250,7 @@ DESCRIPTION: "Another one"
         messy_transactions.len()
     );
 
-    println!("\n7. Тест трейта ParseFromRead для TextTransactions:");
+    println!("\n7. Тест чтения через Transactions::read:");
     let text_test_data = r#"TX_ID: 7777
 TX_TYPE: DEPOSIT
 FROM_USER_ID: 0
@@ -259,11 +260,10 @@ TIMESTAMP: 1672531200000
 STATUS: SUCCESS
 DESCRIPTION: "Test ParseFromRead""#;
 
-    let mut cursor = Cursor::new(text_test_data);
-    let text_transactions: TextTransactions = ParseFromRead::parse(&mut cursor)?;
+    let text_transactions = Transactions::read(Format::Txt, Cursor::new(text_test_data))?;
 
     println!(
-        "   Прочитано через трейт: {} транзакций",
+        "   Прочитано через Transactions: {} транзакций",
         text_transactions.0.len()
     );
     if !text_transactions.0.is_empty() {
@@ -0,0 +1,151 @@
+use crate::{BinaryRecord, ParserError, Transaction};
+use byteorder::{BigEndian, ReadBytesExt};
+use std::fs::File;
+use std::io::{BufReader, Seek, SeekFrom};
+use std::path::Path;
+
+/// Размер одного элемента индекса в байтах
+const OFFSET_LEN: u64 = 8;
+
+/// Чтение записей бинарного файла по номеру через индекс смещений
+///
+/// Индекс создаётся [`BinaryParser::write_records_indexed`](crate::BinaryParser::write_records_indexed)
+/// и хранит смещение начала каждой записи. `get` переходит к записи
+/// напрямую, не разбирая предыдущие.
+///
+/// # Пример
+/// ```no_run
+/// use parser_lib::BinaryIndexedReader;
+///
+/// let mut reader = BinaryIndexedReader::open("records.bin", "records.idx").unwrap();
+/// let tx = reader.get(5).unwrap();
+/// println!("TX_ID: {}", tx.tx_id);
+/// ```
+pub struct BinaryIndexedReader {
+    data: BufReader<File>,
+    offsets: Vec<u64>,
+}
+
+impl BinaryIndexedReader {
+    /// Открывает файл данных и загружает индекс
+    ///
+    /// # Аргументы
+    /// * `data_path` - Путь к бинарному файлу данных
+    /// * `idx_path` - Путь к файлу индекса
+    ///
+    /// # Возвращает
+    /// * `Ok(BinaryIndexedReader)` - Читатель с загруженным индексом
+    /// * `Err(ParserError)` - Ошибка открытия файлов или повреждённый индекс
+    pub fn open<P: AsRef<Path>, Q: AsRef<Path>>(
+        data_path: P,
+        idx_path: Q,
+    ) -> Result<Self, ParserError> {
+        let data = File::open(data_path)?;
+        let data_len = data.metadata()?.len();
+
+        let index = File::open(idx_path)?;
+        let index_len = index.metadata()?.len();
+        if index_len % OFFSET_LEN != 0 {
+            return Err(ParserError::Parse(format!(
+                "Index size {} is not a multiple of {} bytes",
+                index_len, OFFSET_LEN
+            )));
+        }
+
+        let mut index = BufReader::new(index);
+        let mut offsets = Vec::with_capacity((index_len / OFFSET_LEN) as usize);
+        for _ in 0..index_len / OFFSET_LEN {
+            let offset = index.read_u64::<BigEndian>()?;
+            if offset >= data_len {
+                return Err(ParserError::Parse(format!(
+                    "Index offset {} is beyond the data file size {}",
+                    offset, data_len
+                )));
+            }
+            offsets.push(offset);
+        }
+
+        Ok(BinaryIndexedReader {
+            data: BufReader::new(data),
+            offsets,
+        })
+    }
+
+    /// Возвращает количество записей в индексе
+    pub fn len(&self) -> usize {
+        self.offsets.len()
+    }
+
+    /// Проверяет, пуст ли индекс
+    pub fn is_empty(&self) -> bool {
+        self.offsets.is_empty()
+    }
+
+    /// Читает запись по номеру, начиная с нуля
+    ///
+    /// # Аргументы
+    /// * `n` - Номер записи
+    ///
+    /// # Возвращает
+    /// * `Ok(Transaction)` - Запись с номером `n`
+    /// * `Err(ParserError)` - Номер вне индекса или ошибка чтения записи
+    pub fn get(&mut self, n: usize) -> Result<Transaction, ParserError> {
+        let offset = *self.offsets.get(n).ok_or_else(|| {
+            ParserError::Parse(format!(
+                "Record {} is out of range: the index has {} records",
+                n,
+                self.offsets.len()
+            ))
+        })?;
+
+        self.data.seek(SeekFrom::Start(offset))?;
+        Ok(BinaryRecord::from_read_seek(&mut self.data)?.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BinaryParser, generate_sample};
+
+    #[test]
+    fn test_get_seeks_directly_to_record() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let data_path = dir.path().join("records.bin");
+        let idx_path = dir.path().join("records.idx");
+        let transactions = generate_sample(10, 3);
+
+        BinaryParser::write_records_indexed(&transactions, &data_path, &idx_path).unwrap();
+        assert_eq!(std::fs::metadata(&idx_path).unwrap().len(), 10 * OFFSET_LEN);
+
+        // Файл данных остаётся обычным бинарным форматом
+        let file = File::open(&data_path).unwrap();
+        assert_eq!(BinaryParser::parse_records(file).unwrap(), transactions);
+
+        // Портим первую запись: последовательное чтение невозможно, а get(5) её не читает
+        let mut data = std::fs::read(&data_path).unwrap();
+        data[..4].copy_from_slice(b"XXXX");
+        std::fs::write(&data_path, &data).unwrap();
+        assert!(BinaryParser::parse_records(data.as_slice()).is_err());
+
+        let mut reader = BinaryIndexedReader::open(&data_path, &idx_path).unwrap();
+        assert_eq!(reader.len(), 10);
+        assert_eq!(reader.get(5).unwrap(), transactions[5]);
+        assert_eq!(reader.get(9).unwrap(), transactions[9]);
+        assert!(reader.get(10).is_err());
+    }
+
+    #[test]
+    fn test_open_rejects_corrupt_index() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let data_path = dir.path().join("records.bin");
+        let idx_path = dir.path().join("records.idx");
+        BinaryParser::write_records_indexed(&generate_sample(2, 3), &data_path, &idx_path).unwrap();
+
+        std::fs::write(&idx_path, [0u8; 7]).unwrap();
+        assert!(BinaryIndexedReader::open(&data_path, &idx_path).is_err());
+
+        std::fs::write(&idx_path, u64::MAX.to_be_bytes()).unwrap();
+        assert!(BinaryIndexedReader::open(&data_path, &idx_path).is_err());
+    }
+}
@@ -0,0 +1,482 @@
+use crate::{Field, Transaction};
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// Настройки сравнения транзакций
+///
+/// По умолчанию сравниваются все поля.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CompareOptions {
+    /// Поля, различия в которых не учитываются
+    pub ignored_fields: HashSet<Field>,
+
+    /// Сопоставлять записи по TX_ID, а не по позиции
+    ///
+    /// Используется функцией `compare`: записи без пары попадают в
+    /// `CompareReport::only_in_a` и `CompareReport::only_in_b`. Повторяющиеся
+    /// TX_ID сопоставляются по порядку вхождений, поэтому лишний дубликат
+    /// тоже остаётся без пары.
+    pub by_id: bool,
+}
+
+impl CompareOptions {
+    /// Добавляет поле в список игнорируемых
+    ///
+    /// # Пример
+    /// ```
+    /// use parser_lib::{CompareOptions, Field};
+    ///
+    /// let options = CompareOptions::default()
+    ///     .ignore(Field::Timestamp)
+    ///     .ignore(Field::Description);
+    /// assert!(options.is_ignored(Field::Timestamp));
+    /// assert!(!options.is_ignored(Field::Amount));
+    /// ```
+    pub fn ignore(mut self, field: Field) -> Self {
+        self.ignored_fields.insert(field);
+        self
+    }
+
+    /// Проверяет, исключено ли поле из сравнения
+    pub fn is_ignored(&self, field: Field) -> bool {
+        self.ignored_fields.contains(&field)
+    }
+}
+
+/// Различие в одном поле двух транзакций
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldDiff {
+    /// Поле, в котором найдено различие
+    pub field: Field,
+
+    /// Значение в первой транзакции
+    pub left: String,
+
+    /// Значение во второй транзакции
+    pub right: String,
+}
+
+/// Находит различающиеся поля двух транзакций
+///
+/// # Аргументы
+/// * `tx1` - Первая транзакция
+/// * `tx2` - Вторая транзакция
+/// * `options` - Настройки сравнения
+///
+/// # Возвращает
+/// Список различий в каноническом порядке полей; пустой список
+/// означает, что транзакции совпадают
+///
+/// # Пример
+/// ```
+/// use parser_lib::{diff_transactions, CompareOptions, Field, Transaction, TransactionStatus, TransactionType};
+///
+/// let tx1 = Transaction {
+///     tx_id: 1,
+///     tx_type: TransactionType::Deposit,
+///     from_user_id: 0,
+///     to_user_id: 501,
+///     amount: 100,
+///     timestamp: 0,
+///     status: TransactionStatus::Success,
+///     description: "Test".to_string(),
+///     currency: None,
+/// };
+/// let mut tx2 = tx1.clone();
+/// tx2.amount = 200;
+///
+/// let diffs = diff_transactions(&tx1, &tx2, &CompareOptions::default());
+/// assert_eq!(diffs.len(), 1);
+/// assert_eq!(diffs[0].field, Field::Amount);
+/// ```
+pub fn diff_transactions(
+    tx1: &Transaction,
+    tx2: &Transaction,
+    options: &CompareOptions,
+) -> Vec<FieldDiff> {
+    Field::ALL
+        .into_iter()
+        .filter(|field| !options.is_ignored(*field))
+        .filter_map(|field| {
+            let left = field_value(tx1, field);
+            let right = field_value(tx2, field);
+            (left != right).then_some(FieldDiff { field, left, right })
+        })
+        .collect()
+}
+
+/// Различие пары записей при сравнении списков
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordMismatch {
+    /// Позиция записи в первом списке (с нуля)
+    pub index: usize,
+
+    /// TX_ID записи из первого списка
+    pub tx_id: u64,
+
+    /// Различающиеся поля
+    pub diffs: Vec<FieldDiff>,
+}
+
+/// Результат сравнения двух списков транзакций
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompareReport {
+    /// Списки совпадают с учётом настроек сравнения
+    pub identical: bool,
+
+    /// Количество записей в первом списке
+    pub len_a: usize,
+
+    /// Количество записей во втором списке
+    pub len_b: usize,
+
+    /// Пары записей с различиями
+    ///
+    /// При позиционном сравнении списков разной длины сравнивается
+    /// только общий префикс.
+    pub mismatches: Vec<RecordMismatch>,
+
+    /// TX_ID записей, которых нет во втором списке (только при `by_id`)
+    pub only_in_a: Vec<u64>,
+
+    /// TX_ID записей, которых нет в первом списке (только при `by_id`)
+    pub only_in_b: Vec<u64>,
+}
+
+/// Сравнивает два списка транзакций
+///
+/// По умолчанию записи сравниваются попарно по позиции. С
+/// `CompareOptions::by_id` записи сопоставляются по TX_ID, а записи
+/// без пары перечисляются в `only_in_a` и `only_in_b`.
+///
+/// # Аргументы
+/// * `a` - Первый список
+/// * `b` - Второй список
+/// * `options` - Настройки сравнения
+///
+/// # Возвращает
+/// Отчёт с различиями; `identical` равен `true`, если различий нет
+///
+/// # Пример
+/// ```
+/// use parser_lib::{compare, generate_sample, CompareOptions};
+///
+/// let a = generate_sample(3, 1);
+/// let mut b = a.clone();
+/// b[1].amount += 1;
+///
+/// let report = compare(&a, &b, &CompareOptions::default());
+/// assert!(!report.identical);
+/// assert_eq!(report.mismatches[0].index, 1);
+/// ```
+pub fn compare(a: &[Transaction], b: &[Transaction], options: &CompareOptions) -> CompareReport {
+    let mut mismatches = Vec::new();
+    let mut only_in_a = Vec::new();
+    let mut only_in_b = Vec::new();
+
+    let mut push_diffs = |index: usize, tx1: &Transaction, tx2: &Transaction| {
+        let diffs = diff_transactions(tx1, tx2, options);
+        if !diffs.is_empty() {
+            mismatches.push(RecordMismatch {
+                index,
+                tx_id: tx1.tx_id,
+                diffs,
+            });
+        }
+    };
+
+    if options.by_id {
+        // Позиции записей `b` для каждого TX_ID в порядке следования
+        let mut unmatched: HashMap<u64, VecDeque<usize>> = HashMap::new();
+        for (position, tx) in b.iter().enumerate() {
+            unmatched.entry(tx.tx_id).or_default().push_back(position);
+        }
+        let mut matched = vec![false; b.len()];
+
+        for (index, tx1) in a.iter().enumerate() {
+            match unmatched.get_mut(&tx1.tx_id).and_then(VecDeque::pop_front) {
+                Some(position) => {
+                    matched[position] = true;
+                    push_diffs(index, tx1, &b[position]);
+                }
+                None => only_in_a.push(tx1.tx_id),
+            }
+        }
+        only_in_b.extend(
+            b.iter()
+                .zip(&matched)
+                .filter(|(_, matched)| !**matched)
+                .map(|(tx, _)| tx.tx_id),
+        );
+    } else {
+        for (index, (tx1, tx2)) in a.iter().zip(b).enumerate() {
+            push_diffs(index, tx1, tx2);
+        }
+    }
+
+    let lengths_match = options.by_id || a.len() == b.len();
+    CompareReport {
+        identical: lengths_match
+            && mismatches.is_empty()
+            && only_in_a.is_empty()
+            && only_in_b.is_empty(),
+        len_a: a.len(),
+        len_b: b.len(),
+        mismatches,
+        only_in_a,
+        only_in_b,
+    }
+}
+
+/// Вычисляет отпечаток набора транзакций, не зависящий от формата и порядка
+///
+/// Каждая запись приводится к каноническому виду (игнорируемые поля
+/// пропускаются), канонические записи сортируются и подаются в SHA-256.
+/// Одинаковые по содержимому файлы в разных форматах дают один отпечаток.
+///
+/// # Аргументы
+/// * `transactions` - Транзакции для хэширования
+/// * `options` - Настройки сравнения: игнорируемые поля
+///
+/// # Возвращает
+/// 32-байтовый хэш SHA-256
+///
+/// # Пример
+/// ```
+/// use parser_lib::{fingerprint, generate_sample, CompareOptions};
+///
+/// let transactions = generate_sample(5, 1);
+/// let mut reversed = transactions.clone();
+/// reversed.reverse();
+///
+/// let options = CompareOptions::default();
+/// assert_eq!(fingerprint(&transactions, &options), fingerprint(&reversed, &options));
+/// ```
+pub fn fingerprint(transactions: &[Transaction], options: &CompareOptions) -> [u8; 32] {
+    let mut canonical: Vec<Vec<u8>> = transactions
+        .iter()
+        .map(|tx| canonical_record(tx, options))
+        .collect();
+    canonical.sort();
+
+    let mut hasher = Sha256::new();
+    for record in &canonical {
+        hasher.update((record.len() as u64).to_be_bytes());
+        hasher.update(record);
+    }
+    hasher.finalize().into()
+}
+
+/// Возвращает строковое значение поля транзакции для сравнения
+fn field_value(tx: &Transaction, field: Field) -> String {
+    match field {
+        Field::TxId => tx.tx_id.to_string(),
+        Field::TxType => format!("{:?}", tx.tx_type),
+        Field::FromUserId => tx.from_user_id.to_string(),
+        Field::ToUserId => tx.to_user_id.to_string(),
+        Field::Amount => tx.amount.to_string(),
+        Field::Timestamp => tx.timestamp.to_string(),
+        Field::Status => format!("{:?}", tx.status),
+        Field::Description => tx.description.clone(),
+        Field::Currency => tx.currency.clone().unwrap_or_default(),
+    }
+}
+
+/// Кодирует неигнорируемые поля записи как `ИМЯ`, длина значения и значение
+fn canonical_record(tx: &Transaction, options: &CompareOptions) -> Vec<u8> {
+    let mut record = Vec::new();
+    for field in Field::ALL {
+        if options.is_ignored(field) {
+            continue;
+        }
+        let value = field_value(tx, field);
+        record.extend_from_slice(field.name().as_bytes());
+        record.extend_from_slice(&(value.len() as u64).to_be_bytes());
+        record.extend_from_slice(value.as_bytes());
+    }
+    record
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{TransactionStatus, TransactionType};
+
+    fn create_test_transaction(id: u64) -> Transaction {
+        Transaction {
+            tx_id: id,
+            tx_type: TransactionType::Deposit,
+            from_user_id: 0,
+            to_user_id: 501,
+            amount: 50000,
+            timestamp: 1672531200000,
+            status: TransactionStatus::Success,
+            description: format!("Test transaction {}", id),
+            currency: None,
+        }
+    }
+
+    #[test]
+    fn test_diff_identical_transactions() {
+        let tx = create_test_transaction(1001);
+
+        assert!(diff_transactions(&tx, &tx.clone(), &CompareOptions::default()).is_empty());
+    }
+
+    #[test]
+    fn test_diff_respects_ignore_options() {
+        let tx1 = create_test_transaction(1001);
+        let mut tx2 = tx1.clone();
+        tx2.status = TransactionStatus::Failure;
+        tx2.description = "Changed".to_string();
+
+        let diffs = diff_transactions(&tx1, &tx2, &CompareOptions::default());
+        let fields: Vec<Field> = diffs.iter().map(|d| d.field).collect();
+        assert_eq!(fields, vec![Field::Status, Field::Description]);
+        assert_eq!(diffs[0].left, "Success");
+        assert_eq!(diffs[0].right, "Failure");
+
+        let options = CompareOptions::default()
+            .ignore(Field::Description)
+            .ignore(Field::Status);
+        assert!(diff_transactions(&tx1, &tx2, &options).is_empty());
+    }
+
+    #[test]
+    fn test_diff_ignores_arbitrary_fields() {
+        let tx1 = create_test_transaction(1001);
+        let mut tx2 = tx1.clone();
+        tx2.timestamp += 1000;
+        tx2.from_user_id = 42;
+
+        let options = CompareOptions::default()
+            .ignore(Field::Timestamp)
+            .ignore(Field::FromUserId);
+        assert!(diff_transactions(&tx1, &tx2, &options).is_empty());
+
+        tx2.amount += 1;
+        let diffs = diff_transactions(&tx1, &tx2, &options);
+        assert_eq!(diffs.len(), 1);
+        assert_eq!(diffs[0].field, Field::Amount);
+    }
+
+    #[test]
+    fn test_compare_equal_lists() {
+        let list = vec![create_test_transaction(1001), create_test_transaction(1002)];
+
+        let report = compare(&list, &list.clone(), &CompareOptions::default());
+        assert!(report.identical);
+        assert!(report.mismatches.is_empty());
+        assert_eq!((report.len_a, report.len_b), (2, 2));
+    }
+
+    #[test]
+    fn test_compare_differing_lists() {
+        let a = vec![create_test_transaction(1001), create_test_transaction(1002)];
+        let mut b = a.clone();
+        b[1].amount = 1;
+
+        let report = compare(&a, &b, &CompareOptions::default());
+        assert!(!report.identical);
+        assert_eq!(report.mismatches.len(), 1);
+        assert_eq!(report.mismatches[0].index, 1);
+        assert_eq!(report.mismatches[0].tx_id, 1002);
+        assert_eq!(report.mismatches[0].diffs[0].field, Field::Amount);
+
+        let options = CompareOptions::default().ignore(Field::Amount);
+        assert!(compare(&a, &b, &options).identical);
+    }
+
+    #[test]
+    fn test_compare_differing_lengths() {
+        let a = vec![create_test_transaction(1001), create_test_transaction(1002)];
+        let b = vec![create_test_transaction(1001)];
+
+        let report = compare(&a, &b, &CompareOptions::default());
+        assert!(!report.identical);
+        assert!(report.mismatches.is_empty());
+        assert_eq!((report.len_a, report.len_b), (2, 1));
+        assert!(report.only_in_a.is_empty());
+    }
+
+    #[test]
+    fn test_compare_by_id_reports_unmatched_records() {
+        let a = vec![create_test_transaction(1001), create_test_transaction(1002)];
+        let mut changed = create_test_transaction(1001);
+        changed.status = TransactionStatus::Pending;
+        let b = vec![create_test_transaction(1003), changed];
+
+        let options = CompareOptions {
+            by_id: true,
+            ..Default::default()
+        };
+        let report = compare(&a, &b, &options);
+        assert!(!report.identical);
+        assert_eq!(report.only_in_a, vec![1002]);
+        assert_eq!(report.only_in_b, vec![1003]);
+        assert_eq!(report.mismatches.len(), 1);
+        assert_eq!(report.mismatches[0].diffs[0].field, Field::Status);
+
+        let mut reordered = a.clone();
+        reordered.reverse();
+        assert!(compare(&a, &reordered, &options).identical);
+    }
+
+    #[test]
+    fn test_compare_by_id_counts_duplicate_ids() {
+        let options = CompareOptions {
+            by_id: true,
+            ..Default::default()
+        };
+        let twice = vec![create_test_transaction(1001), create_test_transaction(1001)];
+        let once = vec![create_test_transaction(1001)];
+
+        let report = compare(&twice, &once, &options);
+        assert!(!report.identical);
+        assert_eq!(report.only_in_a, vec![1001]);
+        assert!(report.only_in_b.is_empty());
+
+        let report = compare(&once, &twice, &options);
+        assert!(!report.identical);
+        assert_eq!(report.only_in_b, vec![1001]);
+
+        assert!(compare(&twice, &twice, &options).identical);
+    }
+
+    #[test]
+    fn test_fingerprint_csv_and_txt_match() {
+        use crate::{CsvParser, TextParser};
+
+        let mut transactions = vec![create_test_transaction(1001), create_test_transaction(1002)];
+        transactions[1].currency = Some("EUR".to_string());
+
+        let mut csv = Vec::new();
+        CsvParser::write_records(&transactions, &mut csv).unwrap();
+        let mut txt = Vec::new();
+        TextParser::write_records(&transactions, &mut txt).unwrap();
+
+        let from_csv = CsvParser::parse_records(csv.as_slice()).unwrap();
+        let mut from_txt = TextParser::parse_records(txt.as_slice()).unwrap();
+        from_txt.reverse();
+
+        let options = CompareOptions::default();
+        assert_eq!(
+            fingerprint(&from_csv, &options),
+            fingerprint(&from_txt, &options)
+        );
+    }
+
+    #[test]
+    fn test_fingerprint_respects_ignore_options() {
+        let tx1 = create_test_transaction(1001);
+        let mut tx2 = tx1.clone();
+        tx2.description = "Changed".to_string();
+        let (left, right) = ([tx1], [tx2]);
+
+        let options = CompareOptions::default();
+        assert_ne!(fingerprint(&left, &options), fingerprint(&right, &options));
+
+        let options = options.ignore(Field::Description);
+        assert_eq!(fingerprint(&left, &options), fingerprint(&right, &options));
+    }
+}
@@ -0,0 +1,546 @@
+use crate::{
+    BinaryParser, CsvParser, Format, FormatRegistry, ParseOptions, ParserError, TextParser,
+    Transaction, map_transactions,
+};
+use std::io::{Read, Write};
+
+/// Итог конвертации с отбраковкой некорректных записей
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FilterCounts {
+    /// Количество записей, записанных в основной поток
+    pub written: usize,
+
+    /// Количество записей, отправленных в поток отбраковки
+    pub rejected: usize,
+}
+
+/// Конвертирует поток транзакций между форматами, заданными по имени
+///
+/// Имена ищутся в реестре `registry`; без реестра доступны только
+/// встроенные форматы `csv`, `txt` и `bin`. Если передан `warnings`,
+/// в него добавляются предупреждения парсера о восстановимых проблемах
+/// входных данных.
+///
+/// # Аргументы
+/// * `reader` - Входной поток
+/// * `input` - Имя входного формата
+/// * `writer` - Выходной поток
+/// * `output` - Имя выходного формата
+/// * `registry` - Реестр с пользовательскими форматами
+/// * `warnings` - Список для предупреждений парсера
+///
+/// # Возвращает
+/// * `Ok(usize)` - Количество записанных транзакций
+/// * `Err(ParserError::UnsupportedFormat)` - Имя формата не зарегистрировано
+/// * `Err(ParserError)` - Ошибка парсинга или записи
+///
+/// # Пример
+/// ```
+/// use parser_lib::convert_stream;
+/// use std::io::Cursor;
+///
+/// let csv = "TX_ID,TX_TYPE,FROM_USER_ID,TO_USER_ID,AMOUNT,TIMESTAMP,STATUS,DESCRIPTION\n\
+///            1001,DEPOSIT,0,501,50000,1672531200000,SUCCESS,\"Test\"";
+///
+/// let mut output = Vec::new();
+/// let count = convert_stream(Cursor::new(csv), "csv", &mut output, "txt", None, None).unwrap();
+/// assert_eq!(count, 1);
+/// ```
+pub fn convert_stream<R, W>(
+    mut reader: R,
+    input: &str,
+    writer: &mut W,
+    output: &str,
+    registry: Option<&FormatRegistry>,
+    warnings: Option<&mut Vec<String>>,
+) -> Result<usize, ParserError>
+where
+    R: Read,
+    W: Write,
+{
+    let builtin;
+    let registry = match registry {
+        Some(registry) => registry,
+        None => {
+            builtin = FormatRegistry::default();
+            &builtin
+        }
+    };
+
+    let input = registry.resolve(input)?;
+    let output = registry.resolve(output)?;
+
+    let transactions = match warnings {
+        Some(warnings) => input.parse_with_warnings(&mut reader, warnings)?,
+        None => input.parse(&mut reader)?,
+    };
+    output.write(&transactions, writer)?;
+
+    Ok(transactions.len())
+}
+
+/// Конвертирует поток транзакций из одного формата в другой,
+/// применяя функцию к каждой записи перед записью
+///
+/// Это программная точка расширения для обогащения и нормализации
+/// данных, которые не покрываются флагами конвертера. Если передан
+/// `warnings`, в него добавляются предупреждения парсера (например,
+/// о лишних пустых полях CSV или неизвестном ключе текстового формата),
+/// чтобы вызывающий код мог на них отреагировать.
+///
+/// # Аргументы
+/// * `reader` - Входной поток
+/// * `input` - Формат входных данных
+/// * `writer` - Выходной поток
+/// * `output` - Формат выходных данных
+/// * `warnings` - Список для предупреждений парсера
+/// * `f` - Функция, применяемая к каждой транзакции
+///
+/// # Возвращает
+/// * `Ok(usize)` - Количество записанных транзакций
+/// * `Err(ParserError)` - Ошибка парсинга или записи
+///
+/// # Пример
+/// ```
+/// use parser_lib::{convert_stream_with, Format};
+/// use std::io::Cursor;
+///
+/// let csv = "TX_ID,TX_TYPE,FROM_USER_ID,TO_USER_ID,AMOUNT,TIMESTAMP,STATUS,DESCRIPTION\n\
+///            1001,DEPOSIT,0,501,50000,1672531200000,SUCCESS,\"Test\",,";
+///
+/// let (mut output, mut warnings) = (Vec::new(), Vec::new());
+/// let count = convert_stream_with(
+///     Cursor::new(csv),
+///     Format::Csv,
+///     &mut output,
+///     Format::Txt,
+///     Some(&mut warnings),
+///     |tx| tx.amount *= 2,
+/// )
+/// .unwrap();
+///
+/// assert_eq!(count, 1);
+/// assert!(String::from_utf8(output).unwrap().contains("AMOUNT: 100000"));
+/// assert_eq!(warnings, ["Line 2: ignored 2 trailing empty field(s)"]);
+/// ```
+pub fn convert_stream_with<R, W, F>(
+    reader: R,
+    input: Format,
+    writer: &mut W,
+    output: Format,
+    warnings: Option<&mut Vec<String>>,
+    f: F,
+) -> Result<usize, ParserError>
+where
+    R: Read,
+    W: Write,
+    F: FnMut(&mut Transaction),
+{
+    let options = ParseOptions::default();
+    let mut transactions = match warnings {
+        Some(warnings) => input.parse_records_with_warnings(reader, &options, warnings)?,
+        None => input.parse_records(reader, &options)?,
+    };
+
+    map_transactions(&mut transactions, f);
+
+    output.write_records(&transactions, writer)?;
+
+    Ok(transactions.len())
+}
+
+/// Конвертирует поток, отправляя некорректные записи в отдельный поток
+///
+/// В отличие от `convert_stream_with`, ошибка в отдельной записи не
+/// прерывает конвертацию: корректные записи пишутся в `writer` в формате
+/// `output`, а каждая отбракованная пишется в `reject_writer` строкой
+/// `# <ошибка>` (номер строки для CSV и текстового формата, номер записи
+/// и смещение для бинарного), за которой следует сама запись и пустая
+/// строка. Запись CSV и текстового формата сохраняется в исходном виде,
+/// бинарная — шестнадцатеричным дампом её байтов. Ошибки заголовка,
+/// структуры файла и ввода-вывода по-прежнему прерывают конвертацию.
+/// Если передан `warnings`, в него добавляются предупреждения парсера.
+///
+/// # Аргументы
+/// * `reader` - Входной поток
+/// * `input` - Формат входных данных
+/// * `writer` - Выходной поток для корректных записей
+/// * `output` - Формат выходных данных
+/// * `reject_writer` - Поток для сообщений об отбракованных записях
+/// * `warnings` - Список для предупреждений парсера
+///
+/// # Возвращает
+/// * `Ok(FilterCounts)` - Количество записанных и отбракованных записей
+/// * `Err(ParserError)` - Ошибка, не относящаяся к отдельной записи
+///
+/// # Пример
+/// ```
+/// use parser_lib::{convert_stream_filtered, Format};
+/// use std::io::Cursor;
+///
+/// let csv = "TX_ID,TX_TYPE,FROM_USER_ID,TO_USER_ID,AMOUNT,TIMESTAMP,STATUS,DESCRIPTION\n\
+///            1001,DEPOSIT,0,501,50000,1672531200000,SUCCESS,\"Ok\"\n\
+///            1002,DEPOSIT,7,501,50000,1672531200000,SUCCESS,\"Bad sender\"";
+///
+/// let (mut output, mut rejects) = (Vec::new(), Vec::new());
+/// let counts = convert_stream_filtered(
+///     Cursor::new(csv),
+///     Format::Csv,
+///     &mut output,
+///     Format::Txt,
+///     &mut rejects,
+///     None,
+/// )
+/// .unwrap();
+/// assert_eq!((counts.written, counts.rejected), (1, 1));
+/// ```
+pub fn convert_stream_filtered<R, W, J>(
+    reader: R,
+    input: Format,
+    writer: &mut W,
+    output: Format,
+    reject_writer: &mut J,
+    warnings: Option<&mut Vec<String>>,
+) -> Result<FilterCounts, ParserError>
+where
+    R: Read,
+    W: Write,
+    J: Write,
+{
+    let options = ParseOptions::default();
+    let mut errors = Vec::new();
+    let mut ignored = Vec::new();
+    let warnings = warnings.unwrap_or(&mut ignored);
+
+    let valid = match input {
+        Format::Csv => CsvParser::parse_rows(reader, &options, warnings, Some(&mut errors))?,
+        Format::Txt => TextParser::parse_blocks(reader, &options, warnings, Some(&mut errors))?,
+        Format::Bin => BinaryParser::parse_frames(reader, &options, Some(&mut errors))?,
+    };
+
+    for rejected in &errors {
+        writeln!(
+            reject_writer,
+            "# {}\n{}\n",
+            rejected.error,
+            rejected.raw.trim_end()
+        )
+        .map_err(ParserError::Io)?;
+    }
+    output.write_records(&valid, writer)?;
+
+    Ok(FilterCounts {
+        written: valid.len(),
+        rejected: errors.len(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BinaryParser, CsvParser, TransactionFormat};
+    use std::io::Cursor;
+
+    /// Формат для тестов: CSV с перевёрнутым описанием
+    struct ReversedCsv;
+
+    impl TransactionFormat for ReversedCsv {
+        fn parse(&self, reader: &mut dyn Read) -> Result<Vec<Transaction>, ParserError> {
+            let mut transactions = CsvParser::parse_records(reader)?;
+            map_transactions(&mut transactions, |tx| {
+                tx.description = tx.description.chars().rev().collect();
+            });
+            Ok(transactions)
+        }
+
+        fn write(
+            &self,
+            records: &[Transaction],
+            mut writer: &mut dyn Write,
+        ) -> Result<(), ParserError> {
+            let mut reversed = records.to_vec();
+            map_transactions(&mut reversed, |tx| {
+                tx.description = tx.description.chars().rev().collect();
+            });
+            CsvParser::write_records(&reversed, &mut writer)
+        }
+    }
+
+    #[test]
+    fn test_convert_stream_through_registered_format() {
+        let csv = "TX_ID,TX_TYPE,FROM_USER_ID,TO_USER_ID,AMOUNT,TIMESTAMP,STATUS,DESCRIPTION\n\
+                   1001,DEPOSIT,0,501,50000,1672531200000,SUCCESS,\"Initial funding\"";
+
+        let mut registry = FormatRegistry::default();
+        registry.register("reversed", Box::new(ReversedCsv));
+
+        let mut output = Vec::new();
+        let count = convert_stream(
+            Cursor::new(csv),
+            "csv",
+            &mut output,
+            "reversed",
+            Some(&registry),
+            None,
+        )
+        .unwrap();
+        assert_eq!(count, 1);
+        assert!(
+            String::from_utf8(output.clone())
+                .unwrap()
+                .contains("\"gnidnuf laitinI\"")
+        );
+
+        let mut text = Vec::new();
+        convert_stream(
+            Cursor::new(&output),
+            "reversed",
+            &mut text,
+            "txt",
+            Some(&registry),
+            None,
+        )
+        .unwrap();
+        assert!(
+            String::from_utf8(text)
+                .unwrap()
+                .contains("DESCRIPTION: \"Initial funding\"")
+        );
+    }
+
+    #[test]
+    fn test_convert_stream_filtered_routes_invalid_record() {
+        let csv = "TX_ID,TX_TYPE,FROM_USER_ID,TO_USER_ID,AMOUNT,TIMESTAMP,STATUS,DESCRIPTION\n\
+                   1001,DEPOSIT,0,501,50000,1672531200000,SUCCESS,\"First\"\n\
+                   1002,WITHDRAWAL,501,0,-5,1672531201000,SUCCESS,\"Negative\"\n\
+                   1003,TRANSFER,501,502,700,1672531202000,PENDING,\"Third\"";
+
+        let (mut output, mut rejects) = (Vec::new(), Vec::new());
+        let counts = convert_stream_filtered(
+            Cursor::new(csv),
+            Format::Csv,
+            &mut output,
+            Format::Bin,
+            &mut rejects,
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(
+            counts,
+            FilterCounts {
+                written: 2,
+                rejected: 1
+            }
+        );
+        let written = BinaryParser::parse_records(Cursor::new(&output)).unwrap();
+        let ids: Vec<u64> = written.iter().map(|tx| tx.tx_id).collect();
+        assert_eq!(ids, vec![1001, 1003]);
+
+        let rejects = String::from_utf8(rejects).unwrap();
+        assert_eq!(
+            rejects,
+            "# Validation error: Line 3: AMOUNT must be positive in CSV format, got -5\n\
+             1002,WITHDRAWAL,501,0,-5,1672531201000,SUCCESS,\"Negative\"\n\n"
+        );
+    }
+
+    #[test]
+    fn test_convert_stream_filtered_keeps_rejected_text_block() {
+        let valid = "TX_ID: 1001\nTX_TYPE: DEPOSIT\nFROM_USER_ID: 0\nTO_USER_ID: 501\n\
+                     AMOUNT: 50000\nTIMESTAMP: 1672531200000\nSTATUS: SUCCESS\n\
+                     DESCRIPTION: \"Ok\"\n";
+        let invalid = "TX_ID: 1002\nTX_TYPE: BONUS\nFROM_USER_ID: 0\nTO_USER_ID: 501\n\
+                       AMOUNT: 50000\nTIMESTAMP: 1672531200000\nSTATUS: SUCCESS\n\
+                       DESCRIPTION: \"Bad type\"\n";
+        let text = format!("{}\n{}", valid, invalid);
+
+        let (mut output, mut rejects) = (Vec::new(), Vec::new());
+        let counts = convert_stream_filtered(
+            Cursor::new(text),
+            Format::Txt,
+            &mut output,
+            Format::Csv,
+            &mut rejects,
+            None,
+        )
+        .unwrap();
+
+        assert_eq!((counts.written, counts.rejected), (1, 1));
+        let rejects = String::from_utf8(rejects).unwrap();
+        assert!(rejects.starts_with("# "), "{}", rejects);
+        assert!(rejects.contains(invalid), "{}", rejects);
+        assert!(!rejects.contains("TX_ID: 1001"), "{}", rejects);
+    }
+
+    #[test]
+    fn test_convert_stream_filtered_keeps_non_positive_binary_amounts() {
+        let mut records = crate::generate_sample(2, 1);
+        records[1].amount = 0;
+        let mut binary = Vec::new();
+        BinaryParser::write_records(&records, &mut binary).unwrap();
+
+        let (mut output, mut rejects) = (Vec::new(), Vec::new());
+        let counts = convert_stream_filtered(
+            Cursor::new(binary),
+            Format::Bin,
+            &mut output,
+            Format::Bin,
+            &mut rejects,
+            None,
+        )
+        .unwrap();
+
+        // Бинарный формат допускает неположительные суммы
+        assert_eq!((counts.written, counts.rejected), (2, 0));
+        assert!(rejects.is_empty());
+        assert_eq!(
+            BinaryParser::parse_records(Cursor::new(&output)).unwrap(),
+            records
+        );
+    }
+
+    #[test]
+    fn test_convert_stream_filtered_rejects_corrupt_binary_record() {
+        let records = crate::generate_sample(3, 1);
+        let mut binary = Vec::new();
+        BinaryParser::write_records(&records[..1], &mut binary).unwrap();
+        let start = binary.len();
+        BinaryParser::write_records(&records[1..], &mut binary).unwrap();
+        // Байт TX_TYPE второй записи
+        binary[start + 16] = 7;
+
+        let (mut output, mut rejects) = (Vec::new(), Vec::new());
+        let counts = convert_stream_filtered(
+            Cursor::new(binary),
+            Format::Bin,
+            &mut output,
+            Format::Bin,
+            &mut rejects,
+            None,
+        )
+        .unwrap();
+
+        assert_eq!((counts.written, counts.rejected), (2, 1));
+        let written = BinaryParser::parse_records(Cursor::new(&output)).unwrap();
+        assert_eq!(written, vec![records[0].clone(), records[2].clone()]);
+
+        let rejects = String::from_utf8(rejects).unwrap();
+        assert!(
+            rejects.starts_with(&format!(
+                "# Parse error: Record 2 at byte offset {}: Invalid TX_TYPE '7'",
+                start
+            )),
+            "{}",
+            rejects
+        );
+        assert!(rejects.contains("\n59 50 42 4E "), "{}", rejects);
+    }
+
+    #[test]
+    fn test_convert_stream_unknown_format() {
+        let result = convert_stream(Cursor::new(""), "csv", &mut Vec::new(), "mt940", None, None);
+
+        assert!(matches!(result, Err(ParserError::UnsupportedFormat)));
+    }
+
+    #[test]
+    fn test_convert_stream_with_uppercase_mapper() {
+        let csv = "TX_ID,TX_TYPE,FROM_USER_ID,TO_USER_ID,AMOUNT,TIMESTAMP,STATUS,DESCRIPTION\n\
+                   1001,DEPOSIT,0,501,50000,1672531200000,SUCCESS,\"Initial funding\"\n\
+                   1002,WITHDRAWAL,502,0,1000,1672538400000,PENDING,\"atm withdrawal\"";
+
+        let mut binary = Vec::new();
+        let count = convert_stream_with(
+            Cursor::new(csv),
+            Format::Csv,
+            &mut binary,
+            Format::Bin,
+            None,
+            |tx| {
+                tx.description = tx.description.to_uppercase();
+            },
+        )
+        .unwrap();
+        assert_eq!(count, 2);
+
+        let transactions = BinaryParser::parse_records(Cursor::new(&binary)).unwrap();
+        assert_eq!(transactions[0].description, "INITIAL FUNDING");
+        assert_eq!(transactions[1].description, "ATM WITHDRAWAL");
+    }
+
+    #[test]
+    fn test_convert_stream_with_collects_warnings() {
+        let csv = "TX_ID,TX_TYPE,FROM_USER_ID,TO_USER_ID,AMOUNT,TIMESTAMP,STATUS,DESCRIPTION\n\
+                   1001,DEPOSIT,0,501,100,1672531200000,SUCCESS,\"One\",,\n\
+                   1002,DEPOSIT,0,501,100,1672531200000,SUCCESS,\"Two\"";
+
+        let (mut text, mut warnings) = (Vec::new(), Vec::new());
+        let count = convert_stream_with(
+            Cursor::new(csv),
+            Format::Csv,
+            &mut text,
+            Format::Txt,
+            Some(&mut warnings),
+            |_| {},
+        )
+        .unwrap();
+        assert_eq!(count, 2);
+        assert_eq!(warnings, ["Line 2: ignored 2 trailing empty field(s)"]);
+
+        let text = String::from_utf8(text).unwrap().replacen(
+            "STATUS: SUCCESS",
+            "STATUS: SUCCESS\nBRANCH: 12",
+            1,
+        );
+        warnings.clear();
+        let count = convert_stream_with(
+            Cursor::new(text),
+            Format::Txt,
+            &mut Vec::new(),
+            Format::Bin,
+            Some(&mut warnings),
+            |_| {},
+        )
+        .unwrap();
+        assert_eq!(count, 2);
+        assert_eq!(warnings.len(), 1);
+        assert!(
+            warnings[0].ends_with(": ignored unknown field 'BRANCH'"),
+            "{:?}",
+            warnings
+        );
+    }
+
+    #[test]
+    fn test_convert_stream_and_filtered_collect_warnings() {
+        let csv = "TX_ID,TX_TYPE,FROM_USER_ID,TO_USER_ID,AMOUNT,TIMESTAMP,STATUS,DESCRIPTION\n\
+                   1001,DEPOSIT,0,501,100,1672531200000,SUCCESS,\"One\",\n\
+                   1002,DEPOSIT,7,501,100,1672531200000,SUCCESS,\"Bad sender\"";
+
+        let mut warnings = Vec::new();
+        let counts = convert_stream_filtered(
+            Cursor::new(csv),
+            Format::Csv,
+            &mut Vec::new(),
+            Format::Bin,
+            &mut Vec::new(),
+            Some(&mut warnings),
+        )
+        .unwrap();
+        assert_eq!((counts.written, counts.rejected), (1, 1));
+        assert_eq!(warnings, ["Line 2: ignored 1 trailing empty field(s)"]);
+
+        warnings.clear();
+        let result = convert_stream(
+            Cursor::new(csv),
+            "csv",
+            &mut Vec::new(),
+            "txt",
+            None,
+            Some(&mut warnings),
+        );
+        assert!(result.is_err());
+        assert_eq!(warnings, ["Line 2: ignored 1 trailing empty field(s)"]);
+    }
+}
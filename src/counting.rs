@@ -0,0 +1,137 @@
+use crate::{Format, ParserError, Transaction};
+use std::io::{self, Write};
+
+/// Обёртка над записываемым потоком, подсчитывающая записанные байты
+///
+/// # Пример
+/// ```
+/// use parser_lib::CountingWriter;
+/// use std::io::Write;
+///
+/// let mut writer = CountingWriter::new(Vec::new());
+/// writer.write_all(b"YPBN").unwrap();
+/// assert_eq!(writer.bytes_written(), 4);
+/// ```
+#[derive(Debug)]
+pub struct CountingWriter<W> {
+    inner: W,
+    bytes_written: u64,
+}
+
+impl<W: Write> CountingWriter<W> {
+    /// Создаёт обёртку с нулевым счётчиком
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner,
+            bytes_written: 0,
+        }
+    }
+
+    /// Возвращает количество байт, принятых нижележащим потоком
+    pub fn bytes_written(&self) -> u64 {
+        self.bytes_written
+    }
+
+    /// Возвращает нижележащий поток
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: Write> Write for CountingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.bytes_written += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Записывает транзакции в заданном формате и возвращает размер вывода
+///
+/// # Аргументы
+/// * `records` - Срез транзакций для записи
+/// * `writer` - Записываемый поток
+/// * `format` - Формат вывода
+///
+/// # Возвращает
+/// * `Ok(u64)` - Количество записанных байт
+/// * `Err(ParserError)` - Ошибка записи
+///
+/// # Пример
+/// ```
+/// use parser_lib::{write_records_counted, Format};
+///
+/// let mut buffer = Vec::new();
+/// let bytes = write_records_counted(&[], &mut buffer, Format::Csv).unwrap();
+/// assert_eq!(bytes, buffer.len() as u64);
+/// ```
+pub fn write_records_counted<W: Write>(
+    records: &[Transaction],
+    writer: &mut W,
+    format: Format,
+) -> Result<u64, ParserError> {
+    let mut counting = CountingWriter::new(writer);
+    format.write_records(records, &mut counting)?;
+    Ok(counting.bytes_written())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{TransactionStatus, TransactionType};
+
+    fn create_test_transaction(id: u64, description: &str) -> Transaction {
+        Transaction {
+            tx_id: id,
+            tx_type: TransactionType::Deposit,
+            from_user_id: 0,
+            to_user_id: 501,
+            amount: 50000,
+            timestamp: 1672531200000,
+            status: TransactionStatus::Success,
+            description: description.to_string(),
+            currency: None,
+        }
+    }
+
+    #[test]
+    fn test_counted_binary_bytes_match_record_sizes() {
+        let records = vec![
+            create_test_transaction(1001, "First"),
+            create_test_transaction(1002, "A longer description"),
+        ];
+
+        let mut buffer = Vec::new();
+        let bytes = write_records_counted(&records, &mut buffer, Format::Bin).unwrap();
+
+        // Каждая запись: MAGIC (4 байта) + RECORD_SIZE (4 байта) + тело
+        let mut expected = 0u64;
+        let mut offset = 0;
+        for _ in &records {
+            assert_eq!(&buffer[offset..offset + 4], b"YPBN");
+            let record_size =
+                u32::from_be_bytes(buffer[offset + 4..offset + 8].try_into().unwrap()) as u64;
+            expected += record_size + 8;
+            offset += record_size as usize + 8;
+        }
+
+        assert_eq!(bytes, expected);
+        assert_eq!(bytes, buffer.len() as u64);
+    }
+
+    #[test]
+    fn test_counted_text_formats_match_buffer_len() {
+        let records = vec![create_test_transaction(1001, "Test")];
+
+        for format in [Format::Csv, Format::Txt] {
+            let mut buffer = Vec::new();
+            let bytes = write_records_counted(&records, &mut buffer, format).unwrap();
+            assert_eq!(bytes, buffer.len() as u64);
+            assert!(bytes > 0);
+        }
+    }
+}
@@ -1,15 +1,268 @@
+use crate::validation::{check_positive_amount, check_user_ids};
 use crate::{
-    CsvTransactions, ParseFromRead, ParserError, Transaction, TransactionStatus, TransactionType,
-    WriteTo,
+    CsvParseOptions, Field, Format, LineEnding, ParseOptions, ParserError, Quoting, Rejected,
+    Transaction, TransactionStatus, TransactionType, check_currency_code, normalize_line_endings,
+    parse_field,
 };
 use std::io::{Read, Write};
 
+/// Настройки записи CSV формата
+///
+/// Значение по умолчанию соответствует поведению `write_records`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsvWriteOptions {
+    /// Записывать ли строку заголовка перед данными
+    ///
+    /// Отключается при дописывании в уже существующий CSV файл.
+    pub write_header: bool,
+
+    /// Записывать ли девятый столбец `CURRENCY`
+    ///
+    /// `write_records` включает его автоматически, если хотя бы у одной
+    /// транзакции задана валюта.
+    pub currency_column: bool,
+
+    /// Защищать описания от интерпретации как формулы в табличных редакторах
+    ///
+    /// Описание, начинающееся с `=`, `+`, `-` или `@`, получает префикс `'`.
+    /// Описания, уже начинающиеся с `'`, тоже получают префикс, чтобы
+    /// преобразование было обратимым. Файл, записанный с этой настройкой,
+    /// нужно читать с `CsvParseOptions::sanitize_formulas`, иначе префикс
+    /// останется в описании.
+    pub sanitize_formulas: bool,
+
+    /// Окончание строк заголовка и записей
+    ///
+    /// Переводы строк внутри описаний в кавычках записываются как есть.
+    pub line_ending: LineEnding,
+}
+
+impl Default for CsvWriteOptions {
+    fn default() -> Self {
+        CsvWriteOptions {
+            write_header: true,
+            currency_column: false,
+            sanitize_formulas: false,
+            line_ending: LineEnding::Lf,
+        }
+    }
+}
+
+/// Префикс, которым экранируются описания-формулы
+const FORMULA_GUARD: char = '\'';
+
+/// Добавляет `FORMULA_GUARD` к описанию, которое табличный редактор примет за формулу
+fn guard_formula(description: &str) -> std::borrow::Cow<'_, str> {
+    match description.chars().next() {
+        Some('=' | '+' | '-' | '@' | FORMULA_GUARD) => {
+            format!("{}{}", FORMULA_GUARD, description).into()
+        }
+        _ => description.into(),
+    }
+}
+
+/// Потоковый писатель CSV формата
+///
+/// Хранит состояние записи: был ли уже записан заголовок и сколько строк
+/// записано с момента последнего сброса буфера. Заголовок записывается
+/// не более одного раза, даже при повторных вызовах `write_header`.
+///
+/// # Пример
+/// ```
+/// use parser_lib::{CsvWriter, Transaction, TransactionStatus, TransactionType};
+///
+/// # fn main() -> Result<(), parser_lib::ParserError> {
+/// let transaction = Transaction {
+///     tx_id: 1001,
+///     tx_type: TransactionType::Deposit,
+///     from_user_id: 0,
+///     to_user_id: 501,
+///     amount: 50000,
+///     timestamp: 1672531200000,
+///     status: TransactionStatus::Success,
+///     description: "Test".to_string(),
+///     currency: None,
+/// };
+///
+/// let mut writer = CsvWriter::new(Vec::new()).flush_every(100);
+/// writer.write_one(&transaction)?;
+/// writer.flush()?;
+///
+/// let output = String::from_utf8(writer.into_inner()).unwrap();
+/// assert!(output.starts_with("TX_ID,"));
+/// # Ok(())
+/// # }
+/// ```
+pub struct CsvWriter<W: Write> {
+    writer: W,
+    options: CsvWriteOptions,
+    header_written: bool,
+    flush_every: usize,
+    pending: usize,
+}
+
+impl<W: Write> CsvWriter<W> {
+    /// Создает писатель с настройками по умолчанию
+    ///
+    /// # Аргументы
+    /// * `writer` - Записываемый поток (например, файл или буфер)
+    pub fn new(writer: W) -> Self {
+        Self::with_options(writer, CsvWriteOptions::default())
+    }
+
+    /// Создает писатель с указанными настройками
+    ///
+    /// Если `options.write_header` равен `false`, заголовок не будет
+    /// записан автоматически перед первой строкой.
+    ///
+    /// # Аргументы
+    /// * `writer` - Записываемый поток
+    /// * `options` - Настройки записи
+    pub fn with_options(writer: W, options: CsvWriteOptions) -> Self {
+        CsvWriter {
+            writer,
+            options,
+            header_written: false,
+            flush_every: 0,
+            pending: 0,
+        }
+    }
+
+    /// Задает периодичность сброса буфера
+    ///
+    /// После каждых `n` записанных строк вызывается `flush` потока.
+    /// Значение 0 отключает периодический сброс.
+    ///
+    /// # Аргументы
+    /// * `n` - Количество строк между сбросами
+    pub fn flush_every(mut self, n: usize) -> Self {
+        self.flush_every = n;
+        self
+    }
+
+    /// Записывает строку заголовка, если она еще не была записана
+    ///
+    /// # Возвращает
+    /// * `Ok(())` - Заголовок записан или был записан ранее
+    /// * `Err(ParserError)` - Ошибка записи
+    pub fn write_header(&mut self) -> Result<(), ParserError> {
+        if self.header_written {
+            return Ok(());
+        }
+
+        let currency = if self.options.currency_column {
+            ",CURRENCY"
+        } else {
+            ""
+        };
+        write!(
+            self.writer,
+            "TX_ID,TX_TYPE,FROM_USER_ID,TO_USER_ID,AMOUNT,TIMESTAMP,STATUS,DESCRIPTION{}{}",
+            currency,
+            self.options.line_ending.as_str()
+        )
+        .map_err(ParserError::Io)?;
+        self.header_written = true;
+
+        Ok(())
+    }
+
+    /// Записывает одну транзакцию
+    ///
+    /// Перед первой строкой автоматически записывает заголовок, если это
+    /// разрешено настройками. При достижении порога `flush_every`
+    /// сбрасывает буфер потока.
+    ///
+    /// # Аргументы
+    /// * `record` - Транзакция для записи
+    ///
+    /// # Возвращает
+    /// * `Ok(())` - Успешная запись
+    /// * `Err(ParserError)` - Ошибка записи или сброса буфера, либо
+    ///   валюта задана при отключённом столбце `CURRENCY`
+    pub fn write_one(&mut self, record: &Transaction) -> Result<(), ParserError> {
+        let currency = match (&record.currency, self.options.currency_column) {
+            (Some(code), true) => format!(",{}", code),
+            (None, true) => ",".to_string(),
+            (None, false) => String::new(),
+            (Some(_), false) => {
+                return Err(ParserError::Validation(format!(
+                    "TX_ID {}: currency requires the CURRENCY column",
+                    record.tx_id
+                )));
+            }
+        };
+
+        if self.options.write_header {
+            self.write_header()?;
+        }
+
+        let tx_type = match record.tx_type {
+            TransactionType::Deposit => "DEPOSIT",
+            TransactionType::Transfer => "TRANSFER",
+            TransactionType::Withdrawal => "WITHDRAWAL",
+        };
+
+        let status = match record.status {
+            TransactionStatus::Success => "SUCCESS",
+            TransactionStatus::Failure => "FAILURE",
+            TransactionStatus::Pending => "PENDING",
+            TransactionStatus::Reversed => "REVERSED",
+        };
+
+        let description = if self.options.sanitize_formulas {
+            CsvParser::escape_description(&guard_formula(&record.description))
+        } else {
+            CsvParser::escape_description(&record.description)
+        };
+
+        write!(
+            self.writer,
+            "{},{},{},{},{},{},{},{}{}{}",
+            record.tx_id,
+            tx_type,
+            record.from_user_id,
+            record.to_user_id,
+            record.amount,
+            record.timestamp,
+            status,
+            description,
+            currency,
+            self.options.line_ending.as_str()
+        )
+        .map_err(ParserError::Io)?;
+
+        self.pending += 1;
+        if self.flush_every > 0 && self.pending >= self.flush_every {
+            self.flush()?;
+        }
+
+        Ok(())
+    }
+
+    /// Сбрасывает буфер потока
+    ///
+    /// # Возвращает
+    /// * `Ok(())` - Буфер успешно сброшен
+    /// * `Err(ParserError)` - Ошибка сброса буфера
+    pub fn flush(&mut self) -> Result<(), ParserError> {
+        self.writer.flush().map_err(ParserError::Io)?;
+        self.pending = 0;
+        Ok(())
+    }
+
+    /// Возвращает внутренний поток
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
 /// Парсер CSV формата транзакций
 ///
 /// CSV формат имеет следующую структуру:
 /// - Заголовок с именами полей (первая строка)
 /// - Данные транзакций (последующие строки)
-/// - Поддерживает экранирование кавычек и запятых в описаниях
+/// - Поддерживает экранирование кавычек, запятых и переводов строк в описаниях
 pub struct CsvParser;
 
 impl CsvParser {
@@ -23,33 +276,228 @@ impl CsvParser {
     /// * `Err(ParserError)` - Ошибка парсинга или ввода-вывода
     ///
     pub fn parse_records<R: Read>(reader: R) -> Result<Vec<Transaction>, ParserError> {
-        let content = std::io::read_to_string(reader).map_err(ParserError::Io)?;
+        Self::parse_records_with(reader, &ParseOptions::default())
+    }
+
+    /// Парсит CSV записи транзакций с дополнительными настройками
+    ///
+    /// # Аргументы
+    /// * `reader` - Читаемый поток (например, файл или буфер)
+    /// * `options` - Настройки парсинга и правила валидации
+    ///
+    /// # Возвращает
+    /// * `Ok(Vec<Transaction>)` - Вектор распарсенных транзакций
+    /// * `Err(ParserError)` - Ошибка парсинга, валидации или ввода-вывода
+    pub fn parse_records_with<R: Read>(
+        reader: R,
+        options: &ParseOptions,
+    ) -> Result<Vec<Transaction>, ParserError> {
+        Self::parse_records_with_warnings(reader, options, &mut Vec::new())
+    }
+
+    /// Парсит CSV записи, собирая предупреждения о пропущенных строках
+    ///
+    /// # Аргументы
+    /// * `reader` - Читаемый поток (например, файл или буфер)
+    /// * `options` - Настройки парсинга и правила валидации
+    /// * `warnings` - Список, в который добавляются предупреждения
+    ///
+    /// # Возвращает
+    /// * `Ok(Vec<Transaction>)` - Вектор распарсенных транзакций
+    /// * `Err(ParserError)` - Ошибка парсинга, валидации или ввода-вывода
+    pub fn parse_records_with_warnings<R: Read>(
+        reader: R,
+        options: &ParseOptions,
+        warnings: &mut Vec<String>,
+    ) -> Result<Vec<Transaction>, ParserError> {
+        Self::parse_rows(reader, options, warnings, None)
+    }
+
+    /// Парсит CSV записи, собирая ошибки всех некорректных строк
+    ///
+    /// В отличие от `parse_records`, разбор не останавливается на первой
+    /// ошибке в строке данных: проверяются все строки файла. Ошибки чтения
+    /// и заголовка по-прежнему прерывают разбор.
+    ///
+    /// # Аргументы
+    /// * `reader` - Читаемый поток (например, файл или буфер)
+    ///
+    /// # Возвращает
+    /// * `Ok(Vec<Transaction>)` - Все строки корректны
+    /// * `Err(Vec<ParserError>)` - Ошибки всех некорректных строк в порядке следования
+    ///
+    /// # Пример
+    /// ```
+    /// use parser_lib::CsvParser;
+    /// use std::io::Cursor;
+    ///
+    /// let csv = "TX_ID,TX_TYPE,FROM_USER_ID,TO_USER_ID,AMOUNT,TIMESTAMP,STATUS,DESCRIPTION\n\
+    ///            x,DEPOSIT,0,501,100,1672531200000,SUCCESS,\"Bad id\"\n\
+    ///            1002,DEPOSIT,0,501,-1,1672531200000,SUCCESS,\"Bad amount\"";
+    ///
+    /// let errors = CsvParser::parse_collect_errors(Cursor::new(csv)).unwrap_err();
+    /// assert_eq!(errors.len(), 2);
+    /// ```
+    pub fn parse_collect_errors<R: Read>(reader: R) -> Result<Vec<Transaction>, Vec<ParserError>> {
+        let mut errors = Vec::new();
+        let result = Self::parse_rows(
+            reader,
+            &ParseOptions::default(),
+            &mut Vec::new(),
+            Some(&mut errors),
+        );
+
+        let mut errors: Vec<ParserError> = errors.into_iter().map(|r| r.error).collect();
+        match result {
+            Ok(records) if errors.is_empty() => Ok(records),
+            Ok(_) => Err(errors),
+            Err(e) => {
+                errors.push(e);
+                Err(errors)
+            }
+        }
+    }
 
-        let lines: Vec<&str> = content.lines().collect();
+    /// Разбирает строки CSV
+    ///
+    /// Если передан `errors`, ошибки отдельных строк добавляются в него
+    /// вместе с текстом строки, а разбор продолжается; иначе первая ошибка
+    /// возвращается сразу.
+    pub(crate) fn parse_rows<R: Read>(
+        reader: R,
+        options: &ParseOptions,
+        warnings: &mut Vec<String>,
+        mut errors: Option<&mut Vec<Rejected>>,
+    ) -> Result<Vec<Transaction>, ParserError> {
+        let content = options.read_text(reader)?;
+        let content = normalize_line_endings(&content, Quoting::Doubled);
+
+        let lines = Self::logical_lines(&content, options.csv.comment_char);
 
         if lines.is_empty() {
             return Ok(Vec::new());
         }
 
-        let headers = Self::parse_line(lines[0], 0)?;
-        Self::validate_headers(&headers)?;
+        let source_headers = Self::header_fields(&lines[0].1, lines[0].0, warnings)?;
+        let (headers, order) = match &options.csv.field_map {
+            Some(map) => {
+                let (headers, order) = map.map_header(&source_headers)?;
+                (headers, Some(order))
+            }
+            None => {
+                Self::validate_headers(&source_headers)?;
+                (source_headers.clone(), None)
+            }
+        };
 
         let mut records = Vec::new();
 
-        for (line_num, line) in lines.iter().enumerate().skip(1) {
-            let line_num = line_num + 1;
+        for (line_num, line) in lines.iter().skip(1) {
+            let line_num = *line_num;
             if line.trim().is_empty() {
                 continue;
             }
 
-            let fields = Self::parse_line(line, line_num)?;
-            let transaction = Self::parse_record(&fields, line_num)?;
-            records.push(transaction);
+            match Self::parse_row(
+                line,
+                line_num,
+                &source_headers,
+                &headers,
+                order.as_deref(),
+                options,
+                warnings,
+            ) {
+                Ok(Some(transaction)) => {
+                    options.check_record_limit(records.len())?;
+                    records.push(transaction);
+                }
+                Ok(None) => {}
+                Err(error) => match errors.as_deref_mut() {
+                    Some(errors) => errors.push(Rejected {
+                        error,
+                        raw: line.clone(),
+                    }),
+                    None => return Err(error),
+                },
+            }
         }
 
         Ok(records)
     }
 
+    /// Разбирает одну строку данных; `None` означает пропущенный повторный заголовок
+    ///
+    /// Повторный заголовок распознаётся по исходным именам столбцов
+    /// `source_headers`; `headers` и `order` — результат `CsvFieldMap::map_header`
+    /// (без карты полей совпадают с исходными).
+    fn parse_row(
+        line: &str,
+        line_num: usize,
+        source_headers: &[String],
+        headers: &[String],
+        order: Option<&[usize]>,
+        options: &ParseOptions,
+        warnings: &mut Vec<String>,
+    ) -> Result<Option<Transaction>, ParserError> {
+        let mut fields = Self::parse_line(line, line_num)?;
+        Self::strip_trailing_empty(&mut fields, source_headers.len(), line_num, warnings);
+        if fields == source_headers {
+            if options.csv.tolerate_repeated_header {
+                warnings.push(format!("Line {}: skipped repeated header", line_num));
+                return Ok(None);
+            }
+            return Err(ParserError::Parse(format!(
+                "Line {}: repeated header",
+                line_num
+            )));
+        }
+
+        let fields = match (&options.csv.field_map, order) {
+            (Some(map), Some(order)) => map.map_row(fields, order),
+            _ => fields,
+        };
+
+        let transaction = Self::parse_record(&fields, headers.len(), line_num, &options.csv)?;
+        options
+            .rules
+            .check(&transaction)
+            .map_err(|msg| ParserError::Validation(format!("Line {}: {}", line_num, msg)))?;
+        Ok(Some(transaction))
+    }
+
+    /// Парсит одну строку данных CSV
+    ///
+    /// Применяет те же проверки заголовка и бизнес-правил, что и
+    /// `parse_records`, но без построения многострочного буфера.
+    ///
+    /// # Аргументы
+    /// * `line` - Строка данных без заголовка
+    /// * `header` - Имена столбцов в порядке их следования
+    ///
+    /// # Возвращает
+    /// * `Ok(Transaction)` - Распарсенная транзакция
+    /// * `Err(ParserError)` - Ошибка заголовка, парсинга или валидации
+    ///
+    /// # Пример
+    /// ```
+    /// use parser_lib::CsvParser;
+    ///
+    /// let header: Vec<String> = "TX_ID,TX_TYPE,FROM_USER_ID,TO_USER_ID,AMOUNT,TIMESTAMP,STATUS,DESCRIPTION"
+    ///     .split(',')
+    ///     .map(String::from)
+    ///     .collect();
+    ///
+    /// let tx = CsvParser::parse_one("1001,DEPOSIT,0,501,50000,1672531200000,SUCCESS,\"Test\"", &header)
+    ///     .unwrap();
+    /// assert_eq!(tx.tx_id, 1001);
+    /// ```
+    pub fn parse_one(line: &str, header: &[String]) -> Result<Transaction, ParserError> {
+        Self::validate_headers(header)?;
+
+        let fields = Self::parse_line(line.trim_end_matches(['\r', '\n']), 1)?;
+        Self::parse_record(&fields, header.len(), 1, &CsvParseOptions::default())
+    }
+
     /// Записывает транзакции в CSV формат в записываемый поток
     ///
     /// # Аргументы
@@ -61,7 +509,7 @@ impl CsvParser {
     /// * `Err(ParserError)` - Ошибка записи
     ///
     /// # Пример
-    /// ```
+    /// ```no_run
     /// use parser_lib::{CsvParser, Transaction, TransactionType, TransactionStatus};
     /// use std::fs::File;
     /// use std::io::BufWriter;
@@ -76,6 +524,7 @@ impl CsvParser {
     ///     timestamp: 1672531200000,
     ///     status: TransactionStatus::Success,
     ///     description: "Test".to_string(),
+    ///     currency: None,
     /// }];
     ///
     /// let file = File::create("output.csv")?;
@@ -88,76 +537,96 @@ impl CsvParser {
         records: &[Transaction],
         writer: &mut W,
     ) -> Result<(), ParserError> {
-        writeln!(
-            writer,
-            "TX_ID,TX_TYPE,FROM_USER_ID,TO_USER_ID,AMOUNT,TIMESTAMP,STATUS,DESCRIPTION"
-        )
-        .map_err(ParserError::Io)?;
+        Self::write_records_with(records, writer, &CsvWriteOptions::default())
+    }
+
+    /// Записывает транзакции в CSV формат с дополнительными настройками
+    ///
+    /// # Аргументы
+    /// * `records` - Список транзакций для записи
+    /// * `writer` - Записываемый поток (например, файл или буфер)
+    /// * `options` - Настройки записи
+    ///
+    /// # Возвращает
+    /// * `Ok(())` - Успешная запись
+    /// * `Err(ParserError)` - Ошибка записи
+    pub fn write_records_with<W: Write>(
+        records: &[Transaction],
+        writer: &mut W,
+        options: &CsvWriteOptions,
+    ) -> Result<(), ParserError> {
+        let mut options = options.clone();
+        options.currency_column |= records.iter().any(|r| r.currency.is_some());
+
+        let mut csv_writer = CsvWriter::with_options(writer, options.clone());
+
+        if options.write_header {
+            csv_writer.write_header()?;
+        }
 
         for record in records {
-            let tx_type = match record.tx_type {
-                TransactionType::Deposit => "DEPOSIT",
-                TransactionType::Transfer => "TRANSFER",
-                TransactionType::Withdrawal => "WITHDRAWAL",
-            };
+            csv_writer.write_one(record)?;
+        }
+
+        csv_writer.flush()
+    }
+
+    /// Собирает логические строки CSV, объединяя физические строки,
+    /// если описание в кавычках содержит перевод строки
+    ///
+    /// Каждая логическая строка сопровождается номером физической строки,
+    /// с которой она начинается. Начальные физические строки, начинающиеся
+    /// с `comment_char`, пропускаются до объединения, поэтому кавычка
+    /// в комментарии не поглощает следующие строки.
+    fn logical_lines(content: &str, comment_char: Option<char>) -> Vec<(usize, String)> {
+        let mut lines = Vec::new();
+        let mut pending: Option<(usize, String)> = None;
+
+        for (index, line) in content.lines().enumerate() {
+            if lines.is_empty()
+                && pending.is_none()
+                && comment_char.is_some_and(|marker| line.trim_start().starts_with(marker))
+            {
+                continue;
+            }
 
-            let status = match record.status {
-                TransactionStatus::Success => "SUCCESS",
-                TransactionStatus::Failure => "FAILURE",
-                TransactionStatus::Pending => "PENDING",
+            let (start, text) = match pending.take() {
+                Some((start, mut text)) => {
+                    text.push('\n');
+                    text.push_str(line);
+                    (start, text)
+                }
+                None => (index + 1, line.to_string()),
             };
 
-            let description = Self::escape_description(&record.description);
+            if text.matches('"').count() % 2 == 0 {
+                lines.push((start, text));
+            } else {
+                pending = Some((start, text));
+            }
+        }
 
-            writeln!(
-                writer,
-                "{},{},{},{},{},{},{},{}",
-                record.tx_id,
-                tx_type,
-                record.from_user_id,
-                record.to_user_id,
-                record.amount,
-                record.timestamp,
-                status,
-                description
-            )
-            .map_err(ParserError::Io)?;
+        if let Some(unclosed) = pending {
+            lines.push(unclosed);
         }
 
-        Ok(())
+        lines
     }
 
     fn parse_line(line: &str, line_num: usize) -> Result<Vec<String>, ParserError> {
         let mut fields = Vec::new();
         let mut current_field = String::new();
         let mut in_quotes = false;
-        let mut chars = line.chars().peekable();
 
-        while let Some(ch) = chars.next() {
+        for ch in line.chars() {
             match ch {
                 '"' => {
-                    if in_quotes {
-                        if let Some(&next_ch) = chars.peek() {
-                            if next_ch == '"' {
-                                chars.next();
-                                current_field.push('"');
-                            } else {
-                                in_quotes = false;
-                            }
-                        } else {
-                            in_quotes = false;
-                        }
-                    } else {
-                        in_quotes = true;
-                    }
+                    in_quotes = !in_quotes;
+                    current_field.push(ch);
                 }
-                ',' => {
-                    if in_quotes {
-                        current_field.push(',');
-                    } else {
-                        fields.push(current_field);
-                        current_field = String::new();
-                    }
+                ',' if !in_quotes => {
+                    fields.push(Self::unescape_field(&current_field));
+                    current_field = String::new();
                 }
                 _ => {
                     current_field.push(ch);
@@ -165,7 +634,7 @@ impl CsvParser {
             }
         }
 
-        fields.push(current_field);
+        fields.push(Self::unescape_field(&current_field));
 
         if in_quotes {
             return Err(ParserError::Parse(format!(
@@ -177,6 +646,72 @@ impl CsvParser {
         Ok(fields)
     }
 
+    /// Снимает кавычки с поля, если оно целиком заключено в них;
+    /// остальные поля возвращаются без изменений
+    fn unescape_field(field: &str) -> String {
+        if Self::is_quoted(field) {
+            Self::unescape_description(field)
+        } else {
+            field.to_string()
+        }
+    }
+
+    fn is_quoted(field: &str) -> bool {
+        let trimmed = field.trim();
+        trimmed.len() >= 2 && trimmed.starts_with('"') && trimmed.ends_with('"')
+    }
+
+    /// Отбрасывает пустые поля после `columns`-го, которые добавляют табличные редакторы
+    ///
+    /// Если среди лишних полей есть непустое, строка не изменяется, и
+    /// `parse_record` сообщит о неверном количестве полей.
+    fn strip_trailing_empty(
+        fields: &mut Vec<String>,
+        columns: usize,
+        line_num: usize,
+        warnings: &mut Vec<String>,
+    ) {
+        if fields.len() <= columns || fields[columns..].iter().any(|f| !f.trim().is_empty()) {
+            return;
+        }
+
+        warnings.push(format!(
+            "Line {}: ignored {} trailing empty field(s)",
+            line_num,
+            fields.len() - columns
+        ));
+        fields.truncate(columns);
+    }
+
+    /// Разбирает и проверяет строку заголовка
+    ///
+    /// Пустые столбцы после последнего именованного отбрасываются
+    /// с предупреждением.
+    pub(crate) fn parse_header(
+        line: &str,
+        line_num: usize,
+        warnings: &mut Vec<String>,
+    ) -> Result<Vec<String>, ParserError> {
+        let headers = Self::header_fields(line, line_num, warnings)?;
+        Self::validate_headers(&headers)?;
+        Ok(headers)
+    }
+
+    /// Разбирает строку заголовка без проверки имён столбцов
+    fn header_fields(
+        line: &str,
+        line_num: usize,
+        warnings: &mut Vec<String>,
+    ) -> Result<Vec<String>, ParserError> {
+        let mut headers = Self::parse_line(line, 0)?;
+        let named = headers
+            .iter()
+            .rposition(|h| !h.trim().is_empty())
+            .map_or(0, |i| i + 1);
+        Self::strip_trailing_empty(&mut headers, named, line_num, warnings);
+        Ok(headers)
+    }
+
     fn validate_headers(headers: &[String]) -> Result<(), ParserError> {
         let expected = [
             "TX_ID",
@@ -187,12 +722,14 @@ impl CsvParser {
             "TIMESTAMP",
             "STATUS",
             "DESCRIPTION",
+            "CURRENCY",
         ];
 
-        if headers.len() != expected.len() {
+        // Столбец CURRENCY необязателен для совместимости со старыми файлами
+        if headers.len() != expected.len() - 1 && headers.len() != expected.len() {
             return Err(ParserError::Parse(format!(
                 "Expected {} columns, got {}",
-                expected.len(),
+                expected.len() - 1,
                 headers.len()
             )));
         }
@@ -211,77 +748,88 @@ impl CsvParser {
         Ok(())
     }
 
-    fn parse_record(fields: &[String], line_num: usize) -> Result<Transaction, ParserError> {
-        if fields.len() != 8 {
+    fn parse_record(
+        fields: &[String],
+        columns: usize,
+        line_num: usize,
+        options: &CsvParseOptions,
+    ) -> Result<Transaction, ParserError> {
+        if fields.len() != columns {
             return Err(ParserError::Parse(format!(
-                "Line {}: Expected 8 fields, got {}",
+                "Line {}: Expected {} fields, got {}",
                 line_num,
+                columns,
                 fields.len()
             )));
         }
 
-        let tx_id = fields[0].parse::<u64>().map_err(|e| {
-            ParserError::Parse(format!(
-                "Line {}: Invalid TX_ID '{}': {}",
-                line_num, fields[0], e
-            ))
-        })?;
+        let tx_id: u64 = parse_field(&fields[0], "TX_ID", line_num)?;
 
         let tx_type = match fields[1].as_str() {
             "DEPOSIT" => TransactionType::Deposit,
             "TRANSFER" => TransactionType::Transfer,
             "WITHDRAWAL" => TransactionType::Withdrawal,
             other => {
-                return Err(ParserError::Parse(format!(
-                    "Line {}: Invalid TX_TYPE '{}', must be DEPOSIT, TRANSFER, or WITHDRAWAL",
-                    line_num, other
-                )));
+                return Err(ParserError::InvalidEnum {
+                    field: Field::TxType,
+                    value: other.to_string(),
+                    line: Some(line_num),
+                    record: None,
+                    offset: None,
+                });
             }
         };
 
-        let from_user_id = fields[2].parse::<u64>().map_err(|e| {
-            ParserError::Parse(format!(
-                "Line {}: Invalid FROM_USER_ID '{}': {}",
-                line_num, fields[2], e
-            ))
-        })?;
-
-        let to_user_id = fields[3].parse::<u64>().map_err(|e| {
-            ParserError::Parse(format!(
-                "Line {}: Invalid TO_USER_ID '{}': {}",
-                line_num, fields[3], e
-            ))
-        })?;
-
-        let amount = fields[4].parse::<i64>().map_err(|e| {
-            ParserError::Parse(format!(
-                "Line {}: Invalid AMOUNT '{}': {}",
-                line_num, fields[4], e
-            ))
-        })?;
-
-        let timestamp = fields[5].parse::<u64>().map_err(|e| {
-            ParserError::Parse(format!(
-                "Line {}: Invalid TIMESTAMP '{}': {}",
-                line_num, fields[5], e
-            ))
-        })?;
+        let from_user_id: u64 = parse_field(&fields[2], "FROM_USER_ID", line_num)?;
+
+        let to_user_id: u64 = parse_field(&fields[3], "TO_USER_ID", line_num)?;
+
+        let amount: i64 = if options.lenient_amount {
+            let digits: String = fields[4]
+                .chars()
+                .filter(|c| !matches!(c, ',' | '_') && !c.is_whitespace())
+                .collect();
+            parse_field(&digits, "AMOUNT", line_num)?
+        } else {
+            parse_field(&fields[4], "AMOUNT", line_num)?
+        };
+
+        let timestamp: u64 = parse_field(&fields[5], "TIMESTAMP", line_num)?;
 
         let status = match fields[6].as_str() {
             "SUCCESS" => TransactionStatus::Success,
             "FAILURE" => TransactionStatus::Failure,
             "PENDING" => TransactionStatus::Pending,
+            "REVERSED" => TransactionStatus::Reversed,
             other => {
-                return Err(ParserError::Parse(format!(
-                    "Line {}: Invalid STATUS '{}', must be SUCCESS, FAILURE, or PENDING",
-                    line_num, other
-                )));
+                return Err(ParserError::InvalidEnum {
+                    field: Field::Status,
+                    value: other.to_string(),
+                    line: Some(line_num),
+                    record: None,
+                    offset: None,
+                });
             }
         };
 
-        let description = Self::unescape_description(&fields[7]);
+        let description = match fields[7].strip_prefix(FORMULA_GUARD) {
+            Some(original) if options.sanitize_formulas => original.to_string(),
+            _ => fields[7].clone(),
+        };
+
+        let currency = match fields.get(8).map(|c| c.trim()) {
+            Some(code) if !code.is_empty() => {
+                check_currency_code(code)
+                    .map_err(|msg| ParserError::Parse(format!("Line {}: {}", line_num, msg)))?;
+                Some(code.to_string())
+            }
+            _ => None,
+        };
 
-        Self::validate_record(tx_type, from_user_id, to_user_id, amount, line_num)?;
+        if !options.signed_amounts {
+            check_positive_amount(amount, Format::Csv, Some(line_num))?;
+        }
+        check_user_ids(tx_type, from_user_id, to_user_id, Some(line_num))?;
 
         Ok(Transaction {
             tx_id,
@@ -292,59 +840,10 @@ impl CsvParser {
             timestamp,
             status,
             description,
+            currency,
         })
     }
 
-    fn validate_record(
-        tx_type: TransactionType,
-        from_user_id: u64,
-        to_user_id: u64,
-        amount: i64,
-        line_num: usize,
-    ) -> Result<(), ParserError> {
-        if amount <= 0 {
-            return Err(ParserError::Parse(format!(
-                "Line {}: AMOUNT must be positive in CSV format, got {}",
-                line_num, amount
-            )));
-        }
-
-        match tx_type {
-            TransactionType::Deposit => {
-                if from_user_id != 0 {
-                    return Err(ParserError::Parse(format!(
-                        "Line {}: DEPOSIT must have FROM_USER_ID = 0, got {}",
-                        line_num, from_user_id
-                    )));
-                }
-            }
-            TransactionType::Withdrawal => {
-                if to_user_id != 0 {
-                    return Err(ParserError::Parse(format!(
-                        "Line {}: WITHDRAWAL must have TO_USER_ID = 0, got {}",
-                        line_num, to_user_id
-                    )));
-                }
-            }
-            TransactionType::Transfer => {
-                if from_user_id == 0 {
-                    return Err(ParserError::Parse(format!(
-                        "Line {}: TRANSFER cannot have FROM_USER_ID = 0",
-                        line_num
-                    )));
-                }
-                if to_user_id == 0 {
-                    return Err(ParserError::Parse(format!(
-                        "Line {}: TRANSFER cannot have TO_USER_ID = 0",
-                        line_num
-                    )));
-                }
-            }
-        }
-
-        Ok(())
-    }
-
     fn escape_description(description: &str) -> String {
         let escaped = description.replace('"', "\"\"");
         format!("\"{}\"", escaped)
@@ -353,7 +852,7 @@ impl CsvParser {
     fn unescape_description(description: &str) -> String {
         let trimmed = description.trim();
 
-        if trimmed.starts_with('"') && trimmed.ends_with('"') {
+        if Self::is_quoted(trimmed) {
             let content = &trimmed[1..trimmed.len() - 1];
             content.replace("\"\"", "\"")
         } else {
@@ -362,34 +861,10 @@ impl CsvParser {
     }
 }
 
-// Реализуем трейт ParseFromRead для CsvTransactions
-impl<R: Read> ParseFromRead<R> for CsvTransactions {
-    fn parse(reader: &mut R) -> Result<Self, ParserError> {
-        let transactions = CsvParser::parse_records(reader)?;
-        Ok(CsvTransactions(transactions))
-    }
-}
-
-// Реализуем трейт WriteTo для CsvTransactions
-impl<W: Write> WriteTo<W> for CsvTransactions {
-    fn write(&self, writer: &mut W) -> Result<(), ParserError> {
-        CsvParser::write_records(&self.0, writer)
-    }
-}
-
-// Реализуем трейт WriteTo для среза CsvTransactions
-impl<W: Write> WriteTo<W> for [CsvTransactions] {
-    fn write(&self, writer: &mut W) -> Result<(), ParserError> {
-        for transactions in self {
-            transactions.write(writer)?;
-        }
-        Ok(())
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{Format, assert_roundtrip};
     use std::io::Cursor;
 
     const VALID_CSV: &str = r#"TX_ID,TX_TYPE,FROM_USER_ID,TO_USER_ID,AMOUNT,TIMESTAMP,STATUS,DESCRIPTION
@@ -459,6 +934,19 @@ mod tests {
         assert_eq!(transactions[0].description, r#"Test with "quotes" inside"#);
     }
 
+    #[test]
+    fn test_parse_with_max_description_len() {
+        let mut options = ParseOptions::default();
+        options.rules.max_description_len = Some(10);
+
+        let cursor = Cursor::new(VALID_CSV);
+        let result = CsvParser::parse_records_with(cursor, &options);
+
+        assert!(
+            matches!(result, Err(ParserError::Validation(msg)) if msg.starts_with("Line 2:") && msg.contains("too long"))
+        );
+    }
+
     #[test]
     fn test_parse_csv_wrong_headers() {
         let csv = r#"ID,TYPE,FROM,TO,AMOUNT,TIME,STATUS,DESC
@@ -482,6 +970,7 @@ mod tests {
                 timestamp: 1672531200000,
                 status: TransactionStatus::Success,
                 description: "Initial deposit".to_string(),
+                currency: None,
             },
             Transaction {
                 tx_id: 1002,
@@ -492,6 +981,7 @@ mod tests {
                 timestamp: 1672534800000,
                 status: TransactionStatus::Failure,
                 description: "Withdrawal with, comma and \"quotes\"".to_string(),
+                currency: None,
             },
         ];
 
@@ -521,6 +1011,372 @@ mod tests {
         assert_eq!(transactions[1].description, parsed[1].description);
     }
 
+    #[test]
+    fn test_write_records_without_header() {
+        let transaction = Transaction {
+            tx_id: 1001,
+            tx_type: TransactionType::Deposit,
+            from_user_id: 0,
+            to_user_id: 501,
+            amount: 50000,
+            timestamp: 1672531200000,
+            status: TransactionStatus::Success,
+            description: "No header".to_string(),
+            currency: None,
+        };
+
+        let options = CsvWriteOptions {
+            write_header: false,
+            ..Default::default()
+        };
+        let mut buffer = Vec::new();
+        CsvParser::write_records_with(&[transaction], &mut buffer, &options).unwrap();
+
+        let csv_output = String::from_utf8(buffer).unwrap();
+        assert_eq!(
+            csv_output,
+            "1001,DEPOSIT,0,501,50000,1672531200000,SUCCESS,\"No header\"\n"
+        );
+    }
+
+    #[test]
+    fn test_csv_writer_header_written_once() {
+        let transaction = Transaction {
+            tx_id: 1001,
+            tx_type: TransactionType::Deposit,
+            from_user_id: 0,
+            to_user_id: 501,
+            amount: 50000,
+            timestamp: 1672531200000,
+            status: TransactionStatus::Success,
+            description: "Test".to_string(),
+            currency: None,
+        };
+
+        let mut writer = CsvWriter::new(Vec::new());
+        writer.write_header().unwrap();
+        writer.write_one(&transaction).unwrap();
+        writer.write_header().unwrap();
+        writer.write_one(&transaction).unwrap();
+        writer.flush().unwrap();
+
+        let csv_output = String::from_utf8(writer.into_inner()).unwrap();
+        assert_eq!(csv_output.matches("TX_ID,TX_TYPE").count(), 1);
+        assert_eq!(csv_output.lines().count(), 3);
+        assert!(csv_output.starts_with("TX_ID,TX_TYPE"));
+    }
+
+    #[test]
+    fn test_csv_writer_flush_every_surfaces_errors() {
+        struct FailingFlush {
+            flushes: usize,
+        }
+
+        impl Write for FailingFlush {
+            fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+                Ok(buf.len())
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                self.flushes += 1;
+                Err(std::io::Error::other("flush failed"))
+            }
+        }
+
+        let transaction = Transaction {
+            tx_id: 1001,
+            tx_type: TransactionType::Deposit,
+            from_user_id: 0,
+            to_user_id: 501,
+            amount: 50000,
+            timestamp: 1672531200000,
+            status: TransactionStatus::Success,
+            description: "Test".to_string(),
+            currency: None,
+        };
+
+        let mut writer = CsvWriter::new(FailingFlush { flushes: 0 }).flush_every(2);
+        assert!(writer.write_one(&transaction).is_ok());
+
+        let result = writer.write_one(&transaction);
+        assert!(matches!(result, Err(ParserError::Io(_))));
+        assert_eq!(writer.into_inner().flushes, 1);
+    }
+
+    #[test]
+    fn test_embedded_cr_in_description_roundtrips() {
+        let transaction = Transaction {
+            tx_id: 1001,
+            tx_type: TransactionType::Deposit,
+            from_user_id: 0,
+            to_user_id: 501,
+            amount: 50000,
+            timestamp: 1672531200000,
+            status: TransactionStatus::Success,
+            description: "a\rb".to_string(),
+            currency: None,
+        };
+
+        let mut buffer = Vec::new();
+        CsvParser::write_records(std::slice::from_ref(&transaction), &mut buffer).unwrap();
+
+        let parsed = CsvParser::parse_records(Cursor::new(&buffer)).unwrap();
+        assert_eq!(parsed, vec![transaction]);
+    }
+
+    #[test]
+    fn test_parse_csv_with_cr_line_endings() {
+        let csv = "TX_ID,TX_TYPE,FROM_USER_ID,TO_USER_ID,AMOUNT,TIMESTAMP,STATUS,DESCRIPTION\r\
+                   1001,DEPOSIT,0,501,50000,1672531200000,SUCCESS,\"First\"\r\n\
+                   1002,WITHDRAWAL,502,0,1000,1672538400000,PENDING,\"Second\"\r";
+
+        let transactions = CsvParser::parse_records(Cursor::new(csv)).unwrap();
+
+        assert_eq!(transactions.len(), 2);
+        assert!(matches!(transactions[0].status, TransactionStatus::Success));
+        assert!(matches!(transactions[1].status, TransactionStatus::Pending));
+        assert_eq!(transactions[1].description, "Second");
+    }
+
+    #[test]
+    fn test_parse_one_line() {
+        let header: Vec<String> = [
+            "TX_ID",
+            "TX_TYPE",
+            "FROM_USER_ID",
+            "TO_USER_ID",
+            "AMOUNT",
+            "TIMESTAMP",
+            "STATUS",
+            "DESCRIPTION",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+
+        let tx = CsvParser::parse_one(
+            "1002,TRANSFER,501,502,15000,1672534800000,FAILURE,\"Payment, invoice #123\"",
+            &header,
+        )
+        .unwrap();
+
+        assert_eq!(tx.tx_id, 1002);
+        assert!(matches!(tx.tx_type, TransactionType::Transfer));
+        assert!(matches!(tx.status, TransactionStatus::Failure));
+        assert_eq!(tx.description, "Payment, invoice #123");
+
+        assert!(CsvParser::parse_one("1002,TRANSFER", &header).is_err());
+        assert!(CsvParser::parse_one("1002,TRANSFER", &header[..2]).is_err());
+    }
+
+    #[test]
+    fn test_escape_roundtrip_boundary_descriptions() {
+        for description in [",", "\"", "\"\"", "\n", "", " padded "] {
+            let escaped = CsvParser::escape_description(description);
+            assert_eq!(
+                CsvParser::unescape_description(&escaped),
+                description,
+                "escaped: {:?}",
+                escaped
+            );
+        }
+    }
+
+    #[test]
+    fn test_roundtrip_boundary_descriptions() {
+        let descriptions = [",", "\"", "\"\"", "\n", ""];
+        let original: Vec<Transaction> = descriptions
+            .iter()
+            .enumerate()
+            .map(|(i, description)| Transaction {
+                tx_id: 1000 + i as u64,
+                tx_type: TransactionType::Deposit,
+                from_user_id: 0,
+                to_user_id: 501,
+                amount: 100,
+                timestamp: 1672531200000,
+                status: TransactionStatus::Success,
+                description: description.to_string(),
+                currency: None,
+            })
+            .collect();
+
+        assert_roundtrip(Format::Csv, &original, |a, b| a == b);
+    }
+
+    #[test]
+    fn test_parse_unclosed_multiline_description_reports_start_line() {
+        let csv = "TX_ID,TX_TYPE,FROM_USER_ID,TO_USER_ID,AMOUNT,TIMESTAMP,STATUS,DESCRIPTION\n\
+                   1001,DEPOSIT,0,501,50000,1672531200000,SUCCESS,\"Open\n\
+                   still open";
+
+        let result = CsvParser::parse_records(Cursor::new(csv));
+
+        match result {
+            Err(ParserError::Parse(msg)) => assert!(msg.contains("Line 2"), "{}", msg),
+            other => panic!("Expected parse error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_stops_at_max_records() {
+        let csv = "TX_ID,TX_TYPE,FROM_USER_ID,TO_USER_ID,AMOUNT,TIMESTAMP,STATUS,DESCRIPTION\n\
+                   1001,DEPOSIT,0,501,100,1672531200000,SUCCESS,\"One\"\n\
+                   1002,DEPOSIT,0,501,100,1672531200000,SUCCESS,\"Two\"\n\
+                   1003,DEPOSIT,0,501,100,1672531200000,SUCCESS,\"Three\"";
+
+        let mut options = ParseOptions {
+            max_records: Some(2),
+            ..Default::default()
+        };
+
+        match CsvParser::parse_records_with(Cursor::new(csv), &options) {
+            Err(ParserError::Parse(msg)) => assert!(msg.contains("record limit exceeded")),
+            other => panic!("Expected record limit error, got {:?}", other),
+        }
+
+        options.max_records = Some(3);
+        let transactions = CsvParser::parse_records_with(Cursor::new(csv), &options).unwrap();
+        assert_eq!(transactions.len(), 3);
+    }
+
+    #[test]
+    fn test_repeated_header_is_clear_error_by_default() {
+        let csv = "TX_ID,TX_TYPE,FROM_USER_ID,TO_USER_ID,AMOUNT,TIMESTAMP,STATUS,DESCRIPTION\n\
+                   1001,DEPOSIT,0,501,100,1672531200000,SUCCESS,\"One\"\n\
+                   TX_ID,TX_TYPE,FROM_USER_ID,TO_USER_ID,AMOUNT,TIMESTAMP,STATUS,DESCRIPTION\n\
+                   1002,DEPOSIT,0,501,100,1672531200000,SUCCESS,\"Two\"";
+
+        match CsvParser::parse_records(Cursor::new(csv)) {
+            Err(ParserError::Parse(msg)) => assert_eq!(msg, "Line 3: repeated header"),
+            other => panic!("Expected repeated header error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_repeated_header_tolerated_with_warning() {
+        let csv = "TX_ID,TX_TYPE,FROM_USER_ID,TO_USER_ID,AMOUNT,TIMESTAMP,STATUS,DESCRIPTION\n\
+                   1001,DEPOSIT,0,501,100,1672531200000,SUCCESS,\"One\"\n\
+                   TX_ID,TX_TYPE,FROM_USER_ID,TO_USER_ID,AMOUNT,TIMESTAMP,STATUS,DESCRIPTION\n\
+                   1002,DEPOSIT,0,501,100,1672531200000,SUCCESS,\"Two\"";
+
+        let mut options = ParseOptions::default();
+        options.csv.tolerate_repeated_header = true;
+        let mut warnings = Vec::new();
+
+        let transactions =
+            CsvParser::parse_records_with_warnings(Cursor::new(csv), &options, &mut warnings)
+                .unwrap();
+
+        assert_eq!(transactions.len(), 2);
+        assert_eq!(
+            warnings,
+            vec!["Line 3: skipped repeated header".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_repeated_source_header_tolerated_with_field_map() {
+        let csv = "ID,TX_TYPE,FROM_USER_ID,TO_USER_ID,AMOUNT,TIMESTAMP,STATUS,DESCRIPTION\n\
+                   1001,DEPOSIT,0,501,100,1672531200000,SUCCESS,\"One\"\n\
+                   ID,TX_TYPE,FROM_USER_ID,TO_USER_ID,AMOUNT,TIMESTAMP,STATUS,DESCRIPTION\n\
+                   1002,DEPOSIT,0,501,100,1672531200000,SUCCESS,\"Two\"";
+
+        let mut options = ParseOptions::default();
+        options.csv.field_map =
+            Some(crate::CsvFieldMap::from_json(r#"{"columns":{"ID":"TX_ID"}}"#).unwrap());
+        options.csv.tolerate_repeated_header = true;
+        let mut warnings = Vec::new();
+
+        let transactions =
+            CsvParser::parse_records_with_warnings(Cursor::new(csv), &options, &mut warnings)
+                .unwrap();
+
+        let ids: Vec<u64> = transactions.iter().map(|tx| tx.tx_id).collect();
+        assert_eq!(ids, vec![1001, 1002]);
+        assert_eq!(
+            warnings,
+            vec!["Line 3: skipped repeated header".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_leading_comment_lines_skipped_when_configured() {
+        let csv = "# generated by exporter 2.1\n\
+                   # 2023-01-01\n\
+                   TX_ID,TX_TYPE,FROM_USER_ID,TO_USER_ID,AMOUNT,TIMESTAMP,STATUS,DESCRIPTION\n\
+                   1001,DEPOSIT,0,501,100,1672531200000,SUCCESS,\"One\"";
+
+        assert!(CsvParser::parse_records(Cursor::new(csv)).is_err());
+
+        let mut options = ParseOptions::default();
+        options.csv.comment_char = Some('#');
+        let transactions = CsvParser::parse_records_with(Cursor::new(csv), &options).unwrap();
+
+        assert_eq!(transactions.len(), 1);
+        assert_eq!(transactions[0].tx_id, 1001);
+    }
+
+    #[test]
+    fn test_comment_with_unbalanced_quote_does_not_swallow_rows() {
+        let csv = "# exported by \"tool\n\
+                   TX_ID,TX_TYPE,FROM_USER_ID,TO_USER_ID,AMOUNT,TIMESTAMP,STATUS,DESCRIPTION\n\
+                   1001,DEPOSIT,0,501,100,1672531200000,SUCCESS,\"One\"\n\
+                   1002,DEPOSIT,0,501,100,1672531200000,SUCCESS,\"Two\"";
+
+        let mut options = ParseOptions::default();
+        options.csv.comment_char = Some('#');
+        let transactions = CsvParser::parse_records_with(Cursor::new(csv), &options).unwrap();
+
+        let ids: Vec<u64> = transactions.iter().map(|tx| tx.tx_id).collect();
+        assert_eq!(ids, vec![1001, 1002]);
+    }
+
+    #[test]
+    fn test_trailing_empty_fields_are_ignored_with_warning() {
+        let csv = "TX_ID,TX_TYPE,FROM_USER_ID,TO_USER_ID,AMOUNT,TIMESTAMP,STATUS,DESCRIPTION,,\n\
+                   1001,DEPOSIT,0,501,100,1672531200000,SUCCESS,\"One\",,\n\
+                   1002,DEPOSIT,0,501,100,1672531200000,SUCCESS,\"Two\"";
+
+        let mut warnings = Vec::new();
+        let transactions = CsvParser::parse_records_with_warnings(
+            Cursor::new(csv),
+            &ParseOptions::default(),
+            &mut warnings,
+        )
+        .unwrap();
+
+        assert_eq!(transactions.len(), 2);
+        assert_eq!(transactions[0].description, "One");
+        assert_eq!(
+            warnings,
+            vec![
+                "Line 1: ignored 2 trailing empty field(s)".to_string(),
+                "Line 2: ignored 2 trailing empty field(s)".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_trailing_nonempty_or_missing_fields_still_rejected() {
+        let header = "TX_ID,TX_TYPE,FROM_USER_ID,TO_USER_ID,AMOUNT,TIMESTAMP,STATUS,DESCRIPTION\n";
+
+        let extra = format!(
+            "{}1001,DEPOSIT,0,501,100,1672531200000,SUCCESS,\"One\",,junk",
+            header
+        );
+        match CsvParser::parse_records(Cursor::new(extra)) {
+            Err(ParserError::Parse(msg)) => assert!(msg.contains("Expected 8 fields, got 10")),
+            other => panic!("Expected field count error, got {:?}", other),
+        }
+
+        let short = format!("{}1001,DEPOSIT,0,501,100,1672531200000,SUCCESS", header);
+        match CsvParser::parse_records(Cursor::new(short)) {
+            Err(ParserError::Parse(msg)) => assert!(msg.contains("Expected 8 fields, got 7")),
+            other => panic!("Expected field count error, got {:?}", other),
+        }
+    }
+
     #[test]
     fn test_roundtrip() {
         let original_transactions = vec![
@@ -533,6 +1389,7 @@ mod tests {
                 timestamp: 1672531200000,
                 status: TransactionStatus::Success,
                 description: "Test deposit with \"quotes\" and, commas".to_string(),
+                currency: None,
             },
             Transaction {
                 tx_id: 1002,
@@ -543,16 +1400,54 @@ mod tests {
                 timestamp: 1672538400000,
                 status: TransactionStatus::Pending,
                 description: "ATM withdrawal".to_string(),
+                currency: None,
             },
         ];
 
+        assert_roundtrip(Format::Csv, &original_transactions, |a, b| a == b);
+    }
+
+    #[test]
+    fn test_roundtrip_with_currency_column() {
+        let mut transactions = CsvParser::parse_records(Cursor::new(VALID_CSV)).unwrap();
+        assert!(transactions.iter().all(|tx| tx.currency.is_none()));
+        transactions[0].currency = Some("USD".to_string());
+
         let mut buffer = Vec::new();
-        CsvParser::write_records(&original_transactions, &mut buffer).unwrap();
+        CsvParser::write_records(&transactions, &mut buffer).unwrap();
 
-        let cursor = Cursor::new(&buffer);
-        let parsed_transactions = CsvParser::parse_records(cursor).unwrap();
+        let output = String::from_utf8(buffer.clone()).unwrap();
+        assert!(output.starts_with(
+            "TX_ID,TX_TYPE,FROM_USER_ID,TO_USER_ID,AMOUNT,TIMESTAMP,STATUS,DESCRIPTION,CURRENCY\n"
+        ));
+        assert!(output.contains("\"Payment for services\",\n"));
 
-        assert_eq!(original_transactions, parsed_transactions);
+        let parsed = CsvParser::parse_records(Cursor::new(&buffer)).unwrap();
+        assert_eq!(parsed, transactions);
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_currency() {
+        let csv = "TX_ID,TX_TYPE,FROM_USER_ID,TO_USER_ID,AMOUNT,TIMESTAMP,STATUS,DESCRIPTION,CURRENCY\n\
+                   1001,DEPOSIT,0,501,50000,1672531200000,SUCCESS,\"Test\",usd";
+
+        let result = CsvParser::parse_records(Cursor::new(csv));
+        assert!(matches!(
+            result,
+            Err(ParserError::Parse(msg)) if msg == "Line 2: invalid CURRENCY 'usd': expected a 3-letter code"
+        ));
+    }
+
+    #[test]
+    fn test_csv_writer_rejects_currency_without_column() {
+        let mut transactions = CsvParser::parse_records(Cursor::new(VALID_CSV)).unwrap();
+        transactions[0].currency = Some("USD".to_string());
+
+        let mut writer = CsvWriter::new(Vec::new());
+        assert!(matches!(
+            writer.write_one(&transactions[0]),
+            Err(ParserError::Validation(_))
+        ));
     }
 
     #[test]
@@ -658,7 +1553,147 @@ mod tests {
         let cursor = Cursor::new(csv);
         let result = CsvParser::parse_records(cursor);
 
-        assert!(matches!(result, Err(ParserError::Parse(_))));
+        assert!(matches!(
+            result,
+            Err(ParserError::BusinessRule { line: Some(2), .. })
+        ));
+    }
+
+    #[test]
+    fn test_signed_amounts_mode() {
+        let csv = r#"TX_ID,TX_TYPE,FROM_USER_ID,TO_USER_ID,AMOUNT,TIMESTAMP,STATUS,DESCRIPTION
+1001,WITHDRAWAL,501,0,-1000,1672538400000,PENDING,"Debit""#;
+
+        assert!(matches!(
+            CsvParser::parse_records(Cursor::new(csv)),
+            Err(ParserError::BusinessRule { .. })
+        ));
+
+        let mut options = ParseOptions::default();
+        options.csv.signed_amounts = true;
+        let transactions = CsvParser::parse_records_with(Cursor::new(csv), &options).unwrap();
+
+        assert_eq!(transactions.len(), 1);
+        assert_eq!(transactions[0].amount, -1000);
+    }
+
+    #[test]
+    fn test_lenient_amount_strips_grouping_separators() {
+        let csv = r#"TX_ID,TX_TYPE,FROM_USER_ID,TO_USER_ID,AMOUNT,TIMESTAMP,STATUS,DESCRIPTION
+1001,DEPOSIT,0,501,"50,000",1672531200000,SUCCESS,"Salary, 50,000"
+1002,DEPOSIT,0,501,1_250 000,1672531200000,SUCCESS,"a_b c""#;
+
+        assert!(matches!(
+            CsvParser::parse_records(Cursor::new(csv)),
+            Err(ParserError::Parse(msg)) if msg.contains("invalid AMOUNT '50,000'")
+        ));
+
+        let mut options = ParseOptions::default();
+        options.csv.lenient_amount = true;
+        let transactions = CsvParser::parse_records_with(Cursor::new(csv), &options).unwrap();
+
+        assert_eq!(transactions[0].amount, 50000);
+        assert_eq!(transactions[0].description, "Salary, 50,000");
+        assert_eq!(transactions[1].amount, 1250000);
+        assert_eq!(transactions[1].description, "a_b c");
+    }
+
+    #[test]
+    fn test_parse_collect_errors_reports_every_bad_row() {
+        let csv = r#"TX_ID,TX_TYPE,FROM_USER_ID,TO_USER_ID,AMOUNT,TIMESTAMP,STATUS,DESCRIPTION
+1001,DEPOSIT,0,501,100,1672531200000,SUCCESS,"Valid"
+1002,REFUND,0,501,100,1672531200000,SUCCESS,"Bad type"
+1003,DEPOSIT,0,501,abc,1672531200000,SUCCESS,"Bad amount"
+1004,DEPOSIT,0,501,100,1672531200000,SUCCESS,"Valid"
+1005,WITHDRAWAL,501,502,100,1672531200000,SUCCESS,"Bad actors""#;
+
+        let errors = CsvParser::parse_collect_errors(Cursor::new(csv)).unwrap_err();
+
+        assert_eq!(errors.len(), 3);
+        for (error, line) in errors.iter().zip([3, 4, 6]) {
+            assert!(
+                error.to_string().contains(&format!("Line {}:", line)),
+                "{}",
+                error
+            );
+        }
+        assert!(matches!(errors[0], ParserError::InvalidEnum { .. }));
+        assert!(matches!(errors[2], ParserError::BusinessRule { .. }));
+    }
+
+    #[test]
+    fn test_parse_collect_errors_returns_records_when_valid() {
+        let csv = r#"TX_ID,TX_TYPE,FROM_USER_ID,TO_USER_ID,AMOUNT,TIMESTAMP,STATUS,DESCRIPTION
+1001,DEPOSIT,0,501,100,1672531200000,SUCCESS,"Valid""#;
+
+        let records = CsvParser::parse_collect_errors(Cursor::new(csv)).unwrap();
+        assert_eq!(records, CsvParser::parse_records(Cursor::new(csv)).unwrap());
+
+        let errors = CsvParser::parse_collect_errors(Cursor::new("ID\n1")).unwrap_err();
+        assert_eq!(errors.len(), 1);
+    }
+
+    #[test]
+    fn test_sanitize_formulas_roundtrip() {
+        let descriptions = ["=cmd|' /C calc'!A0", "-5 fee", "'quoted", "plain", ""];
+        let transactions: Vec<Transaction> = descriptions
+            .iter()
+            .enumerate()
+            .map(|(i, description)| Transaction {
+                tx_id: i as u64 + 1,
+                tx_type: TransactionType::Deposit,
+                from_user_id: 0,
+                to_user_id: 501,
+                amount: 100,
+                timestamp: 1672531200000,
+                status: TransactionStatus::Success,
+                description: description.to_string(),
+                currency: None,
+            })
+            .collect();
+        let write_options = CsvWriteOptions {
+            sanitize_formulas: true,
+            ..Default::default()
+        };
+
+        let mut buffer = Vec::new();
+        CsvParser::write_records_with(&transactions, &mut buffer, &write_options).unwrap();
+        let output = String::from_utf8(buffer).unwrap();
+
+        assert!(output.contains(",\"'=cmd|' /C calc'!A0\""));
+        assert!(output.contains(",\"'-5 fee\""));
+        assert!(output.contains(",\"''quoted\""));
+        assert!(output.contains(",\"plain\""));
+
+        let plain = CsvParser::parse_records(Cursor::new(&output)).unwrap();
+        assert_eq!(plain[0].description, "'=cmd|' /C calc'!A0");
+
+        let mut options = ParseOptions::default();
+        options.csv.sanitize_formulas = true;
+        let parsed = CsvParser::parse_records_with(Cursor::new(&output), &options).unwrap();
+        assert_eq!(parsed, transactions);
+    }
+
+    #[test]
+    fn test_parse_invalid_tx_type_is_typed() {
+        let csv = r#"TX_ID,TX_TYPE,FROM_USER_ID,TO_USER_ID,AMOUNT,TIMESTAMP,STATUS,DESCRIPTION
+1001,REFUND,0,501,1000,1672538400000,SUCCESS,"Test""#;
+
+        let error = CsvParser::parse_records(Cursor::new(csv)).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Parse error: Line 2: Invalid TX_TYPE 'REFUND', must be DEPOSIT, TRANSFER, or WITHDRAWAL"
+        );
+        match error {
+            ParserError::InvalidEnum {
+                field, value, line, ..
+            } => {
+                assert_eq!(field, Field::TxType);
+                assert_eq!(value, "REFUND");
+                assert_eq!(line, Some(2));
+            }
+            other => panic!("Expected invalid TX_TYPE, got {:?}", other),
+        }
     }
 
     #[test]
@@ -672,6 +1707,7 @@ mod tests {
             timestamp: 1672531200000,
             status: TransactionStatus::Success,
             description: "Simple description".to_string(),
+            currency: None,
         };
 
         let mut buffer = Vec::new();
@@ -702,10 +1738,11 @@ mod tests {
             timestamp: 1672531200000,
             status: TransactionStatus::Success,
             description: "Record number 1".to_string(),
+            currency: None,
         };
 
         let mut buffer = Vec::new();
-        CsvParser::write_records(&[original.clone()], &mut buffer).unwrap();
+        CsvParser::write_records(std::slice::from_ref(&original), &mut buffer).unwrap();
 
         let csv_output = String::from_utf8(buffer).unwrap();
         println!("CSV output: {}", csv_output);
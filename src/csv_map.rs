@@ -0,0 +1,197 @@
+use crate::ParserError;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// Столбцы CSV формата в каноническом порядке
+const COLUMNS: [&str; 9] = [
+    "TX_ID",
+    "TX_TYPE",
+    "FROM_USER_ID",
+    "TO_USER_ID",
+    "AMOUNT",
+    "TIMESTAMP",
+    "STATUS",
+    "DESCRIPTION",
+    "CURRENCY",
+];
+
+/// Индекс столбца TX_TYPE в каноническом порядке
+const TX_TYPE_COLUMN: usize = 1;
+
+/// Индекс столбца STATUS в каноническом порядке
+const STATUS_COLUMN: usize = 6;
+
+/// Отображение нестандартного CSV на схему транзакций
+///
+/// Переименовывает столбцы заголовка и переводит значения TX_TYPE и
+/// STATUS до разбора. Столбцы после переименования могут идти в любом
+/// порядке. Значения, отсутствующие в отображении, используются как есть,
+/// поэтому неизвестное значение по-прежнему приводит к ошибке.
+///
+/// # Пример
+/// ```
+/// use parser_lib::{CsvFieldMap, CsvParser, ParseOptions};
+/// use std::io::Cursor;
+///
+/// let map = CsvFieldMap::from_json(r#"{ "columns": {"ID": "TX_ID"}, "types": {"CR": "DEPOSIT"} }"#)
+///     .unwrap();
+/// let mut options = ParseOptions::default();
+/// options.csv.field_map = Some(map);
+///
+/// let csv = "ID,TX_TYPE,FROM_USER_ID,TO_USER_ID,AMOUNT,TIMESTAMP,STATUS,DESCRIPTION\n\
+///            1001,CR,0,501,50000,1672531200000,SUCCESS,\"Test\"";
+/// let records = CsvParser::parse_records_with(Cursor::new(csv), &options).unwrap();
+/// assert_eq!(records[0].tx_id, 1001);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CsvFieldMap {
+    /// Имена столбцов источника и соответствующие им столбцы схемы
+    pub columns: HashMap<String, String>,
+
+    /// Значения TX_TYPE источника и соответствующие им типы схемы
+    pub types: HashMap<String, String>,
+
+    /// Значения STATUS источника и соответствующие им статусы схемы
+    pub statuses: HashMap<String, String>,
+}
+
+impl CsvFieldMap {
+    /// Читает отображение из JSON
+    ///
+    /// # Аргументы
+    /// * `json` - Объект с необязательными ключами `columns`, `types`, `statuses`
+    ///
+    /// # Возвращает
+    /// * `Ok(CsvFieldMap)` - Отображение
+    /// * `Err(ParserError::Parse)` - Некорректный JSON или неизвестный ключ
+    pub fn from_json(json: &str) -> Result<Self, ParserError> {
+        serde_json::from_str(json)
+            .map_err(|e| ParserError::Parse(format!("Invalid CSV field map: {}", e)))
+    }
+
+    /// Переименовывает столбцы заголовка и вычисляет порядок полей
+    ///
+    /// # Аргументы
+    /// * `headers` - Столбцы заголовка источника
+    ///
+    /// # Возвращает
+    /// * `Ok((columns, order))` - Канонические столбцы и индексы полей
+    ///   источника для каждого из них
+    /// * `Err(ParserError::Parse)` - Столбец отсутствует, повторяется или неизвестен
+    pub(crate) fn map_header(
+        &self,
+        headers: &[String],
+    ) -> Result<(Vec<String>, Vec<usize>), ParserError> {
+        let renamed: Vec<&str> = headers
+            .iter()
+            .map(|h| self.columns.get(h.trim()).map_or(h.trim(), String::as_str))
+            .collect();
+
+        if let Some(unknown) = renamed.iter().find(|name| !COLUMNS.contains(name)) {
+            return Err(ParserError::Parse(format!(
+                "Unknown column '{}' after mapping",
+                unknown
+            )));
+        }
+
+        let count = if renamed.contains(&"CURRENCY") {
+            COLUMNS.len()
+        } else {
+            COLUMNS.len() - 1
+        };
+
+        let mut order = Vec::with_capacity(count);
+        for column in &COLUMNS[..count] {
+            let mut positions = renamed
+                .iter()
+                .enumerate()
+                .filter(|(_, name)| *name == column)
+                .map(|(index, _)| index);
+            match (positions.next(), positions.next()) {
+                (Some(index), None) => order.push(index),
+                (None, _) => {
+                    return Err(ParserError::Parse(format!(
+                        "Column '{}' not found after mapping",
+                        column
+                    )));
+                }
+                (Some(_), Some(_)) => {
+                    return Err(ParserError::Parse(format!(
+                        "Column '{}' appears more than once after mapping",
+                        column
+                    )));
+                }
+            }
+        }
+
+        let columns = COLUMNS[..count].iter().map(|c| c.to_string()).collect();
+        Ok((columns, order))
+    }
+
+    /// Переставляет поля строки в канонический порядок и переводит значения
+    ///
+    /// Строка с числом полей, отличным от числа столбцов заголовка,
+    /// не меняется: ошибку сообщит разбор записи.
+    ///
+    /// # Аргументы
+    /// * `fields` - Поля строки в порядке источника
+    /// * `order` - Индексы полей источника из `map_header`
+    pub(crate) fn map_row(&self, fields: Vec<String>, order: &[usize]) -> Vec<String> {
+        if fields.len() != order.len() {
+            return fields;
+        }
+
+        let mut mapped: Vec<String> = order.iter().map(|&i| fields[i].clone()).collect();
+        if let Some(tx_type) = self.types.get(&mapped[TX_TYPE_COLUMN]) {
+            mapped[TX_TYPE_COLUMN] = tx_type.clone();
+        }
+        if let Some(status) = self.statuses.get(&mapped[STATUS_COLUMN]) {
+            mapped[STATUS_COLUMN] = status.clone();
+        }
+        mapped
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(line: &str) -> Vec<String> {
+        line.split(',').map(String::from).collect()
+    }
+
+    #[test]
+    fn test_map_header_renames_and_reorders() {
+        let map =
+            CsvFieldMap::from_json(r#"{ "columns": {"ID": "TX_ID", "KIND": "TX_TYPE"} }"#).unwrap();
+        let source = headers("KIND,ID,FROM_USER_ID,TO_USER_ID,AMOUNT,TIMESTAMP,STATUS,DESCRIPTION");
+
+        let (columns, order) = map.map_header(&source).unwrap();
+        assert_eq!(columns[..2], ["TX_ID", "TX_TYPE"]);
+        assert_eq!(order, vec![1, 0, 2, 3, 4, 5, 6, 7]);
+    }
+
+    #[test]
+    fn test_map_header_rejects_missing_and_duplicate_columns() {
+        let map = CsvFieldMap::from_json(r#"{ "columns": {"ID": "TX_ID"} }"#).unwrap();
+
+        let missing = headers("ID,FROM_USER_ID,TO_USER_ID,AMOUNT,TIMESTAMP,STATUS,DESCRIPTION");
+        let err = map.map_header(&missing).unwrap_err();
+        assert!(err.to_string().contains("'TX_TYPE' not found"));
+
+        let duplicate =
+            headers("ID,TX_ID,TX_TYPE,FROM_USER_ID,TO_USER_ID,AMOUNT,TIMESTAMP,STATUS,DESCRIPTION");
+        let err = map.map_header(&duplicate).unwrap_err();
+        assert!(err.to_string().contains("more than once"));
+    }
+
+    #[test]
+    fn test_from_json_rejects_unknown_keys() {
+        assert!(CsvFieldMap::from_json(r#"{ "colums": {} }"#).is_err());
+        assert_eq!(
+            CsvFieldMap::from_json("{}").unwrap(),
+            CsvFieldMap::default()
+        );
+    }
+}
@@ -1,3 +1,4 @@
+use crate::Field;
 use std::fmt;
 
 /// Ошибки, возникающие при парсинге и обработке транзакций.
@@ -46,6 +47,112 @@ pub enum ParserError {
     ///
     /// Возникает при проблемах преобразования данных между разными представлениями.
     Conversion(String),
+
+    /// Обязательное поле отсутствует в записи.
+    MissingField {
+        /// Отсутствующее поле
+        field: Field,
+        /// Номер строки, если формат построчный
+        line: Option<usize>,
+    },
+
+    /// Значение перечисления (TX_TYPE, STATUS) не входит в список допустимых.
+    InvalidEnum {
+        /// Поле с некорректным значением
+        field: Field,
+        /// Значение в том виде, в каком оно прочитано
+        value: String,
+        /// Номер строки, если формат построчный
+        line: Option<usize>,
+        /// Номер записи (с единицы), если формат бинарный
+        record: Option<usize>,
+        /// Смещение начала записи в байтах, если формат бинарный
+        offset: Option<u64>,
+    },
+
+    /// Поле встречается в записи повторно.
+    ///
+    /// Имя хранится строкой: повториться может и неизвестный ключ.
+    DuplicateField {
+        /// Имя повторённого поля
+        field: String,
+        /// Номер строки с повтором
+        line: usize,
+    },
+
+    /// Нарушено встроенное бизнес-правило записи.
+    ///
+    /// Например, депозит с ненулевым `FROM_USER_ID` или неположительная сумма.
+    BusinessRule {
+        /// Описание нарушенного правила
+        rule: String,
+        /// Номер строки, если формат построчный
+        line: Option<usize>,
+    },
+}
+
+/// Отбракованная запись: ошибка и исходный текст записи
+#[derive(Debug)]
+pub(crate) struct Rejected {
+    /// Ошибка разбора или проверки записи
+    pub(crate) error: ParserError,
+    /// Исходный текст записи: строка CSV, блок текстового формата или
+    /// шестнадцатеричный дамп бинарной записи
+    pub(crate) raw: String,
+}
+
+impl ParserError {
+    /// Возвращает номер строки, к которой относится ошибка, если он известен
+    ///
+    /// # Пример
+    /// ```
+    /// use parser_lib::{Field, ParserError};
+    ///
+    /// let error = ParserError::MissingField { field: Field::Amount, line: Some(3) };
+    /// assert_eq!(error.line(), Some(3));
+    /// ```
+    pub fn line(&self) -> Option<usize> {
+        match self {
+            ParserError::MissingField { line, .. }
+            | ParserError::InvalidEnum { line, .. }
+            | ParserError::BusinessRule { line, .. } => *line,
+            ParserError::DuplicateField { line, .. } => Some(*line),
+            _ => None,
+        }
+    }
+
+    /// Дополняет ошибку разбора бинарной записи её номером и смещением
+    ///
+    /// # Аргументы
+    /// * `number` - Номер записи, начиная с единицы
+    /// * `start` - Смещение начала записи в байтах
+    pub(crate) fn at_record(self, number: usize, start: u64) -> ParserError {
+        match self {
+            ParserError::Parse(msg) => ParserError::Parse(format!(
+                "Record {} at byte offset {}: {}",
+                number, start, msg
+            )),
+            ParserError::InvalidEnum {
+                field, value, line, ..
+            } => ParserError::InvalidEnum {
+                field,
+                value,
+                line,
+                record: Some(number),
+                offset: Some(start),
+            },
+            other => other,
+        }
+    }
+}
+
+/// Перечисляет допустимые значения поля-перечисления для сообщения об ошибке
+fn allowed_values(field: Field) -> Option<&'static str> {
+    match field {
+        Field::TxType => Some("DEPOSIT, TRANSFER, or WITHDRAWAL"),
+        Field::Status => Some("SUCCESS, FAILURE, PENDING, or REVERSED"),
+        _ => None,
+    }
 }
 
 impl fmt::Display for ParserError {
@@ -56,10 +163,48 @@ impl fmt::Display for ParserError {
             ParserError::Validation(msg) => write!(f, "Validation error: {}", msg),
             ParserError::UnsupportedFormat => write!(f, "Unsupported format"),
             ParserError::Conversion(msg) => write!(f, "Conversion error: {}", msg),
+            ParserError::MissingField { field, .. } => {
+                write!(f, "Parse error: ")?;
+                write_line_prefix(f, self.line())?;
+                write!(f, "Missing required field: {}", field.name())
+            }
+            ParserError::InvalidEnum {
+                field,
+                value,
+                record,
+                offset,
+                ..
+            } => {
+                write!(f, "Parse error: ")?;
+                write_line_prefix(f, self.line())?;
+                if let (Some(record), Some(offset)) = (record, offset) {
+                    write!(f, "Record {} at byte offset {}: ", record, offset)?;
+                }
+                write!(f, "Invalid {} '{}'", field.name(), value)?;
+                match allowed_values(*field) {
+                    Some(allowed) => write!(f, ", must be {}", allowed),
+                    None => Ok(()),
+                }
+            }
+            ParserError::DuplicateField { field, line } => {
+                write!(f, "Parse error: Line {}: duplicate field '{}'", line, field)
+            }
+            ParserError::BusinessRule { rule, .. } => {
+                write!(f, "Validation error: ")?;
+                write_line_prefix(f, self.line())?;
+                write!(f, "{}", rule)
+            }
         }
     }
 }
 
+fn write_line_prefix(f: &mut fmt::Formatter<'_>, line: Option<usize>) -> fmt::Result {
+    match line {
+        Some(line) => write!(f, "Line {}: ", line),
+        None => Ok(()),
+    }
+}
+
 impl std::error::Error for ParserError {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
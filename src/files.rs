@@ -0,0 +1,117 @@
+use crate::{Format, ParseOptions, ParserError, Transaction};
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Write};
+use std::path::Path;
+
+/// Определяет формат файла по расширению пути
+fn format_of(path: &Path) -> Result<Format, ParserError> {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .and_then(Format::from_extension)
+        .ok_or(ParserError::UnsupportedFormat)
+}
+
+/// Читает транзакции из файла, определяя формат по расширению
+///
+/// # Аргументы
+/// * `path` - Путь к файлу с расширением `.csv`, `.txt` или `.bin`
+///
+/// # Возвращает
+/// * `Ok(Vec<Transaction>)` - Вектор распарсенных транзакций
+/// * `Err(ParserError::UnsupportedFormat)` - Расширение не распознано
+/// * `Err(ParserError)` - Ошибка открытия файла или парсинга
+///
+/// # Пример
+/// ```no_run
+/// use parser_lib::parse_file;
+///
+/// let records = parse_file("records.csv").unwrap();
+/// println!("Прочитано записей: {}", records.len());
+/// ```
+pub fn parse_file<P: AsRef<Path>>(path: P) -> Result<Vec<Transaction>, ParserError> {
+    let path = path.as_ref();
+    let format = format_of(path)?;
+    let file = File::open(path)?;
+    format.parse_records(BufReader::new(file), &ParseOptions::default())
+}
+
+/// Записывает транзакции в файл, определяя формат по расширению
+///
+/// Существующий файл перезаписывается.
+///
+/// # Аргументы
+/// * `path` - Путь к файлу с расширением `.csv`, `.txt` или `.bin`
+/// * `records` - Срез транзакций для записи
+///
+/// # Возвращает
+/// * `Ok(())` - Успешная запись
+/// * `Err(ParserError::UnsupportedFormat)` - Расширение не распознано
+/// * `Err(ParserError)` - Ошибка создания файла или записи
+pub fn write_file<P: AsRef<Path>>(path: P, records: &[Transaction]) -> Result<(), ParserError> {
+    let path = path.as_ref();
+    let format = format_of(path)?;
+    let mut writer = BufWriter::new(File::create(path)?);
+    format.write_records(records, &mut writer)?;
+    writer.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{TransactionStatus, TransactionType};
+
+    fn sample() -> Vec<Transaction> {
+        vec![Transaction {
+            tx_id: 1001,
+            tx_type: TransactionType::Transfer,
+            from_user_id: 501,
+            to_user_id: 502,
+            amount: 25000,
+            timestamp: 1672531200000,
+            status: TransactionStatus::Pending,
+            description: "Files".to_string(),
+            currency: None,
+        }]
+    }
+
+    #[test]
+    fn test_write_and_parse_file_by_extension() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let records = sample();
+
+        for name in ["records.csv", "records.txt", "records.bin", "RECORDS.CSV"] {
+            let path = dir.path().join(name);
+            write_file(&path, &records).unwrap();
+            assert_eq!(parse_file(&path).unwrap(), records, "{}", name);
+        }
+
+        let binary = std::fs::read(dir.path().join("records.bin")).unwrap();
+        assert!(binary.starts_with(b"YPBN"));
+    }
+
+    #[test]
+    fn test_unknown_extension_is_unsupported() {
+        let dir = tempfile::TempDir::new().unwrap();
+
+        for name in ["records.json", "records"] {
+            let path = dir.path().join(name);
+            assert!(matches!(
+                write_file(&path, &sample()),
+                Err(ParserError::UnsupportedFormat)
+            ));
+            assert!(matches!(
+                parse_file(&path),
+                Err(ParserError::UnsupportedFormat)
+            ));
+            assert!(!path.exists());
+        }
+    }
+
+    #[test]
+    fn test_parse_file_wraps_io_errors() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let result = parse_file(dir.path().join("missing.csv"));
+        assert!(matches!(result, Err(ParserError::Io(_))));
+    }
+}
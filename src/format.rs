@@ -0,0 +1,198 @@
+use crate::{BinaryParser, CsvParser, ParseOptions, ParserError, TextParser, Transaction};
+use std::io::{Read, Write};
+
+/// Поддерживаемые форматы файлов транзакций
+///
+/// Используется утилитами командной строки для выбора парсера и
+/// писателя, а также для описания потерь данных при конвертации.
+///
+/// # Пример
+/// ```
+/// use parser_lib::Format;
+///
+/// assert!(!Format::Csv.is_lossy());
+/// assert_eq!(Format::Bin.lossy_fields(), &["DESCRIPTION"]);
+/// ```
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Format {
+    /// CSV формат с заголовком
+    Csv,
+    /// Текстовый формат "KEY: VALUE"
+    Txt,
+    /// Бинарный формат YPBN
+    Bin,
+}
+
+impl Format {
+    /// Возвращает стандартное расширение файлов формата
+    ///
+    /// # Пример
+    /// ```
+    /// use parser_lib::Format;
+    ///
+    /// assert_eq!(Format::Txt.extension(), "txt");
+    /// ```
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Format::Csv => "csv",
+            Format::Txt => "txt",
+            Format::Bin => "bin",
+        }
+    }
+
+    /// Определяет формат по расширению файла без учёта регистра
+    ///
+    /// # Аргументы
+    /// * `extension` - Расширение без точки (`csv`, `txt` или `bin`)
+    ///
+    /// # Возвращает
+    /// `None`, если расширение не соответствует ни одному формату
+    ///
+    /// # Пример
+    /// ```
+    /// use parser_lib::Format;
+    ///
+    /// assert_eq!(Format::from_extension("CSV"), Some(Format::Csv));
+    /// assert_eq!(Format::from_extension("json"), None);
+    /// ```
+    pub fn from_extension(extension: &str) -> Option<Format> {
+        [Format::Csv, Format::Txt, Format::Bin]
+            .into_iter()
+            .find(|format| format.extension().eq_ignore_ascii_case(extension))
+    }
+
+    /// Парсит транзакции из потока парсером этого формата
+    ///
+    /// # Аргументы
+    /// * `reader` - Читаемый поток
+    /// * `options` - Настройки парсинга и правила валидации
+    ///
+    /// # Возвращает
+    /// * `Ok(Vec<Transaction>)` - Вектор распарсенных транзакций
+    /// * `Err(ParserError)` - Ошибка парсинга, валидации или ввода-вывода
+    pub fn parse_records<R: Read>(
+        &self,
+        reader: R,
+        options: &ParseOptions,
+    ) -> Result<Vec<Transaction>, ParserError> {
+        match self {
+            Format::Csv => CsvParser::parse_records_with(reader, options),
+            Format::Txt => TextParser::parse_records_with(reader, options),
+            Format::Bin => BinaryParser::parse_records_with(reader, options),
+        }
+    }
+
+    /// Парсит транзакции, добавляя предупреждения о восстановимых проблемах в `warnings`
+    ///
+    /// Предупреждения формируют CSV парсер (лишние пустые поля, пропущенный
+    /// повторный заголовок) и парсер текстового формата (неизвестный ключ,
+    /// повторный ключ, разрешённый политикой); для бинарного формата список
+    /// не изменяется.
+    ///
+    /// # Аргументы
+    /// * `reader` - Читаемый поток
+    /// * `options` - Настройки парсинга и правила валидации
+    /// * `warnings` - Список, в который добавляются предупреждения
+    ///
+    /// # Возвращает
+    /// * `Ok(Vec<Transaction>)` - Вектор распарсенных транзакций
+    /// * `Err(ParserError)` - Ошибка парсинга, валидации или ввода-вывода
+    pub fn parse_records_with_warnings<R: Read>(
+        &self,
+        reader: R,
+        options: &ParseOptions,
+        warnings: &mut Vec<String>,
+    ) -> Result<Vec<Transaction>, ParserError> {
+        match self {
+            Format::Csv => CsvParser::parse_records_with_warnings(reader, options, warnings),
+            Format::Txt => TextParser::parse_records_with_warnings(reader, options, warnings),
+            Format::Bin => self.parse_records(reader, options),
+        }
+    }
+
+    /// Записывает транзакции в поток писателем этого формата
+    ///
+    /// # Аргументы
+    /// * `records` - Срез транзакций для записи
+    /// * `writer` - Записываемый поток
+    ///
+    /// # Возвращает
+    /// * `Ok(())` - Успешная запись
+    /// * `Err(ParserError)` - Ошибка записи
+    pub fn write_records<W: Write>(
+        &self,
+        records: &[Transaction],
+        writer: &mut W,
+    ) -> Result<(), ParserError> {
+        match self {
+            Format::Csv => CsvParser::write_records(records, writer),
+            Format::Txt => TextParser::write_records(records, writer),
+            Format::Bin => BinaryParser::write_records(records, writer),
+        }
+    }
+
+    /// Проверяет, теряет ли формат данные при записи
+    ///
+    /// # Возвращает
+    /// `true`, если хотя бы одно поле может не пережить цикл
+    /// запись-чтение в этом формате
+    pub fn is_lossy(&self) -> bool {
+        !self.lossy_fields().is_empty()
+    }
+
+    /// Возвращает поля, которые могут измениться при записи в формат
+    ///
+    /// Бинарный формат обрезает пробелы и внешние кавычки в DESCRIPTION.
+    ///
+    /// # Возвращает
+    /// Имена полей в нотации заголовка CSV
+    pub fn lossy_fields(&self) -> &'static [&'static str] {
+        match self {
+            Format::Csv | Format::Txt => &[],
+            Format::Bin => &["DESCRIPTION"],
+        }
+    }
+}
+
+/// Окончание строки в выводе CSV и текстового форматов
+///
+/// Парсеры принимают оба варианта, поэтому выбор не влияет на чтение.
+///
+/// # Пример
+/// ```
+/// use parser_lib::LineEnding;
+///
+/// assert_eq!(LineEnding::default().as_str(), "\n");
+/// assert_eq!(LineEnding::Crlf.as_str(), "\r\n");
+/// ```
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LineEnding {
+    /// `\n` (Unix)
+    #[default]
+    Lf,
+    /// `\r\n` (Windows)
+    Crlf,
+}
+
+impl LineEnding {
+    /// Возвращает последовательность символов окончания строки
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LineEnding::Lf => "\n",
+            LineEnding::Crlf => "\r\n",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lossy_formats() {
+        assert!(!Format::Csv.is_lossy());
+        assert!(!Format::Txt.is_lossy());
+        assert!(Format::Bin.is_lossy());
+        assert_eq!(Format::Bin.lossy_fields(), &["DESCRIPTION"]);
+    }
+}
@@ -34,15 +34,66 @@
 //! - Поддерживает отрицательные суммы
 //! - Имеет встроенную проверку целостности
 
+#[cfg(feature = "zip")]
+mod archive;
+mod balance;
 mod binary_format;
+mod binary_index;
+mod compare;
+mod convert;
+mod counting;
 mod csv_format;
+mod csv_map;
 mod error;
+mod files;
+mod format;
+mod messages;
+mod options;
+mod probe;
+mod registry;
+mod sample;
+mod schema;
+mod template;
+mod testing;
+mod transform;
 mod txt_format;
+mod validation;
 
-pub use binary_format::{BinaryParser, BinaryRecord};
-pub use csv_format::CsvParser;
+#[cfg(feature = "zip")]
+pub use archive::read_zip;
+pub use balance::{balance_delta, signed_amount_for};
+pub use binary_format::{
+    BinaryParser, BinaryRecord, BinaryRecords, BinaryWriteOptions, MAX_DESCRIPTION_LEN,
+};
+pub use binary_index::BinaryIndexedReader;
+pub use compare::{
+    CompareOptions, CompareReport, FieldDiff, RecordMismatch, compare, diff_transactions,
+    fingerprint,
+};
+pub use convert::{FilterCounts, convert_stream, convert_stream_filtered, convert_stream_with};
+pub use counting::{CountingWriter, write_records_counted};
+pub use csv_format::{CsvParser, CsvWriteOptions, CsvWriter};
+pub use csv_map::CsvFieldMap;
 pub use error::ParserError;
-pub use txt_format::TextParser;
+pub(crate) use error::Rejected;
+pub use files::{parse_file, write_file};
+pub use format::{Format, LineEnding};
+pub use messages::{Lang, Message};
+pub use options::{
+    BinaryParseOptions, CsvParseOptions, DuplicatePolicy, ParseOptions, TextParseOptions,
+};
+pub use probe::{FormatProbe, probe};
+pub use registry::{FormatRegistry, TransactionFormat};
+pub use sample::generate_sample;
+pub use schema::transaction_json_schema;
+pub use template::OutputTemplate;
+pub use testing::assert_roundtrip;
+pub use transform::{
+    count_by_status, filter_by_type, map_transactions, round_timestamps, select_time_window,
+    sort_by_id, trim_descriptions, zero_synthetic_ids,
+};
+pub use txt_format::{TextParser, TextWriteOptions};
+pub use validation::{TimestampRange, ValidationRules};
 
 use std::io::{Read, Write};
 
@@ -153,18 +204,208 @@ pub struct Transaction {
     /// Может содержать специальные символы, кавычки и запятые.
     /// В CSV формате экранируется двойными кавычками.
     pub description: String,
+
+    /// Трёхбуквенный код валюты (например, `USD`)
+    ///
+    /// Необязательное поле: `None` для файлов без валюты.
+    pub currency: Option<String>,
+}
+
+impl Transaction {
+    /// Возвращает возраст транзакции в миллисекундах относительно `now_ms`
+    ///
+    /// Текущее время передаётся вызывающей стороной, библиотека
+    /// не обращается к системным часам.
+    ///
+    /// # Аргументы
+    /// * `now_ms` - Текущее время в миллисекундах с эпохи UNIX
+    ///
+    /// # Возвращает
+    /// * `Some(age)` - Возраст транзакции (0, если метки совпадают)
+    /// * `None` - Временная метка транзакции находится в будущем относительно `now_ms`
+    pub fn age_ms(&self, now_ms: u64) -> Option<u64> {
+        now_ms.checked_sub(self.timestamp)
+    }
+
+    /// Проверяет, что транзакция старше заданного порога
+    ///
+    /// Транзакции с временной меткой в будущем никогда не считаются устаревшими.
+    ///
+    /// # Аргументы
+    /// * `now_ms` - Текущее время в миллисекундах с эпохи UNIX
+    /// * `threshold_ms` - Порог возраста в миллисекундах
+    pub fn is_older_than(&self, now_ms: u64, threshold_ms: u64) -> bool {
+        self.age_ms(now_ms).is_some_and(|age| age > threshold_ms)
+    }
+
+    /// Исправляет очевидные нарушения инвариантов типа транзакции
+    ///
+    /// В отличие от валидации, которая отклоняет запись, метод изменяет
+    /// данные, поэтому применяется только по явному запросу:
+    /// - для депозитов `from_user_id` становится 0
+    /// - для выводов `to_user_id` становится 0
+    /// - сумма заменяется абсолютным значением (`i64::MIN` — на `i64::MAX`)
+    ///
+    /// # Пример
+    /// ```
+    /// use parser_lib::{Transaction, TransactionStatus, TransactionType};
+    ///
+    /// let mut tx = Transaction {
+    ///     tx_id: 1,
+    ///     tx_type: TransactionType::Withdrawal,
+    ///     from_user_id: 501,
+    ///     to_user_id: 9,
+    ///     amount: -100,
+    ///     timestamp: 0,
+    ///     status: TransactionStatus::Success,
+    ///     description: String::new(),
+    ///     currency: None,
+    /// };
+    ///
+    /// tx.canonicalize();
+    /// assert_eq!((tx.to_user_id, tx.amount), (0, 100));
+    /// ```
+    pub fn canonicalize(&mut self) {
+        match self.tx_type {
+            TransactionType::Deposit => self.from_user_id = 0,
+            TransactionType::Withdrawal => self.to_user_id = 0,
+            TransactionType::Transfer => {}
+        }
+        self.amount = self.amount.saturating_abs();
+    }
+
+    /// Возвращает копию транзакции с другой суммой
+    ///
+    /// Как и остальные методы `with_*`, не проверяет бизнес-правила:
+    /// проверка выполняется при парсинге или через `into_validated`.
+    ///
+    /// # Пример
+    /// ```
+    /// use parser_lib::generate_sample;
+    ///
+    /// let tx = generate_sample(1, 1).remove(0);
+    /// let doubled = tx.clone().with_amount(tx.amount * 2);
+    /// assert_eq!(doubled.tx_id, tx.tx_id);
+    /// ```
+    pub fn with_amount(mut self, amount: i64) -> Transaction {
+        self.amount = amount;
+        self
+    }
+
+    /// Возвращает копию транзакции с другим статусом
+    pub fn with_status(mut self, status: TransactionStatus) -> Transaction {
+        self.status = status;
+        self
+    }
+
+    /// Возвращает копию транзакции с другим описанием
+    pub fn with_description(mut self, description: impl Into<String>) -> Transaction {
+        self.description = description.into();
+        self
+    }
 }
 
 // lib.rs - добавляем после определения Transaction
 
+/// Транзакции, формат которых выбирается при чтении и записи
+///
+/// Заменяет обертки `CsvTransactions`, `TextTransactions` и
+/// `BinaryTransactions`: вместо отдельного типа на каждый формат
+/// формат передаётся значением [`Format`].
+///
+/// # Пример
+/// ```
+/// use parser_lib::{Format, Transactions, generate_sample};
+///
+/// let transactions = Transactions(generate_sample(3, 1));
+/// let mut buffer = Vec::new();
+/// transactions.write_as(Format::Txt, &mut buffer).unwrap();
+///
+/// let parsed = Transactions::read(Format::Txt, buffer.as_slice()).unwrap();
+/// assert_eq!(parsed, transactions);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Transactions(pub Vec<Transaction>);
+
+impl Transactions {
+    /// Парсит транзакции из потока в заданном формате
+    ///
+    /// # Аргументы
+    /// * `format` - Формат входных данных
+    /// * `reader` - Читаемый поток (например, файл или буфер)
+    ///
+    /// # Возвращает
+    /// * `Ok(Transactions)` - Распарсенные транзакции
+    /// * `Err(ParserError)` - Ошибка парсинга или ввода-вывода
+    pub fn read<R: Read>(format: Format, reader: R) -> Result<Self, ParserError> {
+        format
+            .parse_records(reader, &ParseOptions::default())
+            .map(Transactions)
+    }
+
+    /// Записывает транзакции в поток в заданном формате
+    ///
+    /// # Аргументы
+    /// * `format` - Формат вывода
+    /// * `writer` - Записываемый поток
+    ///
+    /// # Возвращает
+    /// * `Ok(())` - Успешная запись
+    /// * `Err(ParserError)` - Ошибка записи
+    pub fn write_as<W: Write>(&self, format: Format, writer: &mut W) -> Result<(), ParserError> {
+        format.write_records(&self.0, writer)
+    }
+
+    /// Извлекает транзакции, предварительно проверив их
+    ///
+    /// Проверяются встроенные бизнес-правила формата `format` с учётом
+    /// настроек (например, `csv.signed_amounts`) и правила `options.rules`.
+    /// Позволяет разделить парсинг и проверку на два этапа.
+    ///
+    /// # Аргументы
+    /// * `format` - Формат, правила которого применяются
+    /// * `options` - Настройки парсинга и правила валидации
+    ///
+    /// # Возвращает
+    /// * `Ok(Vec<Transaction>)` - Все транзакции корректны
+    /// * `Err(ParserError::BusinessRule)` - Нарушено правило формата
+    /// * `Err(ParserError::Validation)` - Нарушено настраиваемое правило
+    ///
+    /// Сообщение ошибки начинается с индекса первой некорректной записи.
+    ///
+    /// # Пример
+    /// ```
+    /// use parser_lib::{Format, ParseOptions, Transactions, generate_sample};
+    ///
+    /// let mut records = generate_sample(2, 1);
+    /// records[1].amount = -records[1].amount.abs();
+    ///
+    /// let transactions = Transactions(records);
+    /// let options = ParseOptions::default();
+    /// assert!(transactions.clone().into_validated(Format::Csv, &options).is_err());
+    /// assert!(transactions.into_validated(Format::Bin, &options).is_ok());
+    /// ```
+    pub fn into_validated(
+        self,
+        format: Format,
+        options: &ParseOptions,
+    ) -> Result<Vec<Transaction>, ParserError> {
+        validation::validate_transactions(&self.0, format, options)?;
+        Ok(self.0)
+    }
+}
+
 /// Обертка для парсинга CSV формата
-pub struct CsvTransactions(pub Vec<Transaction>);
+#[deprecated(note = "use `Transactions::read` and `Transactions::write_as` with `Format::Csv`")]
+pub type CsvTransactions = Transactions;
 
 /// Обертка для парсинга текстового формата
-pub struct TextTransactions(pub Vec<Transaction>);
+#[deprecated(note = "use `Transactions::read` and `Transactions::write_as` with `Format::Txt`")]
+pub type TextTransactions = Transactions;
 
 /// Обертка для парсинга бинарного формата
-pub struct BinaryTransactions(pub Vec<Transaction>);
+#[deprecated(note = "use `Transactions::read` and `Transactions::write_as` with `Format::Bin`")]
+pub type BinaryTransactions = Transactions;
 
 /// Типы банковских транзакций
 ///
@@ -224,4 +465,390 @@ pub enum TransactionStatus {
     /// Операция принята системой, но ещё не обработана.
     /// Может перейти в статус Success или Failure.
     Pending,
+
+    /// Транзакция отменена или сторнирована
+    ///
+    /// Операция была принята, но затем отменена: средства
+    /// возвращены отправителю. В отличие от Failure, не означает ошибку.
+    Reversed,
+}
+
+/// Поля транзакции
+///
+/// Используется для настройки порядка полей при записи и для
+/// выбора полей в операциях над транзакциями.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Field {
+    /// Идентификатор транзакции (`TX_ID`)
+    TxId,
+    /// Тип транзакции (`TX_TYPE`)
+    TxType,
+    /// Отправитель (`FROM_USER_ID`)
+    FromUserId,
+    /// Получатель (`TO_USER_ID`)
+    ToUserId,
+    /// Сумма (`AMOUNT`)
+    Amount,
+    /// Время (`TIMESTAMP`)
+    Timestamp,
+    /// Статус (`STATUS`)
+    Status,
+    /// Описание (`DESCRIPTION`)
+    Description,
+    /// Код валюты (`CURRENCY`), необязательное поле
+    Currency,
+}
+
+impl Field {
+    /// Все поля в каноническом порядке форматов
+    pub const ALL: [Field; 9] = [
+        Field::TxId,
+        Field::TxType,
+        Field::FromUserId,
+        Field::ToUserId,
+        Field::Amount,
+        Field::Timestamp,
+        Field::Status,
+        Field::Description,
+        Field::Currency,
+    ];
+
+    /// Возвращает имя поля в нотации заголовка
+    ///
+    /// # Пример
+    /// ```
+    /// use parser_lib::Field;
+    ///
+    /// assert_eq!(Field::FromUserId.name(), "FROM_USER_ID");
+    /// ```
+    pub fn name(&self) -> &'static str {
+        match self {
+            Field::TxId => "TX_ID",
+            Field::TxType => "TX_TYPE",
+            Field::FromUserId => "FROM_USER_ID",
+            Field::ToUserId => "TO_USER_ID",
+            Field::Amount => "AMOUNT",
+            Field::Timestamp => "TIMESTAMP",
+            Field::Status => "STATUS",
+            Field::Description => "DESCRIPTION",
+            Field::Currency => "CURRENCY",
+        }
+    }
+}
+
+impl std::str::FromStr for Field {
+    type Err = ParserError;
+
+    /// Разбирает имя поля без учёта регистра: `TX_ID`, `from_user_id` и т.п.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.trim();
+        Field::ALL
+            .into_iter()
+            .find(|field| field.name().eq_ignore_ascii_case(name))
+            .ok_or_else(|| ParserError::Parse(format!("Unknown field '{}'", name)))
+    }
+}
+
+/// Парсит числовое поле текстового формата с единообразным сообщением об ошибке
+///
+/// # Аргументы
+/// * `value` - Строковое значение поля
+/// * `field` - Имя поля в нотации заголовка (например, `TX_ID`)
+/// * `line` - Номер строки для сообщения об ошибке
+pub(crate) fn parse_field<T>(value: &str, field: &str, line: usize) -> Result<T, ParserError>
+where
+    T: std::str::FromStr,
+    T::Err: std::fmt::Display,
+{
+    value.parse::<T>().map_err(|e| {
+        ParserError::Parse(format!(
+            "Line {}: invalid {} '{}': {}",
+            line, field, value, e
+        ))
+    })
+}
+
+/// Проверяет код валюты: ровно три заглавные латинские буквы
+///
+/// # Возвращает
+/// * `Ok(())` - Код корректен
+/// * `Err(String)` - Описание ошибки без номера строки или записи
+pub(crate) fn check_currency_code(code: &str) -> Result<(), String> {
+    if code.len() == 3 && code.bytes().all(|b| b.is_ascii_uppercase()) {
+        Ok(())
+    } else {
+        Err(format!(
+            "invalid CURRENCY '{}': expected a 3-letter code",
+            code
+        ))
+    }
+}
+
+/// Правила значений в кавычках для `normalize_line_endings`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Quoting {
+    /// Кавычка внутри значения удваивается, значение может занимать
+    /// несколько строк (CSV)
+    Doubled,
+    /// Кавычка внутри значения экранируется `\`, значение заканчивается
+    /// вместе со строкой (текстовый формат)
+    Backslash,
+}
+
+/// Приводит окончания строк вне значений в кавычках к `\n`
+///
+/// Заменяет `\r\n` и одиночные `\r` на `\n`, чтобы файлы со смешанными
+/// окончаниями строк не оставляли `\r` в значениях полей. `\r` внутри
+/// значения в двойных кавычках относится к данным и сохраняется. При
+/// `Quoting::Backslash` кавычка после `\` экранирована, а значение не
+/// продолжается на следующей строке; при `Quoting::Doubled` удвоенная
+/// кавычка переключает состояние дважды.
+pub(crate) fn normalize_line_endings(content: &str, quoting: Quoting) -> String {
+    let text = quoting == Quoting::Backslash;
+    let mut normalized = String::with_capacity(content.len());
+    let mut in_quotes = false;
+    let mut escaped = false;
+    let mut chars = content.chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\r' if !in_quotes => {
+                chars.next_if_eq(&'\n');
+                normalized.push('\n');
+                escaped = false;
+                continue;
+            }
+            '\n' if text => in_quotes = false,
+            '"' if !in_quotes => in_quotes = true,
+            '"' if !escaped || matches!(chars.peek(), None | Some('\r' | '\n')) => {
+                in_quotes = false
+            }
+            _ => {}
+        }
+        escaped = text && ch == '\\';
+        normalized.push(ch);
+    }
+
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_transaction(timestamp: u64) -> Transaction {
+        Transaction {
+            tx_id: 1001,
+            tx_type: TransactionType::Deposit,
+            from_user_id: 0,
+            to_user_id: 501,
+            amount: 50000,
+            timestamp,
+            status: TransactionStatus::Success,
+            description: "Test".to_string(),
+            currency: None,
+        }
+    }
+
+    #[test]
+    fn test_field_from_str() {
+        assert_eq!("timestamp".parse::<Field>().unwrap(), Field::Timestamp);
+        assert_eq!("FROM_USER_ID".parse::<Field>().unwrap(), Field::FromUserId);
+        assert_eq!(" tx_id ".parse::<Field>().unwrap(), Field::TxId);
+        assert!(matches!(
+            "fee".parse::<Field>(),
+            Err(ParserError::Parse(msg)) if msg.contains("fee")
+        ));
+    }
+
+    #[test]
+    fn test_age_of_past_record() {
+        let tx = create_test_transaction(1672531200000);
+
+        assert_eq!(tx.age_ms(1672531260000), Some(60000));
+        assert!(tx.is_older_than(1672531260000, 59999));
+        assert!(!tx.is_older_than(1672531260000, 60000));
+    }
+
+    #[test]
+    fn test_age_of_future_record() {
+        let tx = create_test_transaction(1672531200000);
+
+        assert_eq!(tx.age_ms(1672531100000), None);
+        assert!(!tx.is_older_than(1672531100000, 0));
+    }
+
+    #[test]
+    fn test_age_of_equal_timestamp() {
+        let tx = create_test_transaction(1672531200000);
+
+        assert_eq!(tx.age_ms(1672531200000), Some(0));
+        assert!(!tx.is_older_than(1672531200000, 0));
+    }
+
+    #[test]
+    fn test_canonicalize_deposit() {
+        let mut tx = create_test_transaction(1672531200000);
+        tx.from_user_id = 77;
+        tx.amount = -500;
+
+        tx.canonicalize();
+
+        assert_eq!(tx.from_user_id, 0);
+        assert_eq!(tx.to_user_id, 501);
+        assert_eq!(tx.amount, 500);
+    }
+
+    #[test]
+    fn test_canonicalize_keeps_valid_transfer() {
+        let mut tx = Transaction {
+            tx_type: TransactionType::Transfer,
+            from_user_id: 501,
+            to_user_id: 502,
+            amount: i64::MIN,
+            ..create_test_transaction(1672531200000)
+        };
+
+        tx.canonicalize();
+
+        assert_eq!((tx.from_user_id, tx.to_user_id), (501, 502));
+        assert_eq!(tx.amount, i64::MAX);
+    }
+
+    #[test]
+    fn test_with_amount_preserves_other_fields() {
+        let tx = create_test_transaction(1672531200000);
+
+        let updated = tx.clone().with_amount(1);
+
+        assert_eq!(updated.amount, 1);
+        assert_eq!(
+            Transaction {
+                amount: tx.amount,
+                ..updated
+            },
+            tx
+        );
+    }
+
+    #[test]
+    fn test_with_status_and_description() {
+        let tx = create_test_transaction(1672531200000)
+            .with_status(TransactionStatus::Pending)
+            .with_description("Updated");
+
+        assert_eq!(tx.status, TransactionStatus::Pending);
+        assert_eq!(tx.description, "Updated");
+        assert_eq!(tx.amount, 50000);
+        assert_eq!(tx.tx_id, 1001);
+    }
+
+    #[test]
+    fn test_with_amount_does_not_validate() {
+        let tx = create_test_transaction(1672531200000).with_amount(-5);
+
+        assert_eq!(tx.amount, -5);
+        assert!(
+            Transactions(vec![tx])
+                .into_validated(Format::Txt, &ParseOptions::default())
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_transactions_roundtrip_each_format() {
+        let mut records = vec![
+            create_test_transaction(1672531200000),
+            create_test_transaction(1672531300000),
+        ];
+        records[1].tx_id = 1002;
+        records[1].currency = Some("EUR".to_string());
+        let transactions = Transactions(records);
+
+        for format in [Format::Csv, Format::Txt, Format::Bin] {
+            let mut buffer = Vec::new();
+            transactions.write_as(format, &mut buffer).unwrap();
+
+            let parsed = Transactions::read(format, buffer.as_slice()).unwrap();
+            assert_eq!(parsed, transactions, "{:?}", format);
+        }
+
+        assert!(Transactions::read(Format::Bin, &b"TX_ID"[..]).is_err());
+    }
+
+    #[test]
+    fn test_normalize_line_endings() {
+        for quoting in [Quoting::Doubled, Quoting::Backslash] {
+            assert_eq!(
+                normalize_line_endings("a\r\nb\rc\nd", quoting),
+                "a\nb\nc\nd"
+            );
+        }
+        assert_eq!(
+            normalize_line_endings("1,\"a\rb\"\r2,\"\"\"\"\r", Quoting::Doubled),
+            "1,\"a\rb\"\n2,\"\"\"\"\n"
+        );
+        assert_eq!(
+            normalize_line_endings("D: \"a\\\"b\rc\"\rE: \"d\\\"\rF: 1", Quoting::Backslash),
+            "D: \"a\\\"b\rc\"\nE: \"d\\\"\nF: 1"
+        );
+    }
+
+    #[test]
+    fn test_into_validated_reports_failing_index() {
+        let mut invalid = create_test_transaction(1672531200000);
+        invalid.from_user_id = 42;
+
+        let wrapper = Transactions(vec![create_test_transaction(1672531200000), invalid]);
+        let result = wrapper.into_validated(Format::Csv, &ParseOptions::default());
+        assert!(matches!(
+            result,
+            Err(ParserError::BusinessRule { rule, line: None }) if rule.starts_with("Record at index 1:")
+        ));
+
+        let wrapper = Transactions(vec![create_test_transaction(1672531200000)]);
+        assert_eq!(
+            wrapper
+                .into_validated(Format::Csv, &ParseOptions::default())
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_into_validated_applies_rules_of_format() {
+        let mut refund = create_test_transaction(1672531200000);
+        refund.tx_type = TransactionType::Transfer;
+        refund.from_user_id = 501;
+        refund.to_user_id = 502;
+        refund.amount = -15000;
+        let transactions = Transactions(vec![refund]);
+
+        let mut options = ParseOptions::default();
+        assert!(
+            transactions
+                .clone()
+                .into_validated(Format::Bin, &options)
+                .is_ok()
+        );
+        assert!(matches!(
+            transactions.clone().into_validated(Format::Txt, &options),
+            Err(ParserError::BusinessRule { .. })
+        ));
+
+        options.csv.signed_amounts = true;
+        assert!(
+            transactions
+                .clone()
+                .into_validated(Format::Csv, &options)
+                .is_ok()
+        );
+
+        options.rules.max_description_len = Some(1);
+        assert!(matches!(
+            transactions.into_validated(Format::Bin, &options),
+            Err(ParserError::Validation(msg)) if msg.starts_with("Record at index 0:")
+        ));
+    }
 }
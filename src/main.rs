@@ -1,10 +1,44 @@
 use clap::Parser;
+use clap::builder::ArgPredicate;
 use parser_lib::{
-    BinaryTransactions, CsvTransactions, ParseFromRead, TextTransactions, Transaction, WriteTo,
+    BinaryParser, CompareOptions, CountingWriter, CsvFieldMap, CsvParser, CsvWriteOptions, Format,
+    Lang, LineEnding, MAX_DESCRIPTION_LEN, Message, OutputTemplate, ParseOptions, TextParser,
+    TextWriteOptions, TimestampRange, Transaction, TransactionStatus, TransactionType,
+    count_by_status, diff_transactions, filter_by_type, round_timestamps, select_time_window,
+    trim_descriptions, zero_synthetic_ids,
 };
-use std::fs::File;
-use std::io::{self, BufReader, BufWriter};
+use sha2::{Digest, Sha256};
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufReader, BufWriter, Read, Write};
 use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// Диагностическое сообщение режима `--verbose`
+///
+/// Сообщение выводится на языке `--lang`. С функцией `logging` оно
+/// передаётся в `log::info!`, иначе печатается в stderr.
+macro_rules! verbose {
+    ($lang:expr, $message:expr) => {{
+        let text = $message.render($lang);
+        #[cfg(feature = "logging")]
+        log::info!("{}", text);
+        #[cfg(not(feature = "logging"))]
+        eprintln!("{}", text);
+    }};
+}
+
+/// Предупреждение, выводимое независимо от `--verbose`
+macro_rules! warning {
+    ($lang:expr, $($arg:tt)*) => {{
+        #[cfg(feature = "logging")]
+        {
+            let _ = $lang;
+            log::warn!($($arg)*);
+        }
+        #[cfg(not(feature = "logging"))]
+        eprintln!("{}: {}", Message::Warning.render($lang), format_args!($($arg)*));
+    }};
+}
 
 #[derive(Parser, Debug)]
 #[command(name = "ypbank_converter")]
@@ -12,13 +46,16 @@ use std::path::{Path, PathBuf};
 #[command(about = "Конвертирует файлы между форматами YPBank (CSV, Text, Binary)", long_about = None)]
 struct Args {
     #[arg(short, long, value_name = "FILE")]
-    input: PathBuf,
+    input: Option<PathBuf>,
 
     #[arg(
         long = "input-format",
         value_name = "FORMAT",
         value_enum,
-        ignore_case = true
+        ignore_case = true,
+        required = false,
+        required_unless_present = "emit_header_only",
+        default_value_if("emit_header_only", ArgPredicate::IsPresent, "csv")
     )]
     input_format: Format,
 
@@ -26,36 +63,342 @@ struct Args {
         long = "output-format",
         value_name = "FORMAT",
         value_enum,
-        ignore_case = true
+        ignore_case = true,
+        required = false,
+        required_unless_present_any = ["emit_header_only", "template"],
+        default_value_ifs([
+            ("emit_header_only", ArgPredicate::IsPresent, "csv"),
+            ("template", ArgPredicate::IsPresent, "txt")
+        ])
     )]
     output_format: Format,
 
     #[arg(short, long, value_name = "FILE")]
     output: Option<PathBuf>,
 
+    #[arg(
+        long = "input-dir",
+        value_name = "DIR",
+        requires = "output_dir",
+        conflicts_with_all = [
+            "input",
+            "output",
+            "append",
+            "expected",
+            "split_by_type",
+            "dry_run",
+            "audit_log"
+        ]
+    )]
+    input_dir: Option<PathBuf>,
+
+    #[arg(
+        long = "input-list",
+        value_name = "FILE",
+        conflicts_with_all = [
+            "input",
+            "input_dir",
+            "repair_binary",
+            "emit_header_only"
+        ]
+    )]
+    input_list: Option<PathBuf>,
+
+    #[arg(long = "output-dir", value_name = "DIR", requires = "input_dir")]
+    output_dir: Option<PathBuf>,
+
+    #[arg(long = "fail-fast", default_value_t = false, requires = "input_dir")]
+    fail_fast: bool,
+
     #[arg(short, long, default_value_t = false)]
     verbose: bool,
 
     #[arg(long, default_value_t = false)]
     skip_validation: bool,
+
+    #[arg(long, default_value_t = false, requires = "output")]
+    append: bool,
+
+    #[arg(long = "no-header", default_value_t = false)]
+    no_header: bool,
+
+    #[arg(long = "text-b64-desc", default_value_t = false)]
+    text_b64_desc: bool,
+
+    #[arg(long, default_value_t = false)]
+    footer: bool,
+
+    #[arg(
+        long,
+        value_name = "TEMPLATE",
+        value_parser = parse_template,
+        conflicts_with_all = [
+            "output_format",
+            "input_dir",
+            "no_header",
+            "text_b64_desc",
+            "footer",
+            "expected",
+            "split_by_type",
+            "repair_binary",
+            "emit_header_only",
+            "count_by_status"
+        ]
+    )]
+    template: Option<OutputTemplate>,
+
+    #[arg(long, value_enum, default_value_t = LineEnding::Lf, ignore_case = true)]
+    eol: LineEnding,
+
+    #[arg(long = "zero-synthetic-ids", default_value_t = false)]
+    zero_synthetic_ids: bool,
+
+    #[arg(long, default_value_t = false)]
+    canonicalize: bool,
+
+    #[arg(long = "trim-descriptions", default_value_t = false)]
+    trim_descriptions: bool,
+
+    #[arg(long = "round-timestamps", value_name = "MS")]
+    round_timestamps: Option<u64>,
+
+    #[arg(long = "validate-timestamps", default_value_t = false)]
+    validate_timestamps: bool,
+
+    #[arg(long, value_name = "FROM_MS,TO_MS", value_parser = parse_between)]
+    between: Option<(u64, u64)>,
+
+    #[arg(long = "max-records", value_name = "N")]
+    max_records: Option<usize>,
+
+    #[arg(long = "max-line-bytes", value_name = "N")]
+    max_line_bytes: Option<usize>,
+
+    #[arg(long = "require-nonempty", default_value_t = false)]
+    require_nonempty: bool,
+
+    #[arg(long = "tolerate-repeated-header", default_value_t = false)]
+    tolerate_repeated_header: bool,
+
+    #[arg(long = "signed-amounts", default_value_t = false)]
+    signed_amounts: bool,
+
+    #[arg(long = "csv-map", value_name = "FILE")]
+    csv_map: Option<PathBuf>,
+
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["output", "append"])]
+    expected: Option<PathBuf>,
+
+    #[arg(
+        long = "split-by-type",
+        value_name = "DIR",
+        conflicts_with_all = ["output", "append", "expected"]
+    )]
+    split_by_type: Option<PathBuf>,
+
+    #[arg(
+        long = "expected-format",
+        value_name = "FORMAT",
+        value_enum,
+        ignore_case = true,
+        requires = "expected"
+    )]
+    expected_format: Option<Format>,
+
+    #[arg(
+        long = "repair-binary",
+        default_value_t = false,
+        conflicts_with_all = [
+            "input_dir",
+            "append",
+            "expected",
+            "split_by_type",
+            "dry_run",
+            "count_by_status"
+        ]
+    )]
+    repair_binary: bool,
+
+    #[arg(long = "dry-run", default_value_t = false, conflicts_with = "expected")]
+    dry_run: bool,
+
+    #[arg(
+        long = "emit-header-only",
+        default_value_t = false,
+        conflicts_with_all = [
+            "input_dir",
+            "append",
+            "no_header",
+            "expected",
+            "split_by_type",
+            "repair_binary",
+            "dry_run",
+            "count_by_status"
+        ]
+    )]
+    emit_header_only: bool,
+
+    #[arg(long, default_value_t = false)]
+    explain: bool,
+
+    #[arg(
+        long = "count-by-status",
+        default_value_t = false,
+        conflicts_with_all = [
+            "output",
+            "append",
+            "expected",
+            "split_by_type",
+            "dry_run",
+            "audit_log",
+            "input_dir"
+        ]
+    )]
+    count_by_status: bool,
+
+    #[arg(long = "audit-log", value_name = "FILE", conflicts_with_all = ["expected", "dry_run"])]
+    audit_log: Option<PathBuf>,
+
+    #[arg(
+        long = "progress-file",
+        value_name = "FILE",
+        conflicts_with_all = ["expected", "dry_run", "input_dir", "count_by_status"]
+    )]
+    progress_file: Option<PathBuf>,
+
+    #[arg(
+        long = "verify-hash",
+        value_name = "SHA256",
+        value_parser = parse_sha256,
+        conflicts_with_all = [
+            "expected",
+            "dry_run",
+            "split_by_type",
+            "input_dir",
+            "count_by_status"
+        ]
+    )]
+    verify_hash: Option<String>,
+
+    #[arg(long, value_enum, default_value_t = Lang::Ru, ignore_case = true)]
+    lang: Lang,
 }
 
-#[derive(clap::ValueEnum, Clone, Debug, PartialEq)]
-enum Format {
-    Csv,
-    Txt,
-    Bin,
+/// Проверяет, что значение `--verify-hash` — SHA-256 в шестнадцатеричном виде
+fn parse_sha256(value: &str) -> Result<String, String> {
+    if value.len() == 64 && value.chars().all(|c| c.is_ascii_hexdigit()) {
+        Ok(value.to_ascii_lowercase())
+    } else {
+        Err("expected 64 hexadecimal characters".to_string())
+    }
+}
+
+/// Разбирает шаблон строки записи `--template`
+fn parse_template(value: &str) -> Result<OutputTemplate, String> {
+    OutputTemplate::parse(value).map_err(|e| e.to_string())
+}
+
+/// Разбирает значение `--between` вида `FROM_MS,TO_MS`
+fn parse_between(value: &str) -> Result<(u64, u64), String> {
+    let (from, to) = value
+        .split_once(',')
+        .ok_or_else(|| "expected FROM_MS,TO_MS".to_string())?;
+    let from: u64 = from
+        .trim()
+        .parse()
+        .map_err(|e| format!("invalid FROM_MS: {}", e))?;
+    let to: u64 = to
+        .trim()
+        .parse()
+        .map_err(|e| format!("invalid TO_MS: {}", e))?;
+    if from > to {
+        return Err(format!("FROM_MS {} is greater than TO_MS {}", from, to));
+    }
+    Ok((from, to))
+}
+
+/// Настройки записи выходных файлов, заданные флагами командной строки
+#[derive(Debug, Clone, Copy)]
+struct OutputStyle<'a> {
+    /// Писать заголовок CSV (отключается `--no-header`)
+    write_header: bool,
+    /// Кодировать описания текстового формата в base64 (`--text-b64-desc`)
+    text_b64_desc: bool,
+    /// Завершать текстовый вывод числом записей (`--footer`)
+    footer: bool,
+    /// Окончание строк CSV, текстового формата и шаблона (`--eol`)
+    line_ending: LineEnding,
+    /// Шаблон строки записи, заменяющий выходной формат (`--template`)
+    template: Option<&'a OutputTemplate>,
+}
+
+impl Args {
+    /// Возвращает путь к входному файлу или `None`, если данные читаются из stdin
+    fn input_file(&self) -> Option<&Path> {
+        self.input.as_deref().filter(|path| *path != Path::new("-"))
+    }
+
+    /// Создаёт журнал прогресса, если задан `--progress-file`
+    fn progress_log(&self) -> Result<Option<ProgressLog>, Box<dyn std::error::Error>> {
+        let Some(path) = &self.progress_file else {
+            return Ok(None);
+        };
+        let file = File::create(path)
+            .map_err(|e| Message::CannotCreateFile(path, e.to_string()).render(self.lang))?;
+        let now = Instant::now();
+        Ok(Some(ProgressLog {
+            file,
+            started: now,
+            last: now,
+        }))
+    }
+
+    /// Собирает настройки записи из флагов
+    fn output_style(&self) -> OutputStyle<'_> {
+        OutputStyle {
+            write_header: !self.no_header,
+            text_b64_desc: self.text_b64_desc,
+            footer: self.footer,
+            line_ending: self.eol,
+            template: self.template.as_ref(),
+        }
+    }
 }
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let args = Args::parse();
 
-    if !args.input.exists() {
-        eprintln!("Ошибка: входной файл '{}' не найден", args.input.display());
+    #[cfg(feature = "logging")]
+    init_logging(args.verbose);
+
+    if args.emit_header_only {
+        if args.output_format != Format::Csv {
+            return Err(Message::HeaderOnlyNeedsCsv.render(args.lang).into());
+        }
+        // Пустой набор записей: CSV писатель выводит только заголовок
+        let mut digest = OutputDigest::new(args.progress_log()?);
+        write_transactions(
+            &[],
+            &Format::Csv,
+            args.output.as_ref(),
+            false,
+            args.output_style(),
+            args.verbose,
+            args.lang,
+            &mut digest,
+        )?;
+        finish_output(&args, Some(0), digest)?;
+        return Ok(());
+    }
+
+    if let Some(input) = args.input_file()
+        && !input.exists()
+    {
+        eprintln!("{}", Message::InputNotFound(input).render(args.lang));
 
         let examples_dir = Path::new("examples");
         if examples_dir.exists() {
-            eprintln!("Доступные примеры файлов в папке 'examples/':");
+            eprintln!("{}", Message::AvailableExamples.render(args.lang));
             for entry in std::fs::read_dir(examples_dir)?.flatten() {
                 if let Some(ext) = entry.path().extension() {
                     let ext_str = ext.to_string_lossy();
@@ -68,7 +411,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                     eprintln!("  - {} ({})", entry.path().display(), format);
                 }
             }
-            eprintln!("\nПример использования:");
+            eprintln!("{}", Message::UsageExample.render(args.lang));
             eprintln!(
                 "  ypbank_converter --input examples/records_example.csv --input-format csv --output-format txt"
             );
@@ -76,165 +419,1245 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         std::process::exit(1);
     }
 
-    if args.verbose {
-        eprintln!("=== YPBank Converter ===");
-        eprintln!("Входной файл: {}", args.input.display());
-        eprintln!("Входной формат: {:?}", args.input_format);
-        eprintln!("Выходной формат: {:?}", args.output_format);
-        if let Some(output) = &args.output {
-            eprintln!("Выходной файл: {}", output.display());
+    if verbose_enabled(args.verbose) {
+        let lang = args.lang;
+        verbose!(lang, Message::Banner("YPBank Converter"));
+        let input = match (&args.input_list, &args.input_dir, args.input_file()) {
+            (Some(list), _, _) => Message::VerboseInputList(list),
+            (None, Some(dir), _) => Message::VerboseInputDir(dir),
+            (None, None, Some(input)) => Message::VerboseInputFile(input.display().to_string()),
+            (None, None, None) => Message::VerboseInputFile(Message::SourceStdin.render(lang)),
+        };
+        verbose!(lang, input);
+        verbose!(lang, Message::VerboseInputFormat(args.input_format));
+        verbose!(lang, Message::VerboseOutputFormat(args.output_format));
+        if let Some(dir) = &args.output_dir {
+            verbose!(lang, Message::VerboseOutputDir(dir));
+        } else if let Some(output) = &args.output {
+            verbose!(
+                lang,
+                Message::VerboseOutputFile(output.display().to_string())
+            );
         } else {
-            eprintln!("Выходной файл: <stdout>");
+            verbose!(
+                lang,
+                Message::VerboseOutputFile(Message::TargetStdout.render(lang))
+            );
         }
         if args.skip_validation {
-            eprintln!("Режим: пропуск проверки бизнес-правил");
+            verbose!(lang, Message::VerboseSkipValidation);
+        }
+        if args.append {
+            verbose!(lang, Message::VerboseAppendMode);
+        }
+        if let Some(max_records) = args.max_records {
+            verbose!(lang, Message::VerboseMaxRecords(max_records));
+        }
+        if let Some(max_line_bytes) = args.max_line_bytes {
+            verbose!(lang, Message::VerboseMaxLineBytes(max_line_bytes));
         }
     }
 
-    let transactions = read_transactions(&args.input, &args.input_format, args.skip_validation)?;
+    let mut options = ParseOptions {
+        max_records: args.max_records,
+        max_line_bytes: args.max_line_bytes,
+        allow_empty: !args.require_nonempty,
+        ..Default::default()
+    };
+    options.csv.tolerate_repeated_header = args.tolerate_repeated_header;
+    options.csv.signed_amounts = args.signed_amounts;
+    if let Some(path) = &args.csv_map {
+        let json = std::fs::read_to_string(path)
+            .map_err(|e| Message::CannotOpenFile(path, e.to_string()).render(args.lang))?;
+        options.csv.field_map = Some(CsvFieldMap::from_json(&json)?);
+    }
+    if args.output_format == Format::Bin {
+        options.rules.max_description_len = Some(MAX_DESCRIPTION_LEN);
+    }
+    if args.validate_timestamps {
+        options.rules.timestamp_range = Some(TimestampRange::PLAUSIBLE);
+    }
 
-    if args.verbose {
-        eprintln!("Прочитано {} транзакций", transactions.len());
-        if !transactions.is_empty() {
-            eprintln!(
-                "Первая транзакция: ID={}, Тип={:?}, Сумма={}, Статус={:?}",
-                transactions[0].tx_id,
-                transactions[0].tx_type,
-                transactions[0].amount,
-                transactions[0].status
-            );
-            if transactions.len() > 1 {
-                eprintln!(
-                    "Последняя транзакция: ID={}, Тип={:?}, Сумма={}",
-                    transactions.last().unwrap().tx_id,
-                    transactions.last().unwrap().tx_type,
-                    transactions.last().unwrap().amount
-                );
-            }
+    let passthrough = args.input_format == args.output_format
+        && !args.zero_synthetic_ids
+        && args.round_timestamps.unwrap_or(0) == 0
+        && args.between.is_none()
+        && args.csv_map.is_none()
+        && !args.tolerate_repeated_header
+        // Копирование байтов не разбирает вход при --skip-validation,
+        // поэтому ограничения входа проверяются обычным разбором
+        && args.max_records.is_none()
+        && args.max_line_bytes.is_none()
+        && !args.trim_descriptions
+        && !args.canonicalize
+        && !args.append
+        && !args.no_header
+        && !args.text_b64_desc
+        && !args.footer
+        && args.template.is_none()
+        && args.eol == LineEnding::Lf
+        && args.expected.is_none()
+        && args.split_by_type.is_none()
+        && args.input_dir.is_none()
+        && args.input_list.is_none()
+        && !args.dry_run
+        && !args.count_by_status;
+
+    if args.explain {
+        print_plan(&args, passthrough);
+        return Ok(());
+    }
+
+    let mut digest = OutputDigest::new(args.progress_log()?);
+
+    if args.repair_binary {
+        let records = repair_binary(&args, &mut digest)?;
+        finish_output(&args, Some(records), digest)?;
+        return Ok(());
+    }
+
+    if passthrough {
+        let records = copy_unchanged(
+            args.input_file(),
+            &args.input_format,
+            args.output.as_ref(),
+            &options,
+            args.output_style(),
+            args.skip_validation,
+            args.verbose,
+            args.lang,
+            &mut digest,
+        )?;
+        finish_output(&args, records, digest)?;
+        return Ok(());
+    }
+
+    if args.output_format.is_lossy() {
+        let message = Message::LossyOutput {
+            format: args.output_format,
+            fields: args.output_format.lossy_fields().join(", "),
+        };
+        warning!(args.lang, "{}", message.render(args.lang));
+    }
+
+    if let (Some(input_dir), Some(output_dir)) = (&args.input_dir, &args.output_dir) {
+        let failed = convert_directory(&args, input_dir, output_dir, &options)?;
+        if failed > 0 {
+            std::process::exit(1);
         }
+        return Ok(());
+    }
+
+    let mut transactions = match &args.input_list {
+        Some(list) => read_listed_transactions(&args, list, &options)?,
+        None => read_transactions(
+            args.input_file(),
+            &args.input_format,
+            &options,
+            args.skip_validation,
+            args.verbose,
+            args.lang,
+        )?,
+    };
+
+    if verbose_enabled(args.verbose) && !transactions.is_empty() {
+        verbose!(
+            args.lang,
+            Message::VerboseFirstTransaction(&transactions[0])
+        );
+        if let [_, .., last] = transactions.as_slice() {
+            verbose!(args.lang, Message::VerboseLastTransaction(last));
+        }
+    }
+
+    let records_in = transactions.len();
+    digest.report("read", Some(records_in))?;
+
+    apply_transforms(&args, &mut transactions);
+
+    if args.count_by_status {
+        for (status, count) in count_by_status(&transactions) {
+            let name = match status {
+                TransactionStatus::Success => "SUCCESS",
+                TransactionStatus::Failure => "FAILURE",
+                TransactionStatus::Pending => "PENDING",
+                TransactionStatus::Reversed => "REVERSED",
+            };
+            println!("{}: {}", name, count);
+        }
+        return Ok(());
+    }
+
+    if args.dry_run {
+        print_dry_run_summary(&args, records_in, &transactions);
+        return Ok(());
+    }
+
+    if let Some(dir) = &args.split_by_type {
+        write_split_by_type(
+            &transactions,
+            &args.output_format,
+            dir,
+            args.output_style(),
+            args.verbose,
+            args.lang,
+            &mut digest,
+        )?;
+        finish_output(&args, Some(transactions.len()), digest)?;
+        return Ok(());
+    }
+
+    if let Some(expected) = &args.expected {
+        let expected_format = args.expected_format.unwrap_or(args.output_format);
+        let matches = compare_with_expected(
+            &transactions,
+            &args.output_format,
+            expected,
+            &expected_format,
+            args.verbose,
+            args.lang,
+        )?;
+
+        if !matches {
+            std::process::exit(2);
+        }
+
+        println!("{}", Message::ExpectedMatches(expected).render(args.lang));
+        return Ok(());
     }
 
     write_transactions(
         &transactions,
         &args.output_format,
         args.output.as_ref(),
+        args.append,
+        args.output_style(),
         args.verbose,
+        args.lang,
+        &mut digest,
     )?;
 
-    if args.verbose {
-        eprintln!("Конвертация завершена успешно!");
+    finish_output(&args, Some(transactions.len()), digest)?;
+
+    if verbose_enabled(args.verbose) {
+        verbose!(args.lang, Message::VerboseDone);
     }
 
     Ok(())
 }
 
+/// Настраивает `env_logger`: `--verbose` включает уровень info,
+/// переменная `RUST_LOG` имеет приоритет
+#[cfg(feature = "logging")]
+fn init_logging(verbose: bool) {
+    let level = if verbose { "info" } else { "warn" };
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or(level)).init();
+}
+
+/// Проверяет, нужно ли формировать диагностические сообщения
+fn verbose_enabled(verbose: bool) -> bool {
+    #[cfg(feature = "logging")]
+    {
+        let _ = verbose;
+        log::log_enabled!(log::Level::Info)
+    }
+    #[cfg(not(feature = "logging"))]
+    verbose
+}
+
 fn read_transactions(
-    input_path: &Path,
+    input_path: Option<&Path>,
     format: &Format,
+    options: &ParseOptions,
     skip_validation: bool,
+    verbose: bool,
+    lang: Lang,
 ) -> Result<Vec<Transaction>, Box<dyn std::error::Error>> {
     if skip_validation {
-        eprintln!("Предупреждение: проверка бизнес-правил отключена");
+        warning!(lang, "{}", Message::ValidationDisabled.render(lang));
+    }
+
+    let reader: Box<dyn io::Read> = match input_path {
+        Some(path) => Box::new(BufReader::new(File::open(path)?)),
+        None => Box::new(io::stdin().lock()),
+    };
+
+    let transactions = parse_transactions(reader, format, options, lang)?;
+
+    if verbose_enabled(verbose) {
+        let source = input_path.map_or(Message::SourceStdin.render(lang), |path| {
+            path.display().to_string()
+        });
+        verbose!(lang, Message::VerboseReadFrom(source, transactions.len()));
+    }
+
+    Ok(transactions)
+}
+
+/// Разбирает список входных файлов `--input-list`
+///
+/// Каждая строка содержит путь и, через пробел, необязательный формат
+/// (`csv`, `txt` или `bin`); без него используется `default_format`.
+/// Пустые строки и строки, начинающиеся с `#`, пропускаются. Относительные
+/// пути отсчитываются от каталога списка.
+fn parse_input_list(list: &Path, content: &str, default_format: Format) -> Vec<(PathBuf, Format)> {
+    let base = list.parent().unwrap_or(Path::new(""));
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            let explicit = line
+                .rsplit_once(char::is_whitespace)
+                .and_then(|(path, name)| {
+                    Format::from_extension(name).map(|format| (path.trim_end(), format))
+                });
+            let (path, format) = explicit.unwrap_or((line, default_format));
+            (base.join(path), format)
+        })
+        .collect()
+}
+
+/// Читает и объединяет транзакции всех файлов из списка `--input-list`
+fn read_listed_transactions(
+    args: &Args,
+    list: &Path,
+    options: &ParseOptions,
+) -> Result<Vec<Transaction>, Box<dyn std::error::Error>> {
+    let content = std::fs::read_to_string(list)
+        .map_err(|e| Message::CannotOpenFile(list, e.to_string()).render(args.lang))?;
+
+    let mut transactions = Vec::new();
+    for (path, format) in parse_input_list(list, &content, args.input_format) {
+        if !path.exists() {
+            return Err(Message::InputNotFound(&path).render(args.lang).into());
+        }
+        transactions.extend(read_transactions(
+            Some(&path),
+            &format,
+            options,
+            args.skip_validation,
+            args.verbose,
+            args.lang,
+        )?);
+    }
+    Ok(transactions)
+}
+
+fn parse_transactions<R: io::Read>(
+    reader: R,
+    format: &Format,
+    options: &ParseOptions,
+    lang: Lang,
+) -> Result<Vec<Transaction>, Box<dyn std::error::Error>> {
+    let mut warnings = Vec::new();
+    let transactions = format.parse_records_with_warnings(reader, options, &mut warnings)?;
+    for warning in warnings {
+        warning!(lang, "{}", warning);
+    }
+    Ok(transactions)
+}
+
+/// Копирует входные данные без перекодирования, если форматы совпадают
+///
+/// Сохраняет исходные байты без изменений. Данные предварительно
+/// разбираются для проверки, если проверка не отключена. Если при разборе
+/// были предупреждения (например, лишние пустые поля CSV), исходные байты
+/// содержат исправленные при чтении места, поэтому записи перекодируются.
+/// Возвращает количество проверенных записей (`None`, если проверка отключена).
+#[allow(clippy::too_many_arguments)]
+fn copy_unchanged(
+    input_path: Option<&Path>,
+    format: &Format,
+    output_path: Option<&PathBuf>,
+    options: &ParseOptions,
+    style: OutputStyle<'_>,
+    skip_validation: bool,
+    verbose: bool,
+    lang: Lang,
+    digest: &mut OutputDigest,
+) -> Result<Option<usize>, Box<dyn std::error::Error>> {
+    if output_path.is_none() && matches!(format, Format::Bin) {
+        return Err(Message::BinaryNeedsOutput.render(lang).into());
+    }
+
+    let mut data = Vec::new();
+    match input_path {
+        Some(path) => File::open(path)?.read_to_end(&mut data)?,
+        None => io::stdin().lock().read_to_end(&mut data)?,
+    };
+
+    let records = if skip_validation {
+        warning!(lang, "{}", Message::ValidationDisabled.render(lang));
+        if data.is_empty() && !options.allow_empty {
+            return Err(Message::EmptyInput.render(lang).into());
+        }
+        None
+    } else {
+        let mut warnings = Vec::new();
+        let transactions =
+            format.parse_records_with_warnings(data.as_slice(), options, &mut warnings)?;
+        if verbose_enabled(verbose) {
+            verbose!(lang, Message::VerboseValidated(transactions.len()));
+        }
+        if !warnings.is_empty() {
+            for warning in warnings {
+                warning!(lang, "{}", warning);
+            }
+            if verbose_enabled(verbose) {
+                verbose!(lang, Message::VerboseReencoding);
+            }
+            write_transactions(
+                &transactions,
+                format,
+                output_path,
+                false,
+                style,
+                verbose,
+                lang,
+                digest,
+            )?;
+            return Ok(Some(transactions.len()));
+        }
+        Some(transactions.len())
+    };
+
+    if verbose_enabled(verbose) {
+        verbose!(lang, Message::VerboseCopying);
+    }
+
+    match output_path {
+        Some(path) => {
+            let mut file = File::create(path)
+                .map_err(|e| Message::CannotCreateFile(path, e.to_string()).render(lang))?;
+            io::copy(&mut data.as_slice(), &mut file)?;
+        }
+        None => {
+            let mut stdout = io::stdout().lock();
+            io::copy(&mut data.as_slice(), &mut stdout)?;
+            stdout.flush()?;
+        }
+    }
+    digest.update(&data)?;
+
+    Ok(records)
+}
+
+/// Конвертирует транзакции в памяти и сравнивает результат с ожидаемым файлом
+fn compare_with_expected(
+    transactions: &[Transaction],
+    output_format: &Format,
+    expected_path: &Path,
+    expected_format: &Format,
+    verbose: bool,
+    lang: Lang,
+) -> Result<bool, Box<dyn std::error::Error>> {
+    let mut buffer = Vec::new();
+    write_using_trait(
+        transactions,
+        output_format,
+        &mut buffer,
+        false,
+        OutputStyle {
+            write_header: true,
+            text_b64_desc: false,
+            footer: false,
+            line_ending: LineEnding::Lf,
+            template: None,
+        },
+        verbose,
+        lang,
+        &mut OutputDigest::default(),
+    )?;
+    let produced = parse_transactions(
+        io::Cursor::new(buffer),
+        output_format,
+        &ParseOptions::default(),
+        lang,
+    )?;
+
+    let expected = read_transactions(
+        Some(expected_path),
+        expected_format,
+        &ParseOptions::default(),
+        false,
+        verbose,
+        lang,
+    )?;
+
+    if produced.len() != expected.len() {
+        let message = Message::ExpectedCountMismatch {
+            produced: produced.len(),
+            path: expected_path,
+            expected: expected.len(),
+        };
+        println!("{}", message.render(lang));
+        return Ok(false);
+    }
+
+    let options = CompareOptions::default();
+    let mut mismatches = 0;
+
+    for (i, (tx1, tx2)) in produced.iter().zip(expected.iter()).enumerate() {
+        let diffs = diff_transactions(tx1, tx2, &options);
+        if diffs.is_empty() {
+            continue;
+        }
+
+        mismatches += 1;
+        println!("{}", Message::MismatchAt(i + 1, tx1.tx_id).render(lang));
+        for diff in diffs {
+            println!("  {}: {} != {}", diff.field.name(), diff.left, diff.right);
+        }
+    }
+
+    if mismatches > 0 {
+        let message = Message::MismatchesFound(mismatches, produced.len());
+        println!("\n{}", message.render(lang));
+    }
+
+    Ok(mismatches == 0)
+}
+
+/// Переписывает бинарный файл, исправляя размеры в заголовках записей
+///
+/// Возвращает количество записанных транзакций.
+fn repair_binary(
+    args: &Args,
+    digest: &mut OutputDigest,
+) -> Result<usize, Box<dyn std::error::Error>> {
+    if args.input_format != Format::Bin || args.output_format != Format::Bin {
+        return Err(Message::RepairNeedsBinary.render(args.lang).into());
+    }
+
+    let mut data = Vec::new();
+    match args.input_file() {
+        Some(path) => File::open(path)?.read_to_end(&mut data)?,
+        None => io::stdin().lock().read_to_end(&mut data)?,
+    };
+
+    let (transactions, repaired) = BinaryParser::repair_sizes(&data)?;
+    if verbose_enabled(args.verbose) {
+        verbose!(args.lang, Message::VerboseRead(transactions.len()));
+    }
+
+    write_transactions(
+        &transactions,
+        &Format::Bin,
+        args.output.as_ref(),
+        false,
+        args.output_style(),
+        args.verbose,
+        args.lang,
+        digest,
+    )?;
+    eprintln!("{}", Message::HeadersRepaired(repaired).render(args.lang));
+
+    Ok(transactions.len())
+}
+
+/// Применяет к транзакциям отбор и преобразования, включённые аргументами
+fn apply_transforms(args: &Args, transactions: &mut Vec<Transaction>) {
+    if let Some((from, to)) = args.between {
+        let before = transactions.len();
+        *transactions = select_time_window(transactions, from, to)
+            .into_iter()
+            .cloned()
+            .collect();
+        if verbose_enabled(args.verbose) {
+            let message = Message::VerboseWindow {
+                selected: transactions.len(),
+                total: before,
+                from,
+                to,
+            };
+            verbose!(args.lang, message);
+        }
     }
 
-    let file = File::open(input_path)?;
-    let mut reader = BufReader::new(file);
+    if args.zero_synthetic_ids {
+        zero_synthetic_ids(transactions);
+        if verbose_enabled(args.verbose) {
+            verbose!(args.lang, Message::VerboseZeroIds);
+        }
+    }
 
-    match format {
-        Format::Csv => {
-            let csv_transactions: CsvTransactions = ParseFromRead::parse(&mut reader)?;
-            Ok(csv_transactions.0)
+    if let Some(granularity) = args.round_timestamps {
+        round_timestamps(transactions, granularity);
+        if verbose_enabled(args.verbose) && granularity > 0 {
+            verbose!(args.lang, Message::VerboseRounded(granularity));
         }
-        Format::Txt => {
-            let text_transactions: TextTransactions = ParseFromRead::parse(&mut reader)?;
-            Ok(text_transactions.0)
+    }
+
+    if args.canonicalize {
+        transactions.iter_mut().for_each(Transaction::canonicalize);
+        if verbose_enabled(args.verbose) {
+            verbose!(args.lang, Message::VerboseCanonicalized);
         }
-        Format::Bin => {
-            let bin_transactions: BinaryTransactions = ParseFromRead::parse(&mut reader)?;
-            Ok(bin_transactions.0)
+    }
+
+    if args.trim_descriptions {
+        trim_descriptions(transactions);
+        if verbose_enabled(args.verbose) {
+            verbose!(args.lang, Message::VerboseTrimmed);
         }
     }
 }
 
+/// Конвертирует каждый файл каталога с расширением входного формата
+///
+/// Для `name.<вход>` создаётся `name.<выход>` в `output_dir`. Ошибка в
+/// отдельном файле выводится в stderr, и обработка продолжается, если
+/// не задан `--fail-fast`.
+///
+/// # Возвращает
+/// Количество файлов, которые не удалось сконвертировать
+fn convert_directory(
+    args: &Args,
+    input_dir: &Path,
+    output_dir: &Path,
+    options: &ParseOptions,
+) -> Result<usize, Box<dyn std::error::Error>> {
+    let lang = args.lang;
+    let extension = args.input_format.extension();
+
+    let mut inputs: Vec<PathBuf> = std::fs::read_dir(input_dir)
+        .map_err(|e| Message::CannotOpenFile(input_dir, e.to_string()).render(lang))?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.is_file()
+                && path
+                    .extension()
+                    .is_some_and(|ext| ext.eq_ignore_ascii_case(extension))
+        })
+        .collect();
+    inputs.sort();
+
+    std::fs::create_dir_all(output_dir)
+        .map_err(|e| Message::CannotCreateDir(output_dir, e.to_string()).render(lang))?;
+
+    let mut converted = 0;
+    let mut failed = 0;
+
+    for input in &inputs {
+        let stem = input.file_stem().unwrap_or_default().to_string_lossy();
+        let output = output_dir.join(format!("{}.{}", stem, args.output_format.extension()));
+
+        match convert_file(args, input, &output, options) {
+            Ok(count) => {
+                converted += 1;
+                println!(
+                    "{}",
+                    Message::BatchConverted(input, &output, count).render(lang)
+                );
+            }
+            Err(e) => {
+                failed += 1;
+                eprintln!(
+                    "{}",
+                    Message::BatchFailed(input, e.to_string()).render(lang)
+                );
+                if args.fail_fast {
+                    break;
+                }
+            }
+        }
+    }
+
+    println!(
+        "{}",
+        Message::BatchSummary { converted, failed }.render(lang)
+    );
+    Ok(failed)
+}
+
+/// Конвертирует один файл пакетного режима и возвращает число записей
+fn convert_file(
+    args: &Args,
+    input: &Path,
+    output: &Path,
+    options: &ParseOptions,
+) -> Result<usize, Box<dyn std::error::Error>> {
+    let mut transactions = read_transactions(
+        Some(input),
+        &args.input_format,
+        options,
+        args.skip_validation,
+        args.verbose,
+        args.lang,
+    )?;
+    apply_transforms(args, &mut transactions);
+
+    write_transactions(
+        &transactions,
+        &args.output_format,
+        Some(&output.to_path_buf()),
+        false,
+        args.output_style(),
+        args.verbose,
+        args.lang,
+        &mut OutputDigest::default(),
+    )?;
+
+    Ok(transactions.len())
+}
+
+/// Печатает в stderr, что сделал бы конвертер без `--dry-run`
+fn print_dry_run_summary(args: &Args, records_in: usize, transactions: &[Transaction]) {
+    let lang = args.lang;
+    eprintln!("{}", Message::DryRun.render(lang));
+    eprintln!("{}", Message::DryRunRead(records_in).render(lang));
+    eprintln!("{}", Message::DryRunOut(transactions.len()).render(lang));
+    if args.zero_synthetic_ids {
+        eprintln!("{}", Message::DryRunZeroIds.render(lang));
+    }
+
+    let target = match (&args.split_by_type, &args.output) {
+        (Some(dir), _) => Message::TargetSplit(dir),
+        (None, Some(path)) if args.append => Message::TargetAppend(path),
+        (None, Some(path)) => Message::TargetFile(path),
+        (None, None) => Message::TargetStdout,
+    };
+    let message = Message::DryRunTarget(target.render(lang), args.output_format);
+    eprintln!("{}", message.render(lang));
+}
+
+/// Печатает план обработки для `--explain`, не читая и не записывая данные
+///
+/// Шаги перечисляются в том порядке, в котором их выполняет `main`.
+///
+/// # Аргументы
+/// * `args` - Аргументы командной строки
+/// * `passthrough` - Будут ли данные скопированы без перекодирования
+fn print_plan(args: &Args, passthrough: bool) {
+    let lang = args.lang;
+    let mut steps = Vec::new();
+
+    let source = match (&args.input_list, &args.input_dir, args.input_file()) {
+        (Some(list), _, _) => Message::SourceList(list),
+        (None, Some(dir), _) => Message::TargetDir(dir),
+        (None, None, Some(path)) => Message::TargetFile(path),
+        (None, None, None) => Message::SourceStdin,
+    };
+    steps.push(Message::PlanRead(source.render(lang), args.input_format));
+
+    if args.repair_binary {
+        steps.push(Message::PlanRepair);
+    } else if args.skip_validation {
+        steps.push(Message::PlanSkipValidation);
+    } else {
+        steps.push(Message::PlanValidate);
+    }
+
+    if passthrough && !args.repair_binary {
+        steps.push(Message::PlanCopy);
+    }
+    if let Some((from, to)) = args.between {
+        steps.push(Message::PlanBetween(from, to));
+    }
+    if args.zero_synthetic_ids {
+        steps.push(Message::PlanZeroIds);
+    }
+    if let Some(granularity) = args.round_timestamps.filter(|&ms| ms > 0) {
+        steps.push(Message::PlanRoundTimestamps(granularity));
+    }
+    if args.canonicalize {
+        steps.push(Message::PlanCanonicalize);
+    }
+    if args.trim_descriptions {
+        steps.push(Message::PlanTrim);
+    }
+
+    if args.count_by_status {
+        steps.push(Message::PlanCountByStatus);
+    } else if let Some(expected) = &args.expected {
+        let format = args.expected_format.unwrap_or(args.output_format);
+        steps.push(Message::PlanCompare(expected, format));
+    } else {
+        let target = match (&args.output_dir, &args.split_by_type, &args.output) {
+            (Some(dir), _, _) => Message::TargetDir(dir),
+            (None, Some(dir), _) => Message::TargetSplit(dir),
+            (None, None, Some(path)) if args.append => Message::TargetAppend(path),
+            (None, None, Some(path)) => Message::TargetFile(path),
+            (None, None, None) => Message::TargetStdout,
+        };
+        if args.template.is_some() {
+            steps.push(Message::PlanWriteTemplate(target.render(lang)));
+        } else {
+            steps.push(Message::PlanWrite(target.render(lang), args.output_format));
+        }
+    }
+
+    println!("{}", Message::Plan.render(lang));
+    for (index, step) in steps.iter().enumerate() {
+        println!("  {}. {}", index + 1, step.render(lang));
+    }
+    if !args.count_by_status && args.expected.is_none() && args.output_format.is_lossy() {
+        let fields = args.output_format.lossy_fields().join(", ");
+        println!("{}", Message::PlanLossy(fields).render(lang));
+    }
+}
+
+/// Записывает транзакции каждого типа в отдельный файл каталога `dir`
+///
+/// Файлы создаются для всех типов, даже если транзакций этого типа нет.
+fn write_split_by_type(
+    transactions: &[Transaction],
+    format: &Format,
+    dir: &Path,
+    style: OutputStyle<'_>,
+    verbose: bool,
+    lang: Lang,
+    digest: &mut OutputDigest,
+) -> Result<(), Box<dyn std::error::Error>> {
+    std::fs::create_dir_all(dir)
+        .map_err(|e| Message::CannotCreateDir(dir, e.to_string()).render(lang))?;
+
+    let parts = [
+        (TransactionType::Deposit, "deposits"),
+        (TransactionType::Transfer, "transfers"),
+        (TransactionType::Withdrawal, "withdrawals"),
+    ];
+
+    for (tx_type, name) in parts {
+        let path = dir.join(format!("{}.{}", name, format.extension()));
+        let selected = filter_by_type(transactions, tx_type);
+        if verbose_enabled(verbose) {
+            verbose!(
+                lang,
+                Message::VerboseSplitPart(tx_type, selected.len(), &path)
+            );
+        }
+        write_transactions(
+            &selected,
+            format,
+            Some(&path),
+            false,
+            style,
+            verbose,
+            lang,
+            digest,
+        )?;
+    }
+
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
 fn write_transactions(
     transactions: &[Transaction],
     format: &Format,
     output_path: Option<&PathBuf>,
+    append: bool,
+    style: OutputStyle<'_>,
     verbose: bool,
+    lang: Lang,
+    digest: &mut OutputDigest,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    if verbose && output_path.is_none() {
-        eprintln!("Вывод будет отправлен в стандартный вывод (stdout)");
-        eprintln!("Используйте --output <файл> для сохранения в файл");
+    if output_path.is_none() && verbose_enabled(verbose) {
+        verbose!(lang, Message::VerboseToStdout);
+        verbose!(lang, Message::VerboseOutputHint);
     }
 
     if output_path.is_none() && matches!(format, Format::Bin) {
-        return Err("Ошибка: Для бинарного формата необходимо указать выходной файл с помощью --output <файл>".into());
+        return Err(Message::BinaryNeedsOutput.render(lang).into());
     }
 
     match output_path {
+        Some(path) if append => {
+            let file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .map_err(|e| Message::CannotOpenFile(path, e.to_string()).render(lang))?;
+            let has_data = file.metadata()?.len() > 0;
+            if has_data && verbose_enabled(verbose) {
+                verbose!(lang, Message::VerboseAppendTo(path));
+            }
+            let mut writer = BufWriter::new(file);
+            write_using_trait(
+                transactions,
+                format,
+                &mut writer,
+                has_data,
+                style,
+                verbose,
+                lang,
+                digest,
+            )
+        }
         Some(path) => {
-            if path.exists() && verbose {
-                eprintln!("Файл '{}' будет перезаписан", path.display());
+            if path.exists() && verbose_enabled(verbose) {
+                verbose!(lang, Message::VerboseOverwrite(path));
             }
 
             let file = File::create(path)
-                .map_err(|e| format!("Не удалось создать файл '{}': {}", path.display(), e))?;
+                .map_err(|e| Message::CannotCreateFile(path, e.to_string()).render(lang))?;
             let mut writer = BufWriter::new(file);
-            write_using_trait(transactions, format, &mut writer, verbose)
+            write_using_trait(
+                transactions,
+                format,
+                &mut writer,
+                false,
+                style,
+                verbose,
+                lang,
+                digest,
+            )
         }
         None => {
             let stdout = io::stdout();
             let mut writer = BufWriter::new(stdout.lock());
-            write_using_trait(transactions, format, &mut writer, verbose)
+            write_using_trait(
+                transactions,
+                format,
+                &mut writer,
+                false,
+                style,
+                verbose,
+                lang,
+                digest,
+            )
         }
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn write_using_trait<W: std::io::Write>(
     transactions: &[Transaction],
     format: &Format,
     writer: &mut W,
+    appending: bool,
+    style: OutputStyle<'_>,
     verbose: bool,
+    lang: Lang,
+    digest: &mut OutputDigest,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    if verbose {
-        eprintln!(
-            "Запись {} транзакций в формат {:?}...",
-            transactions.len(),
-            format
-        );
+    if verbose_enabled(verbose) {
+        verbose!(lang, Message::VerboseWriting(transactions.len(), *format));
     }
 
-    match format {
-        Format::Csv => {
-            if verbose {
-                eprintln!("Формат: CSV (заголовок + данные)");
+    let mut writer = CountingWriter::new(HashingWriter::new(writer, digest));
+    let result: Result<(), Box<dyn std::error::Error>> = match (style.template, format) {
+        (Some(template), _) => {
+            if verbose_enabled(verbose) {
+                verbose!(lang, Message::VerboseTemplateLayout);
             }
-            let csv_transactions = CsvTransactions(transactions.to_vec());
-            csv_transactions
-                .write(writer)
-                .map_err(|e| format!("Ошибка записи CSV: {}", e).into())
-        }
-        Format::Txt => {
-            if verbose {
-                eprintln!("Формат: Text (KEY: VALUE с комментариями)");
+            template
+                .write_records(transactions, &mut writer, style.line_ending)
+                .map_err(|e| {
+                    Message::TemplateWriteFailed(e.to_string())
+                        .render(lang)
+                        .into()
+                })
+        }
+        (None, Format::Csv) => {
+            if verbose_enabled(verbose) {
+                verbose!(lang, Message::VerboseCsvLayout);
             }
-            let text_transactions = TextTransactions(transactions.to_vec());
-            text_transactions
-                .write(writer)
-                .map_err(|e| format!("Ошибка записи текстового формата: {}", e).into())
-        }
-        Format::Bin => {
-            if verbose {
-                eprintln!("Формат: Binary (магическое число YPBN + бинарные данные)");
-                eprintln!(
-                    "Размер одной записи: ~{} байт + размер описания",
-                    std::mem::size_of::<u64>() * 5 + 2
+            // Заголовок уже есть в непустом файле, повторно его не пишем
+            let options = CsvWriteOptions {
+                write_header: style.write_header && !appending,
+                line_ending: style.line_ending,
+                ..Default::default()
+            };
+            CsvParser::write_records_with(transactions, &mut writer, &options).map_err(|e| {
+                Message::WriteFailed(Format::Csv, e.to_string())
+                    .render(lang)
+                    .into()
+            })
+        }
+        (None, Format::Txt) => {
+            if verbose_enabled(verbose) {
+                verbose!(lang, Message::VerboseTextLayout);
+            }
+            if appending && !transactions.is_empty() {
+                // Пустая строка отделяет новые записи от уже существующих
+                write!(writer, "{}", style.line_ending.as_str())?;
+            }
+            let options = TextWriteOptions {
+                base64_description: style.text_b64_desc,
+                line_ending: style.line_ending,
+                emit_footer: style.footer,
+                ..Default::default()
+            };
+            TextParser::write_records_with(transactions, &mut writer, &options).map_err(|e| {
+                Message::WriteFailed(Format::Txt, e.to_string())
+                    .render(lang)
+                    .into()
+            })
+        }
+        (None, Format::Bin) => {
+            if verbose_enabled(verbose) {
+                verbose!(lang, Message::VerboseBinaryLayout);
+                verbose!(
+                    lang,
+                    Message::VerboseRecordSize(std::mem::size_of::<u64>() * 5 + 2)
                 );
             }
-            let bin_transactions = BinaryTransactions(transactions.to_vec());
-            bin_transactions
-                .write(writer)
-                .map_err(|e| format!("Ошибка записи бинарного формата: {}", e).into())
+            BinaryParser::write_records(transactions, &mut writer).map_err(|e| {
+                Message::WriteFailed(Format::Bin, e.to_string())
+                    .render(lang)
+                    .into()
+            })
+        }
+    };
+    result?;
+
+    // BufWriter при удалении молча теряет ошибки записи, поэтому буфер
+    // сбрасывается явно, до проверки хеша записанных данных
+    writer
+        .flush()
+        .map_err(|e| Message::WriteFailed(*format, e.to_string()).render(lang))?;
+
+    if verbose_enabled(verbose) {
+        let message = Message::VerboseWritten(writer.bytes_written(), transactions.len());
+        verbose!(lang, message);
+    }
+
+    Ok(())
+}
+
+/// Обёртка над записываемым потоком, передающая принятые байты в `OutputDigest`
+///
+/// Хэш считается по тем же байтам, которые получил нижележащий поток.
+struct HashingWriter<'a, W> {
+    inner: W,
+    digest: &'a mut OutputDigest,
+}
+
+impl<'a, W: Write> HashingWriter<'a, W> {
+    fn new(inner: W, digest: &'a mut OutputDigest) -> Self {
+        Self { inner, digest }
+    }
+}
+
+impl<W: Write> Write for HashingWriter<'_, W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.digest.update(&buf[..written])?;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Завершает запись: добавляет строку аудита и сверяет хеш с `--verify-hash`
+///
+/// Вызывается только после того, как вывод записан и сброшен без ошибок,
+/// поэтому строка аудита не появляется для незавершённой записи.
+///
+/// # Аргументы
+/// * `args` - Аргументы командной строки
+/// * `records` - Количество записанных транзакций (`None`, если не подсчитано)
+/// * `digest` - SHA-256, размер и журнал прогресса записанных данных
+fn finish_output(
+    args: &Args,
+    records: Option<usize>,
+    mut digest: OutputDigest,
+) -> Result<(), Box<dyn std::error::Error>> {
+    digest.report("done", records)?;
+    let sha256 = format!("{:x}", digest.hasher.finalize());
+
+    if let Some(audit_log) = &args.audit_log {
+        append_audit_line(audit_log, args, records, &sha256, args.lang)?;
+    }
+
+    if let Some(expected) = &args.verify_hash {
+        if !expected.eq_ignore_ascii_case(&sha256) {
+            let message = Message::HashMismatch {
+                expected,
+                actual: &sha256,
+            };
+            return Err(message.render(args.lang).into());
+        }
+        if verbose_enabled(args.verbose) {
+            verbose!(args.lang, Message::VerboseHashMatches(&sha256));
+        }
+    }
+
+    Ok(())
+}
+
+/// Интервал между промежуточными записями `--progress-file`
+const PROGRESS_INTERVAL: Duration = Duration::from_secs(1);
+
+/// SHA-256 и размер записанных данных, общие для всех выходных файлов
+///
+/// Если задан `--progress-file`, по мере записи в него добавляются
+/// записи прогресса не чаще раза в `PROGRESS_INTERVAL`.
+#[derive(Default)]
+struct OutputDigest {
+    hasher: Sha256,
+    bytes: u64,
+    progress: Option<ProgressLog>,
+}
+
+impl OutputDigest {
+    fn new(progress: Option<ProgressLog>) -> Self {
+        OutputDigest {
+            progress,
+            ..Default::default()
+        }
+    }
+
+    /// Учитывает записанные байты
+    fn update(&mut self, data: &[u8]) -> io::Result<()> {
+        self.hasher.update(data);
+        self.bytes += data.len() as u64;
+        if let Some(progress) = &mut self.progress
+            && progress.last.elapsed() >= PROGRESS_INTERVAL
+        {
+            progress.write("write", None, self.bytes)?;
+        }
+        Ok(())
+    }
+
+    /// Добавляет запись прогресса этапа `phase`, если журнал включён
+    fn report(&mut self, phase: &str, records: Option<usize>) -> io::Result<()> {
+        match &mut self.progress {
+            Some(progress) => progress.write(phase, records, self.bytes),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Журнал прогресса `--progress-file`: по одной JSON-строке на запись
+struct ProgressLog {
+    file: File,
+    started: Instant,
+    last: Instant,
+}
+
+impl ProgressLog {
+    /// Записывает строку `{"phase", "records", "bytes", "elapsed_ms"}`
+    ///
+    /// `records` - количество записей на этом этапе (`null`, если ещё
+    /// не известно), `bytes` - количество уже записанных байт вывода.
+    fn write(&mut self, phase: &str, records: Option<usize>, bytes: u64) -> io::Result<()> {
+        let line = serde_json::json!({
+            "phase": phase,
+            "records": records,
+            "bytes": bytes,
+            "elapsed_ms": self.started.elapsed().as_millis() as u64,
+        });
+        writeln!(self.file, "{}", line)?;
+        self.file.flush()?;
+        self.last = Instant::now();
+        Ok(())
+    }
+}
+
+/// Дописывает в журнал аудита одну JSON-строку о выполненной конвертации
+///
+/// # Аргументы
+/// * `path` - Путь к журналу аудита (создаётся при необходимости)
+/// * `args` - Аргументы запуска: пути и форматы
+/// * `records` - Количество записанных транзакций (`None`, если не подсчитано)
+/// * `sha256` - SHA-256 записанных байт в шестнадцатеричном виде
+/// * `lang` - Язык сообщений об ошибках
+fn append_audit_line(
+    path: &Path,
+    args: &Args,
+    records: Option<usize>,
+    sha256: &str,
+    lang: Lang,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let output = args.split_by_type.as_ref().or(args.output.as_ref());
+    let line = serde_json::json!({
+        "timestamp": chrono::Utc::now().to_rfc3339(),
+        "input": args.input_file().map(|input| input.display().to_string()),
+        "input_format": args.input_format.extension(),
+        "records": records,
+        "output": output.map(|output| output.display().to_string()),
+        "output_format": args.output_format.extension(),
+        "sha256": sha256,
+    });
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| Message::CannotOpenFile(path, e.to_string()).render(lang))?;
+    writeln!(file, "{}", line)?;
+
+    if verbose_enabled(args.verbose) {
+        verbose!(lang, Message::VerboseAuditAppended(path));
+    }
+
+    Ok(())
+}
+
+#[cfg(all(test, feature = "logging"))]
+mod tests {
+    use super::*;
+    use parser_lib::generate_sample;
+    use std::sync::Mutex;
+
+    /// Логгер, сохраняющий сообщения уровня info и выше
+    struct CapturingLogger {
+        messages: Mutex<Vec<String>>,
+    }
+
+    impl log::Log for CapturingLogger {
+        fn enabled(&self, metadata: &log::Metadata) -> bool {
+            metadata.level() <= log::Level::Info
+        }
+
+        fn log(&self, record: &log::Record) {
+            if self.enabled(record.metadata()) {
+                self.messages
+                    .lock()
+                    .unwrap()
+                    .push(record.args().to_string());
+            }
+        }
+
+        fn flush(&self) {}
+    }
+
+    static LOGGER: CapturingLogger = CapturingLogger {
+        messages: Mutex::new(Vec::new()),
+    };
+
+    #[test]
+    fn test_info_record_emitted_per_file_read() {
+        log::set_logger(&LOGGER).unwrap();
+        log::set_max_level(log::LevelFilter::Info);
+
+        let dir = tempfile::tempdir().unwrap();
+        let paths: Vec<PathBuf> = (1..=2)
+            .map(|i| {
+                let path = dir.path().join(format!("input{}.csv", i));
+                let mut file = File::create(&path).unwrap();
+                CsvParser::write_records(&generate_sample(i, 1), &mut file).unwrap();
+                path
+            })
+            .collect();
+
+        for path in &paths {
+            read_transactions(
+                Some(path),
+                &Format::Csv,
+                &ParseOptions::default(),
+                false,
+                false,
+                Lang::En,
+            )
+            .unwrap();
+        }
+
+        let messages = LOGGER.messages.lock().unwrap();
+        for path in &paths {
+            let source = path.display().to_string();
+            assert_eq!(
+                messages.iter().filter(|m| m.contains(&source)).count(),
+                1,
+                "{:?}",
+                messages
+            );
         }
     }
 }
@@ -0,0 +1,827 @@
+use crate::{Field, Format, Transaction, TransactionType};
+use std::path::Path;
+
+/// Язык сообщений утилит командной строки
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Lang {
+    /// Английский
+    En,
+    /// Русский (по умолчанию)
+    #[default]
+    Ru,
+}
+
+/// Сообщения утилит командной строки
+///
+/// Тексты на всех языках собраны в [`Message::render`], чтобы утилиты
+/// не содержали разбросанных строковых литералов.
+///
+/// # Пример
+/// ```
+/// use parser_lib::{Lang, Message};
+/// use std::path::Path;
+///
+/// let msg = Message::InputNotFound(Path::new("data.csv"));
+/// assert_eq!(msg.render(Lang::En), "Error: input file 'data.csv' not found");
+/// assert_eq!(msg.render(Lang::Ru), "Ошибка: входной файл 'data.csv' не найден");
+/// ```
+#[derive(Debug, Clone)]
+pub enum Message<'a> {
+    /// Префикс предупреждения
+    Warning,
+    /// Входной файл конвертера не найден
+    InputNotFound(&'a Path),
+    /// Файл для сравнения не найден
+    FileNotFound(&'a Path),
+    /// Заголовок списка примеров
+    AvailableExamples,
+    /// Заголовок примера использования
+    UsageExample,
+    /// Выходной формат теряет поля
+    LossyOutput {
+        /// Выходной формат
+        format: Format,
+        /// Имена теряемых полей через запятую
+        fields: String,
+    },
+    /// Проверка бизнес-правил отключена
+    ValidationDisabled,
+    /// Бинарный вывод в stdout запрещён
+    BinaryNeedsOutput,
+    /// Вход пуст, а `--require-nonempty` требует данных
+    EmptyInput,
+    /// Не удалось создать файл
+    CannotCreateFile(&'a Path, String),
+    /// Не удалось открыть файл
+    CannotOpenFile(&'a Path, String),
+    /// Не удалось создать каталог
+    CannotCreateDir(&'a Path, String),
+    /// Ошибка записи в заданном формате
+    WriteFailed(Format, String),
+    /// Ошибка записи по шаблону `--template`
+    TemplateWriteFailed(String),
+    /// Результат конвертации совпал с ожидаемым файлом
+    ExpectedMatches(&'a Path),
+    /// Количество транзакций после конвертации и в ожидаемом файле различается
+    ExpectedCountMismatch {
+        /// Количество транзакций после конвертации
+        produced: usize,
+        /// Путь к ожидаемому файлу
+        path: &'a Path,
+        /// Количество транзакций в ожидаемом файле
+        expected: usize,
+    },
+    /// Заголовок несоответствия в транзакции с номером (с единицы) и ID
+    MismatchAt(usize, u64),
+    /// Итог сравнения: найдено несоответствий из общего числа
+    MismatchesFound(usize, usize),
+    /// Количество несоответствий, не выведенных подробно
+    MoreMismatches(usize),
+    /// Файлы сравнения идентичны
+    FilesIdentical(&'a Path, &'a Path),
+    /// Файлы сравнения совпадают побайтово
+    BytesIdentical(&'a Path, &'a Path),
+    /// Файлы различаются побайтово, далее сравниваются транзакции
+    BytesDiffer,
+    /// Ошибка во время сравнения
+    CompareFailed(String),
+    /// Заголовок различия в количестве транзакций
+    CountMismatch,
+    /// Количество транзакций в файле
+    FileCount(&'a Path, usize),
+    /// Оба файла пусты
+    BothEmpty,
+    /// Все транзакции совпадают
+    AllMatch(usize),
+    /// Статистика сравнения: совпавшие, несовпавшие и всего
+    CompareStats {
+        /// Количество совпавших транзакций
+        identical: usize,
+        /// Количество несовпавших транзакций
+        mismatched: usize,
+        /// Общее количество транзакций
+        total: usize,
+    },
+    /// Заголовок сводки `--dry-run`
+    DryRun,
+    /// Количество прочитанных записей
+    DryRunRead(usize),
+    /// Количество записей к выводу
+    DryRunOut(usize),
+    /// Применено обнуление системных ID
+    DryRunZeroIds,
+    /// Цель записи: каталог с файлами по типам
+    TargetSplit(&'a Path),
+    /// Цель записи: дописывание в файл
+    TargetAppend(&'a Path),
+    /// Цель записи: файл
+    TargetFile(&'a Path),
+    /// Цель записи: стандартный вывод
+    TargetStdout,
+    /// Куда была бы выполнена запись
+    DryRunTarget(String, Format),
+    /// Пакетный режим: файл сконвертирован (вход, выход, число записей)
+    BatchConverted(&'a Path, &'a Path, usize),
+    /// Пакетный режим: ошибка конвертации файла
+    BatchFailed(&'a Path, String),
+    /// SHA-256 записанных данных не совпал с ожидаемым
+    HashMismatch {
+        /// Ожидаемый хеш из `--verify-hash`
+        expected: &'a str,
+        /// Хеш записанных данных
+        actual: &'a str,
+    },
+    /// Режим восстановления требует бинарного входа и выхода
+    RepairNeedsBinary,
+    /// Вывод только заголовка поддерживается лишь для CSV
+    HeaderOnlyNeedsCsv,
+    /// Формат файла не задан и не определяется по расширению
+    UnknownExtension(&'a Path),
+    /// Количество исправленных заголовков бинарных записей
+    HeadersRepaired(usize),
+    /// Заголовок плана обработки (`--explain`)
+    Plan,
+    /// Шаг плана: чтение входа в заданном формате
+    PlanRead(String, Format),
+    /// Шаг плана: проверка бизнес-правил
+    PlanValidate,
+    /// Шаг плана: проверка бизнес-правил пропускается
+    PlanSkipValidation,
+    /// Шаг плана: исправление заголовков размера бинарных записей
+    PlanRepair,
+    /// Шаг плана: побайтовое копирование при совпадении форматов
+    PlanCopy,
+    /// Шаг плана: отбор по окну временных меток `[от, до)` в мс
+    PlanBetween(u64, u64),
+    /// Шаг плана: обнуление системных ID
+    PlanZeroIds,
+    /// Шаг плана: округление временных меток (шаг в мс)
+    PlanRoundTimestamps(u64),
+    /// Шаг плана: приведение записей к инвариантам типа
+    PlanCanonicalize,
+    /// Шаг плана: удаление пробелов по краям описаний
+    PlanTrim,
+    /// Шаг плана: подсчёт записей по статусу
+    PlanCountByStatus,
+    /// Шаг плана: сравнение с ожидаемым файлом в заданном формате
+    PlanCompare(&'a Path, Format),
+    /// Шаг плана: запись результата в заданном формате
+    PlanWrite(String, Format),
+    /// Шаг плана: запись результата по шаблону `--template`
+    PlanWriteTemplate(String),
+    /// Примечание к записи: поля, которые формат не сохраняет
+    PlanLossy(String),
+    /// Источник: стандартный ввод
+    SourceStdin,
+    /// Источник: файлы из списка `--input-list`
+    SourceList(&'a Path),
+    /// Источник или цель: каталог
+    TargetDir(&'a Path),
+    /// Итог пакетной конвертации
+    BatchSummary {
+        /// Успешно сконвертировано файлов
+        converted: usize,
+        /// Файлов с ошибками
+        failed: usize,
+    },
+    /// Заголовок диагностики `--verbose` с названием утилиты
+    Banner(&'a str),
+    /// Диагностика: список входных файлов
+    VerboseInputList(&'a Path),
+    /// Диагностика: входной каталог
+    VerboseInputDir(&'a Path),
+    /// Диагностика: входной файл или `<stdin>`
+    VerboseInputFile(String),
+    /// Диагностика: входной формат
+    VerboseInputFormat(Format),
+    /// Диагностика: выходной формат
+    VerboseOutputFormat(Format),
+    /// Диагностика: выходной каталог
+    VerboseOutputDir(&'a Path),
+    /// Диагностика: выходной файл или `<stdout>`
+    VerboseOutputFile(String),
+    /// Диагностика: проверка бизнес-правил пропускается
+    VerboseSkipValidation,
+    /// Диагностика: записи дописываются в конец выходного файла
+    VerboseAppendMode,
+    /// Диагностика: ограничение количества записей
+    VerboseMaxRecords(usize),
+    /// Диагностика: ограничение длины строки в байтах
+    VerboseMaxLineBytes(usize),
+    /// Диагностика: первая прочитанная транзакция
+    VerboseFirstTransaction(&'a Transaction),
+    /// Диагностика: последняя прочитанная транзакция
+    VerboseLastTransaction(&'a Transaction),
+    /// Диагностика: конвертация завершена
+    VerboseDone,
+    /// Диагностика: количество транзакций, прочитанных из источника
+    VerboseReadFrom(String, usize),
+    /// Диагностика: количество прочитанных транзакций
+    VerboseRead(usize),
+    /// Диагностика: количество проверенных транзакций
+    VerboseValidated(usize),
+    /// Диагностика: разбор дал предупреждения, данные перекодируются
+    VerboseReencoding,
+    /// Диагностика: форматы совпадают, данные копируются
+    VerboseCopying,
+    /// Диагностика: отбор по окну временных меток `[от, до)` в мс
+    VerboseWindow {
+        /// Количество отобранных транзакций
+        selected: usize,
+        /// Количество транзакций до отбора
+        total: usize,
+        /// Начало окна
+        from: u64,
+        /// Конец окна (не включается)
+        to: u64,
+    },
+    /// Диагностика: системные ID обнулены
+    VerboseZeroIds,
+    /// Диагностика: временные метки округлены (шаг в мс)
+    VerboseRounded(u64),
+    /// Диагностика: записи приведены к инвариантам типа
+    VerboseCanonicalized,
+    /// Диагностика: пробелы по краям описаний удалены
+    VerboseTrimmed,
+    /// Диагностика: транзакции типа записываются в файл
+    VerboseSplitPart(TransactionType, usize, &'a Path),
+    /// Диагностика: вывод отправляется в stdout
+    VerboseToStdout,
+    /// Диагностика: подсказка о флаге `--output`
+    VerboseOutputHint,
+    /// Диагностика: записи дописываются в непустой файл
+    VerboseAppendTo(&'a Path),
+    /// Диагностика: существующий файл перезаписывается
+    VerboseOverwrite(&'a Path),
+    /// Диагностика: запись транзакций в заданном формате
+    VerboseWriting(usize, Format),
+    /// Диагностика: запись по шаблону
+    VerboseTemplateLayout,
+    /// Диагностика: структура CSV вывода
+    VerboseCsvLayout,
+    /// Диагностика: структура текстового вывода
+    VerboseTextLayout,
+    /// Диагностика: структура бинарного вывода
+    VerboseBinaryLayout,
+    /// Диагностика: размер бинарной записи без описания
+    VerboseRecordSize(usize),
+    /// Диагностика: записано байт и транзакций
+    VerboseWritten(u64, usize),
+    /// Диагностика: SHA-256 совпал с `--verify-hash`
+    VerboseHashMatches(&'a str),
+    /// Диагностика: строка аудита добавлена в файл
+    VerboseAuditAppended(&'a Path),
+    /// Диагностика: заголовок списка сравниваемых файлов
+    VerboseComparing,
+    /// Диагностика: сравниваемый файл с номером и форматом
+    VerboseCompareFile(usize, &'a Path, Format),
+    /// Диагностика: различия в поле игнорируются
+    VerboseIgnoredField(Field),
+    /// Диагностика: форматы различаются, побайтовое сравнение пропущено
+    VerboseFormatsDiffer,
+    /// Диагностика: транзакции отсортированы по TX_ID
+    VerboseSorted,
+}
+
+impl Message<'_> {
+    /// Возвращает текст сообщения на выбранном языке
+    pub fn render(&self, lang: Lang) -> String {
+        use Lang::{En, Ru};
+        use Message::*;
+
+        match (self, lang) {
+            (Warning, En) => "Warning".to_string(),
+            (Warning, Ru) => "Предупреждение".to_string(),
+            (InputNotFound(path), En) => {
+                format!("Error: input file '{}' not found", path.display())
+            }
+            (InputNotFound(path), Ru) => {
+                format!("Ошибка: входной файл '{}' не найден", path.display())
+            }
+            (FileNotFound(path), En) => format!("Error: file '{}' not found", path.display()),
+            (FileNotFound(path), Ru) => format!("Ошибка: файл '{}' не найден", path.display()),
+            (AvailableExamples, En) => "Available example files in 'examples/':".to_string(),
+            (AvailableExamples, Ru) => "Доступные примеры файлов в папке 'examples/':".to_string(),
+            (UsageExample, En) => "\nExample usage:".to_string(),
+            (UsageExample, Ru) => "\nПример использования:".to_string(),
+            (LossyOutput { format, fields }, En) => format!(
+                "format {:?} does not preserve these fields on a round trip: {}",
+                format, fields
+            ),
+            (LossyOutput { format, fields }, Ru) => format!(
+                "формат {:?} не гарантирует сохранение полей при обратной конвертации: {}",
+                format, fields
+            ),
+            (ValidationDisabled, En) => "business rule validation is disabled".to_string(),
+            (ValidationDisabled, Ru) => "проверка бизнес-правил отключена".to_string(),
+            (BinaryNeedsOutput, En) => {
+                "Error: binary output requires an output file, use --output <file>".to_string()
+            }
+            (BinaryNeedsOutput, Ru) => "Ошибка: Для бинарного формата необходимо указать выходной файл с помощью --output <файл>".to_string(),
+            (EmptyInput, En) => "Error: empty input".to_string(),
+            (EmptyInput, Ru) => "Ошибка: входные данные пусты".to_string(),
+            (CannotCreateFile(path, e), En) => {
+                format!("Cannot create file '{}': {}", path.display(), e)
+            }
+            (CannotCreateFile(path, e), Ru) => {
+                format!("Не удалось создать файл '{}': {}", path.display(), e)
+            }
+            (CannotOpenFile(path, e), En) => {
+                format!("Cannot open file '{}': {}", path.display(), e)
+            }
+            (CannotOpenFile(path, e), Ru) => {
+                format!("Не удалось открыть файл '{}': {}", path.display(), e)
+            }
+            (CannotCreateDir(path, e), En) => {
+                format!("Cannot create directory '{}': {}", path.display(), e)
+            }
+            (CannotCreateDir(path, e), Ru) => {
+                format!("Не удалось создать каталог '{}': {}", path.display(), e)
+            }
+            (WriteFailed(Format::Csv, e), En) => format!("Failed to write CSV: {}", e),
+            (WriteFailed(Format::Txt, e), En) => format!("Failed to write text format: {}", e),
+            (WriteFailed(Format::Bin, e), En) => format!("Failed to write binary format: {}", e),
+            (WriteFailed(Format::Csv, e), Ru) => format!("Ошибка записи CSV: {}", e),
+            (WriteFailed(Format::Txt, e), Ru) => {
+                format!("Ошибка записи текстового формата: {}", e)
+            }
+            (WriteFailed(Format::Bin, e), Ru) => {
+                format!("Ошибка записи бинарного формата: {}", e)
+            }
+            (TemplateWriteFailed(e), En) => format!("Failed to write using the template: {}", e),
+            (TemplateWriteFailed(e), Ru) => format!("Ошибка записи по шаблону: {}", e),
+            (ExpectedMatches(path), En) => {
+                format!("Conversion result matches '{}'.", path.display())
+            }
+            (ExpectedMatches(path), Ru) => {
+                format!("Результат конвертации совпадает с '{}'.", path.display())
+            }
+            (
+                ExpectedCountMismatch {
+                    produced,
+                    path,
+                    expected,
+                },
+                En,
+            ) => format!(
+                "Transaction counts differ:\n  After conversion: {}\n  In '{}': {}",
+                produced,
+                path.display(),
+                expected
+            ),
+            (
+                ExpectedCountMismatch {
+                    produced,
+                    path,
+                    expected,
+                },
+                Ru,
+            ) => format!(
+                "Количество транзакций не совпадает:\n  После конвертации: {}\n  В '{}': {}",
+                produced,
+                path.display(),
+                expected
+            ),
+            (MismatchAt(index, tx_id), En) => {
+                format!("\nMismatch in transaction #{} (ID: {}):", index, tx_id)
+            }
+            (MismatchAt(index, tx_id), Ru) => {
+                format!("\nНесоответствие в транзакции #{} (ID: {}):", index, tx_id)
+            }
+            (MismatchesFound(count, total), En) => {
+                format!("Found {} mismatches in {} transactions", count, total)
+            }
+            (MismatchesFound(count, total), Ru) => {
+                format!("Найдено {} несоответствий из {} транзакций", count, total)
+            }
+            (MoreMismatches(count), En) => format!("\n... and {} more mismatches.", count),
+            (MoreMismatches(count), Ru) => format!("\n... и еще {} несоответствий.", count),
+            (FilesIdentical(a, b), En) => format!(
+                "Files '{}' and '{}' are identical.",
+                a.display(),
+                b.display()
+            ),
+            (FilesIdentical(a, b), Ru) => format!(
+                "Файлы '{}' и '{}' идентичны.",
+                a.display(),
+                b.display()
+            ),
+            (BytesIdentical(a, b), En) => format!(
+                "Files '{}' and '{}' are byte-identical.",
+                a.display(),
+                b.display()
+            ),
+            (BytesIdentical(a, b), Ru) => format!(
+                "Файлы '{}' и '{}' совпадают побайтово.",
+                a.display(),
+                b.display()
+            ),
+            (BytesDiffer, En) => {
+                "Files differ byte-for-byte, comparing transactions:".to_string()
+            }
+            (BytesDiffer, Ru) => "Файлы различаются побайтово, сравниваем транзакции:".to_string(),
+            (CompareFailed(e), En) => format!("Comparison failed: {}", e),
+            (CompareFailed(e), Ru) => format!("Ошибка при сравнении: {}", e),
+            (CountMismatch, En) => "Files contain different numbers of transactions:".to_string(),
+            (CountMismatch, Ru) => "Файлы содержат разное количество транзакций:".to_string(),
+            (FileCount(path, count), En) => {
+                format!("  In '{}': {} transactions", path.display(), count)
+            }
+            (FileCount(path, count), Ru) => {
+                format!("  В '{}': {} транзакций", path.display(), count)
+            }
+            (BothEmpty, En) => "Both files are empty.".to_string(),
+            (BothEmpty, Ru) => "Оба файла пусты.".to_string(),
+            (AllMatch(count), En) => format!("All {} transactions match.", count),
+            (AllMatch(count), Ru) => format!("Все {} транзакций совпадают.", count),
+            (
+                CompareStats {
+                    identical,
+                    mismatched,
+                    total,
+                },
+                En,
+            ) => format!(
+                "\nStatistics:\n  Identical transactions: {}\n  Mismatched transactions: {}\n  Total transactions: {}",
+                identical, mismatched, total
+            ),
+            (
+                CompareStats {
+                    identical,
+                    mismatched,
+                    total,
+                },
+                Ru,
+            ) => format!(
+                "\nСтатистика:\n  Идентичных транзакций: {}\n  Несовпадающих транзакций: {}\n  Всего транзакций: {}",
+                identical, mismatched, total
+            ),
+            (DryRun, En) => "Dry run: no files are changed".to_string(),
+            (DryRun, Ru) => "Пробный запуск: файлы не изменяются".to_string(),
+            (DryRunRead(count), En) => format!("  Records read: {}", count),
+            (DryRunRead(count), Ru) => format!("  Прочитано записей: {}", count),
+            (DryRunOut(count), En) => format!("  Records to write: {}", count),
+            (DryRunOut(count), Ru) => format!("  Записей к выводу: {}", count),
+            (DryRunZeroIds, En) => "  Transforms: zero synthetic IDs".to_string(),
+            (DryRunZeroIds, Ru) => "  Преобразования: обнуление системных ID".to_string(),
+            (TargetSplit(dir), En) => {
+                format!("directory '{}' (one file per type)", dir.display())
+            }
+            (TargetSplit(dir), Ru) => {
+                format!("каталог '{}' (по файлу на тип)", dir.display())
+            }
+            (TargetAppend(path), En) => format!("append to '{}'", path.display()),
+            (TargetAppend(path), Ru) => format!("дописывание в '{}'", path.display()),
+            (TargetFile(path), En) => format!("file '{}'", path.display()),
+            (TargetFile(path), Ru) => format!("файл '{}'", path.display()),
+            (TargetStdout, _) => "<stdout>".to_string(),
+            (DryRunTarget(target, format), En) => {
+                format!("  Would write to: {} (format {:?})", target, format)
+            }
+            (DryRunTarget(target, format), Ru) => {
+                format!("  Была бы выполнена запись: {} (формат {:?})", target, format)
+            }
+            (BatchConverted(input, output, count), En) => format!(
+                "OK: '{}' -> '{}' ({} records)",
+                input.display(),
+                output.display(),
+                count
+            ),
+            (BatchConverted(input, output, count), Ru) => format!(
+                "OK: '{}' -> '{}' (записей: {})",
+                input.display(),
+                output.display(),
+                count
+            ),
+            (BatchFailed(input, error), En) => {
+                format!("FAILED: '{}': {}", input.display(), error)
+            }
+            (BatchFailed(input, error), Ru) => {
+                format!("ОШИБКА: '{}': {}", input.display(), error)
+            }
+            (HashMismatch { expected, actual }, En) => {
+                format!(
+                    "Error: SHA-256 mismatch: expected {}, got {}",
+                    expected, actual
+                )
+            }
+            (HashMismatch { expected, actual }, Ru) => {
+                format!(
+                    "Ошибка: SHA-256 не совпадает: ожидался {}, получен {}",
+                    expected, actual
+                )
+            }
+            (RepairNeedsBinary, En) => {
+                "Error: --repair-binary requires --input-format bin and --output-format bin"
+                    .to_string()
+            }
+            (RepairNeedsBinary, Ru) => {
+                "Ошибка: --repair-binary работает только с --input-format bin и --output-format bin"
+                    .to_string()
+            }
+            (HeaderOnlyNeedsCsv, En) => {
+                "Error: --emit-header-only requires --output-format csv".to_string()
+            }
+            (HeaderOnlyNeedsCsv, Ru) => {
+                "Ошибка: --emit-header-only работает только с --output-format csv".to_string()
+            }
+            (UnknownExtension(path), En) => format!(
+                "Error: cannot infer the format of '{}' from its extension; pass it explicitly",
+                path.display()
+            ),
+            (UnknownExtension(path), Ru) => format!(
+                "Ошибка: не удалось определить формат '{}' по расширению; укажите его явно",
+                path.display()
+            ),
+            (HeadersRepaired(count), En) => format!("Record size headers corrected: {}", count),
+            (HeadersRepaired(count), Ru) => {
+                format!("Исправлено заголовков размера записи: {}", count)
+            }
+            (Plan, En) => "Processing plan:".to_string(),
+            (Plan, Ru) => "План обработки:".to_string(),
+            (PlanRead(source, format), En) => format!("read {} as {:?}", source, format),
+            (PlanRead(source, format), Ru) => {
+                format!("чтение: {} (формат {:?})", source, format)
+            }
+            (PlanValidate, En) => "validate business rules".to_string(),
+            (PlanValidate, Ru) => "проверка бизнес-правил".to_string(),
+            (PlanSkipValidation, En) => "skip business rule validation".to_string(),
+            (PlanSkipValidation, Ru) => "проверка бизнес-правил пропускается".to_string(),
+            (PlanRepair, En) => "fix record size headers".to_string(),
+            (PlanRepair, Ru) => "исправление заголовков размера записей".to_string(),
+            (PlanCopy, En) => "copy bytes unchanged (formats match)".to_string(),
+            (PlanCopy, Ru) => "копирование без перекодирования (форматы совпадают)".to_string(),
+            (PlanBetween(from, to), En) => {
+                format!("filter: timestamps in [{}, {}) ms", from, to)
+            }
+            (PlanBetween(from, to), Ru) => {
+                format!("отбор: временные метки в [{}, {}) мс", from, to)
+            }
+            (PlanZeroIds, En) => "transform: zero synthetic IDs".to_string(),
+            (PlanZeroIds, Ru) => "преобразование: обнуление системных ID".to_string(),
+            (PlanRoundTimestamps(ms), En) => {
+                format!("transform: round timestamps down to {} ms", ms)
+            }
+            (PlanRoundTimestamps(ms), Ru) => {
+                format!("преобразование: округление временных меток до {} мс", ms)
+            }
+            (PlanCanonicalize, En) => "transform: canonicalize records".to_string(),
+            (PlanCanonicalize, Ru) => {
+                "преобразование: приведение к инвариантам типа".to_string()
+            }
+            (PlanTrim, En) => "transform: trim descriptions".to_string(),
+            (PlanTrim, Ru) => "преобразование: удаление пробелов по краям описаний".to_string(),
+            (PlanCountByStatus, En) => "print record counts by status".to_string(),
+            (PlanCountByStatus, Ru) => "вывод количества записей по статусу".to_string(),
+            (PlanCompare(path, format), En) => {
+                format!("compare with '{}' (format {:?})", path.display(), format)
+            }
+            (PlanCompare(path, format), Ru) => {
+                format!("сравнение с '{}' (формат {:?})", path.display(), format)
+            }
+            (PlanWrite(target, format), En) => format!("write to {} as {:?}", target, format),
+            (PlanWrite(target, format), Ru) => {
+                format!("запись: {} (формат {:?})", target, format)
+            }
+            (PlanWriteTemplate(target), En) => format!("write to {} using the template", target),
+            (PlanWriteTemplate(target), Ru) => format!("запись: {} (по шаблону)", target),
+            (PlanLossy(fields), En) => format!("   lossy: not preserved: {}", fields),
+            (PlanLossy(fields), Ru) => format!("   с потерями: не сохраняются: {}", fields),
+            (SourceStdin, _) => "<stdin>".to_string(),
+            (SourceList(path), En) => format!("files listed in '{}'", path.display()),
+            (SourceList(path), Ru) => format!("файлы из списка '{}'", path.display()),
+            (TargetDir(dir), En) => format!("directory '{}'", dir.display()),
+            (TargetDir(dir), Ru) => format!("каталог '{}'", dir.display()),
+            (BatchSummary { converted, failed }, En) => {
+                format!("Converted files: {}, failed: {}", converted, failed)
+            }
+            (BatchSummary { converted, failed }, Ru) => {
+                format!("Сконвертировано файлов: {}, с ошибками: {}", converted, failed)
+            }
+            (Banner(name), _) => format!("=== {} ===", name),
+            (VerboseInputList(path), En) => format!("Input file list: {}", path.display()),
+            (VerboseInputList(path), Ru) => format!("Список входных файлов: {}", path.display()),
+            (VerboseInputDir(dir), En) => format!("Input directory: {}", dir.display()),
+            (VerboseInputDir(dir), Ru) => format!("Входной каталог: {}", dir.display()),
+            (VerboseInputFile(input), En) => format!("Input file: {}", input),
+            (VerboseInputFile(input), Ru) => format!("Входной файл: {}", input),
+            (VerboseInputFormat(format), En) => format!("Input format: {:?}", format),
+            (VerboseInputFormat(format), Ru) => format!("Входной формат: {:?}", format),
+            (VerboseOutputFormat(format), En) => format!("Output format: {:?}", format),
+            (VerboseOutputFormat(format), Ru) => format!("Выходной формат: {:?}", format),
+            (VerboseOutputDir(dir), En) => format!("Output directory: {}", dir.display()),
+            (VerboseOutputDir(dir), Ru) => format!("Выходной каталог: {}", dir.display()),
+            (VerboseOutputFile(output), En) => format!("Output file: {}", output),
+            (VerboseOutputFile(output), Ru) => format!("Выходной файл: {}", output),
+            (VerboseSkipValidation, En) => "Mode: skip business rule validation".to_string(),
+            (VerboseSkipValidation, Ru) => "Режим: пропуск проверки бизнес-правил".to_string(),
+            (VerboseAppendMode, En) => "Mode: append to the end of the output file".to_string(),
+            (VerboseAppendMode, Ru) => {
+                "Режим: дописывание в конец выходного файла".to_string()
+            }
+            (VerboseMaxRecords(max), En) => format!("Record limit: {}", max),
+            (VerboseMaxRecords(max), Ru) => format!("Ограничение количества записей: {}", max),
+            (VerboseMaxLineBytes(max), En) => format!("Line length limit: {} bytes", max),
+            (VerboseMaxLineBytes(max), Ru) => format!("Ограничение длины строки: {} байт", max),
+            (VerboseFirstTransaction(tx), En) => format!(
+                "First transaction: ID={}, Type={:?}, Amount={}, Status={:?}",
+                tx.tx_id, tx.tx_type, tx.amount, tx.status
+            ),
+            (VerboseFirstTransaction(tx), Ru) => format!(
+                "Первая транзакция: ID={}, Тип={:?}, Сумма={}, Статус={:?}",
+                tx.tx_id, tx.tx_type, tx.amount, tx.status
+            ),
+            (VerboseLastTransaction(tx), En) => format!(
+                "Last transaction: ID={}, Type={:?}, Amount={}",
+                tx.tx_id, tx.tx_type, tx.amount
+            ),
+            (VerboseLastTransaction(tx), Ru) => format!(
+                "Последняя транзакция: ID={}, Тип={:?}, Сумма={}",
+                tx.tx_id, tx.tx_type, tx.amount
+            ),
+            (VerboseDone, En) => "Conversion completed successfully!".to_string(),
+            (VerboseDone, Ru) => "Конвертация завершена успешно!".to_string(),
+            (VerboseReadFrom(source, count), En) => {
+                format!("Transactions read from {}: {}", source, count)
+            }
+            (VerboseReadFrom(source, count), Ru) => {
+                format!("Прочитано транзакций из {}: {}", source, count)
+            }
+            (VerboseRead(count), En) => format!("Transactions read: {}", count),
+            (VerboseRead(count), Ru) => format!("Прочитано транзакций: {}", count),
+            (VerboseValidated(count), En) => format!("Transactions validated: {}", count),
+            (VerboseValidated(count), Ru) => format!("Проверено транзакций: {}", count),
+            (VerboseReencoding, En) => {
+                "Parsed with warnings: the data is re-encoded".to_string()
+            }
+            (VerboseReencoding, Ru) => {
+                "Разбор с предупреждениями: данные перекодируются".to_string()
+            }
+            (VerboseCopying, En) => {
+                "Formats match: copying the data without re-encoding".to_string()
+            }
+            (VerboseCopying, Ru) => {
+                "Форматы совпадают: данные копируются без перекодирования".to_string()
+            }
+            (
+                VerboseWindow {
+                    selected,
+                    total,
+                    from,
+                    to,
+                },
+                En,
+            ) => format!(
+                "Selected {} of {} transactions in the window [{}, {}) ms",
+                selected, total, from, to
+            ),
+            (
+                VerboseWindow {
+                    selected,
+                    total,
+                    from,
+                    to,
+                },
+                Ru,
+            ) => format!(
+                "Отобрано {} из {} транзакций в окне [{}, {}) мс",
+                selected, total, from, to
+            ),
+            (VerboseZeroIds, En) => "Synthetic user IDs zeroed by transaction type".to_string(),
+            (VerboseZeroIds, Ru) => {
+                "Системные ID пользователей обнулены по типу транзакции".to_string()
+            }
+            (VerboseRounded(ms), En) => format!("Timestamps rounded down to {} ms", ms),
+            (VerboseRounded(ms), Ru) => format!("Временные метки округлены до {} мс", ms),
+            (VerboseCanonicalized, En) => {
+                "Records canonicalized (system IDs and amount sign)".to_string()
+            }
+            (VerboseCanonicalized, Ru) => {
+                "Записи приведены к инвариантам типа (ID системы и знак суммы)".to_string()
+            }
+            (VerboseTrimmed, En) => "Leading and trailing description spaces removed".to_string(),
+            (VerboseTrimmed, Ru) => "Пробелы в начале и конце описаний удалены".to_string(),
+            (VerboseSplitPart(tx_type, count, path), En) => {
+                format!("{:?}: {} transactions -> {}", tx_type, count, path.display())
+            }
+            (VerboseSplitPart(tx_type, count, path), Ru) => {
+                format!("{:?}: {} транзакций -> {}", tx_type, count, path.display())
+            }
+            (VerboseToStdout, En) => "Output goes to standard output (stdout)".to_string(),
+            (VerboseToStdout, Ru) => {
+                "Вывод будет отправлен в стандартный вывод (stdout)".to_string()
+            }
+            (VerboseOutputHint, En) => "Use --output <file> to save to a file".to_string(),
+            (VerboseOutputHint, Ru) => {
+                "Используйте --output <файл> для сохранения в файл".to_string()
+            }
+            (VerboseAppendTo(path), En) => {
+                format!("Records will be appended to '{}'", path.display())
+            }
+            (VerboseAppendTo(path), Ru) => {
+                format!("Записи будут дописаны в конец файла '{}'", path.display())
+            }
+            (VerboseOverwrite(path), En) => {
+                format!("File '{}' will be overwritten", path.display())
+            }
+            (VerboseOverwrite(path), Ru) => format!("Файл '{}' будет перезаписан", path.display()),
+            (VerboseWriting(count, format), En) => {
+                format!("Writing {} transactions as {:?}...", count, format)
+            }
+            (VerboseWriting(count, format), Ru) => {
+                format!("Запись {} транзакций в формат {:?}...", count, format)
+            }
+            (VerboseTemplateLayout, En) => "Layout: template (one line per record)".to_string(),
+            (VerboseTemplateLayout, Ru) => "Формат: шаблон (одна строка на запись)".to_string(),
+            (VerboseCsvLayout, En) => "Layout: CSV (header + data)".to_string(),
+            (VerboseCsvLayout, Ru) => "Формат: CSV (заголовок + данные)".to_string(),
+            (VerboseTextLayout, En) => "Layout: Text (KEY: VALUE with comments)".to_string(),
+            (VerboseTextLayout, Ru) => "Формат: Text (KEY: VALUE с комментариями)".to_string(),
+            (VerboseBinaryLayout, En) => {
+                "Layout: Binary (YPBN magic number + binary data)".to_string()
+            }
+            (VerboseBinaryLayout, Ru) => {
+                "Формат: Binary (магическое число YPBN + бинарные данные)".to_string()
+            }
+            (VerboseRecordSize(size), En) => {
+                format!("Record size: ~{} bytes + description length", size)
+            }
+            (VerboseRecordSize(size), Ru) => {
+                format!("Размер одной записи: ~{} байт + размер описания", size)
+            }
+            (VerboseWritten(bytes, count), En) => {
+                format!("Wrote {} bytes, {} transactions", bytes, count)
+            }
+            (VerboseWritten(bytes, count), Ru) => {
+                format!("Записано {} байт, {} транзакций", bytes, count)
+            }
+            (VerboseHashMatches(sha256), En) => {
+                format!("SHA-256 of the written data matches: {}", sha256)
+            }
+            (VerboseHashMatches(sha256), Ru) => {
+                format!("SHA-256 записанных данных совпадает: {}", sha256)
+            }
+            (VerboseAuditAppended(path), En) => {
+                format!("Audit record appended to '{}'", path.display())
+            }
+            (VerboseAuditAppended(path), Ru) => {
+                format!("Запись аудита добавлена в '{}'", path.display())
+            }
+            (VerboseComparing, En) => "Comparing files:".to_string(),
+            (VerboseComparing, Ru) => "Сравниваем файлы:".to_string(),
+            (VerboseCompareFile(index, path, format), En) => {
+                format!("  File {}: {} (format: {:?})", index, path.display(), format)
+            }
+            (VerboseCompareFile(index, path, format), Ru) => {
+                format!("  Файл {}: {} (формат: {:?})", index, path.display(), format)
+            }
+            (VerboseIgnoredField(field), En) => {
+                format!("  Ignoring differences in field {}", field.name())
+            }
+            (VerboseIgnoredField(field), Ru) => {
+                format!("  Игнорируем различия в поле {}", field.name())
+            }
+            (VerboseFormatsDiffer, En) => {
+                "Formats differ, byte comparison skipped".to_string()
+            }
+            (VerboseFormatsDiffer, Ru) => {
+                "Форматы различаются, побайтовое сравнение пропущено".to_string()
+            }
+            (VerboseSorted, En) => "Transactions sorted by TX_ID before comparing".to_string(),
+            (VerboseSorted, Ru) => {
+                "Транзакции отсортированы по TX_ID перед сравнением".to_string()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_lang_is_russian() {
+        assert_eq!(Lang::default(), Lang::Ru);
+        assert_eq!(
+            Message::BothEmpty.render(Lang::default()),
+            "Оба файла пусты."
+        );
+    }
+
+    #[test]
+    fn test_render_english() {
+        let msg = Message::FilesIdentical(Path::new("a.csv"), Path::new("b.txt"));
+        assert_eq!(
+            msg.render(Lang::En),
+            "Files 'a.csv' and 'b.txt' are identical."
+        );
+        assert_eq!(
+            Message::WriteFailed(Format::Bin, "disk full".to_string()).render(Lang::En),
+            "Failed to write binary format: disk full"
+        );
+        assert_eq!(
+            Message::VerboseWritten(120, 2).render(Lang::En),
+            "Wrote 120 bytes, 2 transactions"
+        );
+    }
+}
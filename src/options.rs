@@ -0,0 +1,307 @@
+use crate::{CsvFieldMap, ParserError, ValidationRules};
+use std::io::{self, Read};
+
+/// Настройки парсинга, общие для всех форматов
+///
+/// Значение по умолчанию сохраняет поведение `parse_records`.
+///
+/// # Пример
+/// ```
+/// use parser_lib::{CsvParser, ParseOptions};
+/// use std::io::Cursor;
+///
+/// let mut options = ParseOptions::default();
+/// options.rules.max_description_len = Some(4);
+///
+/// let csv = "TX_ID,TX_TYPE,FROM_USER_ID,TO_USER_ID,AMOUNT,TIMESTAMP,STATUS,DESCRIPTION\n\
+///            1001,DEPOSIT,0,501,50000,1672531200000,SUCCESS,\"Too long\"";
+/// assert!(CsvParser::parse_records_with(Cursor::new(csv), &options).is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseOptions {
+    /// Правила валидации, применяемые к каждой записи
+    pub rules: ValidationRules,
+
+    /// Максимальное количество записей во входных данных
+    ///
+    /// Защищает от чрезмерно больших или зацикленных входных файлов.
+    /// `None` означает отсутствие ограничения.
+    pub max_records: Option<usize>,
+
+    /// Максимальная длина строки в байтах для CSV и текстового форматов
+    ///
+    /// Защищает от патологических строк без разделителей, которые иначе
+    /// читаются в память целиком. Перевод строки в длину не входит.
+    /// `None` означает отсутствие ограничения.
+    pub max_line_bytes: Option<usize>,
+
+    /// Принимать пустой вход (ноль байт) как пустой список записей
+    ///
+    /// По умолчанию включено. Если выключено, пустой вход любого формата
+    /// приводит к ошибке "empty input", например когда CSV без заголовка
+    /// означает обрыв выгрузки.
+    pub allow_empty: bool,
+
+    /// Настройки, специфичные для CSV формата
+    pub csv: CsvParseOptions,
+
+    /// Настройки, специфичные для текстового формата
+    pub text: TextParseOptions,
+
+    /// Настройки, специфичные для бинарного формата
+    pub binary: BinaryParseOptions,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        ParseOptions {
+            rules: ValidationRules::default(),
+            max_records: None,
+            max_line_bytes: None,
+            allow_empty: true,
+            csv: CsvParseOptions::default(),
+            text: TextParseOptions::default(),
+            binary: BinaryParseOptions::default(),
+        }
+    }
+}
+
+/// Настройки парсинга CSV формата
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CsvParseOptions {
+    /// Пропускать строки, совпадающие с заголовком, вместо ошибки
+    ///
+    /// Повторный заголовок появляется при конкатенации нескольких
+    /// CSV выгрузок. Каждая пропущенная строка порождает предупреждение.
+    pub tolerate_repeated_header: bool,
+
+    /// Символ, которым начинаются строки комментариев перед заголовком
+    ///
+    /// Некоторые экспортёры добавляют в начало файла строки вида
+    /// `# generated ...`. Если задан, такие строки пропускаются до первой
+    /// строки заголовка. `None` сохраняет строгую проверку заголовка.
+    pub comment_char: Option<char>,
+
+    /// Принимать суммы со знаком (отрицательные для списаний)
+    ///
+    /// По умолчанию AMOUNT должен быть положительным, а направление
+    /// задаётся TX_TYPE. Если включено, проверка положительности
+    /// пропускается и знак сохраняется.
+    pub signed_amounts: bool,
+
+    /// Удалять разделители групп разрядов из AMOUNT перед разбором
+    ///
+    /// Отредактированные вручную файлы содержат суммы вида `"50,000"`,
+    /// `50_000` или `50 000`. Если включено, запятые, подчёркивания и
+    /// пробелы удаляются только в столбце AMOUNT; описания не меняются.
+    pub lenient_amount: bool,
+
+    /// Снимать префикс `'`, добавленный `CsvWriteOptions::sanitize_formulas`
+    ///
+    /// Удаляется ровно один начальный `'` описания.
+    pub sanitize_formulas: bool,
+
+    /// Отображение столбцов и значений нестандартного CSV на схему
+    ///
+    /// Если задано, заголовок может содержать столбцы под другими именами
+    /// и в любом порядке. `None` сохраняет строгую проверку заголовка.
+    pub field_map: Option<CsvFieldMap>,
+}
+
+/// Настройки парсинга текстового формата
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TextParseOptions {
+    /// Обработка повторяющегося ключа внутри одной записи
+    ///
+    /// Некоторые выгрузки повторяют поле, подразумевая, что действует
+    /// последнее значение. По умолчанию повтор считается ошибкой.
+    pub duplicate_policy: DuplicatePolicy,
+}
+
+/// Политика обработки повторяющихся ключей текстовой записи
+///
+/// # Пример
+/// ```
+/// use parser_lib::{DuplicatePolicy, ParseOptions, TextParser};
+/// use std::io::Cursor;
+///
+/// let mut options = ParseOptions::default();
+/// options.text.duplicate_policy = DuplicatePolicy::LastWins;
+///
+/// let text = "TX_ID: 1001\nTX_TYPE: DEPOSIT\nFROM_USER_ID: 0\nTO_USER_ID: 501\n\
+///             AMOUNT: 100\nAMOUNT: 200\nTIMESTAMP: 1672531200000\nSTATUS: SUCCESS\n\
+///             DESCRIPTION: \"Test\"";
+/// let records = TextParser::parse_records_with(Cursor::new(text), &options).unwrap();
+/// assert_eq!(records[0].amount, 200);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DuplicatePolicy {
+    /// Повторяющийся ключ приводит к `ParserError::DuplicateField`
+    #[default]
+    Error,
+    /// Сохраняется первое значение, повторы пропускаются
+    FirstWins,
+    /// Каждый повтор заменяет предыдущее значение
+    LastWins,
+}
+
+/// Настройки парсинга бинарного формата
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BinaryParseOptions {
+    /// Ожидаемая преамбула файла перед потоком записей
+    ///
+    /// Некоторые системы добавляют идентифицирующий тег (например,
+    /// `YPBANK01`) в начало файла. Если задана, преамбула должна
+    /// присутствовать и совпадать побайтно.
+    pub preamble: Option<Vec<u8>>,
+
+    /// Байт-разделитель, ожидаемый после каждой записи
+    ///
+    /// Соответствует `BinaryWriteOptions::record_separator`. Разделитель
+    /// после последней записи необязателен, любой другой байт на месте
+    /// разделителя считается ошибкой.
+    pub record_separator: Option<u8>,
+}
+
+impl ParseOptions {
+    /// Проверяет, можно ли добавить ещё одну запись
+    ///
+    /// # Аргументы
+    /// * `parsed` - Количество уже распарсенных записей
+    pub(crate) fn check_record_limit(&self, parsed: usize) -> Result<(), ParserError> {
+        match self.max_records {
+            Some(max) if parsed >= max => Err(ParserError::Parse(format!(
+                "record limit exceeded: more than {} records",
+                max
+            ))),
+            _ => Ok(()),
+        }
+    }
+
+    /// Читает построчный вход целиком, соблюдая `max_line_bytes` и `allow_empty`
+    ///
+    /// # Аргументы
+    /// * `reader` - Читаемый поток с текстовыми данными
+    pub(crate) fn read_text<R: Read>(&self, reader: R) -> Result<String, ParserError> {
+        let content = self.read_bounded(reader)?;
+        if content.is_empty() && !self.allow_empty {
+            return Err(Self::empty_input());
+        }
+        Ok(content)
+    }
+
+    /// Проверяет, что бинарный поток не пуст, если `allow_empty` выключен
+    ///
+    /// Прочитанный для проверки байт возвращается в начало потока.
+    ///
+    /// # Аргументы
+    /// * `reader` - Читаемый поток
+    pub(crate) fn check_nonempty<R: Read>(
+        &self,
+        mut reader: R,
+    ) -> Result<io::Chain<io::Cursor<Vec<u8>>, R>, ParserError> {
+        let mut head = Vec::new();
+        if !self.allow_empty {
+            let mut byte = [0u8; 1];
+            loop {
+                match reader.read(&mut byte) {
+                    Ok(0) => return Err(Self::empty_input()),
+                    Ok(_) => break,
+                    Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                    Err(e) => return Err(ParserError::Io(e)),
+                }
+            }
+            head.push(byte[0]);
+        }
+        Ok(io::Cursor::new(head).chain(reader))
+    }
+
+    fn empty_input() -> ParserError {
+        ParserError::Parse("empty input".to_string())
+    }
+
+    /// Читает вход целиком, прерываясь на строке длиннее `max_line_bytes`
+    ///
+    /// Чтение прерывается, как только текущая строка превышает предел,
+    /// не дожидаясь её конца. Концом строки считаются `\n` и `\r`.
+    fn read_bounded<R: Read>(&self, mut reader: R) -> Result<String, ParserError> {
+        let Some(max) = self.max_line_bytes else {
+            return io::read_to_string(reader).map_err(ParserError::Io);
+        };
+
+        let mut content = Vec::new();
+        let mut buffer = [0u8; 8192];
+        let mut line_len = 0;
+
+        loop {
+            let read = match reader.read(&mut buffer) {
+                Ok(0) => break,
+                Ok(read) => read,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(ParserError::Io(e)),
+            };
+
+            for &byte in &buffer[..read] {
+                if byte == b'\n' || byte == b'\r' {
+                    line_len = 0;
+                    continue;
+                }
+                line_len += 1;
+                if line_len > max {
+                    return Err(ParserError::Parse(format!(
+                        "line too long: more than {} bytes",
+                        max
+                    )));
+                }
+            }
+            content.extend_from_slice(&buffer[..read]);
+        }
+
+        String::from_utf8(content)
+            .map_err(|e| ParserError::Io(io::Error::new(io::ErrorKind::InvalidData, e)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_read_text_enforces_max_line_bytes() {
+        let options = ParseOptions {
+            max_line_bytes: Some(5),
+            ..Default::default()
+        };
+
+        let content = options.read_text(Cursor::new("12345\r\nabc\n")).unwrap();
+        assert_eq!(content, "12345\r\nabc\n");
+
+        let err = options.read_text(Cursor::new("ok\n123456")).unwrap_err();
+        assert!(err.to_string().contains("line too long"));
+
+        let unbounded = ParseOptions::default();
+        assert!(unbounded.read_text(Cursor::new("x".repeat(10_000))).is_ok());
+    }
+
+    #[test]
+    fn test_check_nonempty_preserves_stream() {
+        let options = ParseOptions {
+            allow_empty: false,
+            ..Default::default()
+        };
+
+        let mut content = Vec::new();
+        let mut reader = options.check_nonempty(Cursor::new(b"YPBN")).unwrap();
+        reader.read_to_end(&mut content).unwrap();
+        assert_eq!(content, b"YPBN");
+
+        let err = options.check_nonempty(Cursor::new(b"")).unwrap_err();
+        assert!(err.to_string().contains("empty input"));
+        assert!(
+            ParseOptions::default()
+                .check_nonempty(Cursor::new(b""))
+                .is_ok()
+        );
+    }
+}
@@ -0,0 +1,199 @@
+use crate::binary_format::MAGIC;
+use crate::{CsvParser, Format, ParserError, Quoting, TextParser, normalize_line_endings};
+use std::io::{BufRead, BufReader, Read};
+
+/// Сведения о заголовке файла, полученные без разбора записей
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormatProbe {
+    /// Формат, для которого выполнялась проверка
+    pub format: Format,
+
+    /// Столбцы заголовка CSV или ключи первой записи текстового формата
+    ///
+    /// Для бинарного формата и пустых входных данных список пуст.
+    pub columns: Vec<String>,
+
+    /// Размер первой записи бинарного формата из её заголовка
+    ///
+    /// Не включает магическое число и само поле размера.
+    /// `None` для остальных форматов и пустых входных данных.
+    pub first_record_size: Option<u32>,
+}
+
+/// Проверяет заголовок входных данных, не разбирая записи
+///
+/// Читает только начало потока: строку заголовка CSV, первый блок
+/// текстового формата или магическое число и размер первой бинарной
+/// записи. Подходит для быстрой проверки, что файл имеет ожидаемый
+/// формат и схему.
+///
+/// # Аргументы
+/// * `reader` - Читаемый поток, установленный на начало данных
+/// * `format` - Ожидаемый формат
+///
+/// # Возвращает
+/// * `Ok(FormatProbe)` - Сведения о заголовке
+/// * `Err(ParserError)` - Заголовок не соответствует формату или ошибка чтения
+///
+/// # Пример
+/// ```
+/// use parser_lib::{probe, Format};
+///
+/// let csv = "TX_ID,TX_TYPE,FROM_USER_ID,TO_USER_ID,AMOUNT,TIMESTAMP,STATUS,DESCRIPTION\n\
+///            not,parsed,at,all";
+/// let result = probe(csv.as_bytes(), Format::Csv).unwrap();
+/// assert_eq!(result.columns.len(), 8);
+/// ```
+pub fn probe<R: Read>(reader: R, format: Format) -> Result<FormatProbe, ParserError> {
+    let mut result = FormatProbe {
+        format,
+        columns: Vec::new(),
+        first_record_size: None,
+    };
+
+    match format {
+        Format::Csv => result.columns = probe_csv(BufReader::new(reader))?,
+        Format::Txt => result.columns = probe_txt(BufReader::new(reader))?,
+        Format::Bin => result.first_record_size = probe_bin(reader)?,
+    }
+
+    Ok(result)
+}
+
+/// Читает и проверяет строку заголовка CSV
+fn probe_csv<R: BufRead>(mut reader: R) -> Result<Vec<String>, ParserError> {
+    let mut line = String::new();
+    if reader.read_line(&mut line)? == 0 {
+        return Ok(Vec::new());
+    }
+
+    let line = normalize_line_endings(&line, Quoting::Doubled);
+    CsvParser::parse_header(line.trim_end_matches('\n'), 1, &mut Vec::new())
+}
+
+/// Собирает ключи первой записи текстового формата
+fn probe_txt<R: BufRead>(reader: R) -> Result<Vec<String>, ParserError> {
+    let mut keys = Vec::new();
+
+    for (index, line) in reader.lines().enumerate() {
+        let line = line?;
+        let trimmed = line.trim();
+
+        if trimmed.is_empty() {
+            if keys.is_empty() {
+                continue;
+            }
+            break;
+        }
+        if trimmed.starts_with('#') {
+            continue;
+        }
+
+        let (key, _) = TextParser::parse_key_value(trimmed, index + 1)?;
+        if keys.contains(&key) {
+            return Err(ParserError::DuplicateField {
+                field: key,
+                line: index + 1,
+            });
+        }
+        keys.push(key);
+    }
+
+    Ok(keys)
+}
+
+/// Читает магическое число и размер первой бинарной записи
+fn probe_bin<R: Read>(mut reader: R) -> Result<Option<u32>, ParserError> {
+    let mut header = [0u8; 8];
+    let mut filled = 0;
+    while filled < header.len() {
+        match reader.read(&mut header[filled..]) {
+            Ok(0) if filled == 0 => return Ok(None),
+            Ok(0) => return Err(ParserError::Parse("truncated record".to_string())),
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e.into()),
+        }
+    }
+
+    if header[..4] != MAGIC {
+        return Err(ParserError::Parse(format!(
+            "Invalid magic number: {:?}, expected {:?}",
+            &header[..4],
+            MAGIC
+        )));
+    }
+
+    Ok(Some(u32::from_be_bytes([
+        header[4], header[5], header[6], header[7],
+    ])))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BinaryParser, generate_sample};
+
+    #[test]
+    fn test_probe_csv_returns_columns() {
+        let csv = "TX_ID,TX_TYPE,FROM_USER_ID,TO_USER_ID,AMOUNT,TIMESTAMP,STATUS,DESCRIPTION,CURRENCY\r\n\
+                   1001,DEPOSIT,0,501,50000,1672531200000,SUCCESS,\"Test\",USD\r\n";
+
+        let result = probe(csv.as_bytes(), Format::Csv).unwrap();
+
+        assert_eq!(result.format, Format::Csv);
+        assert_eq!(result.columns.first().map(String::as_str), Some("TX_ID"));
+        assert_eq!(result.columns.last().map(String::as_str), Some("CURRENCY"));
+        assert_eq!(result.columns.len(), 9);
+        assert_eq!(result.first_record_size, None);
+    }
+
+    #[test]
+    fn test_probe_csv_rejects_wrong_header() {
+        let csv = "ID,TYPE\n1,DEPOSIT\n";
+
+        assert!(matches!(
+            probe(csv.as_bytes(), Format::Csv),
+            Err(ParserError::Parse(_))
+        ));
+    }
+
+    #[test]
+    fn test_probe_txt_returns_first_record_keys() {
+        let text = "# Record 1\nTX_ID: 1\nAMOUNT: 100\n\nTX_ID: 2\nSTATUS: SUCCESS\n";
+
+        let result = probe(text.as_bytes(), Format::Txt).unwrap();
+
+        assert_eq!(result.columns, vec!["TX_ID", "AMOUNT"]);
+    }
+
+    #[test]
+    fn test_probe_bin_returns_first_record_size() {
+        let transactions = generate_sample(3, 5);
+        let mut first = Vec::new();
+        BinaryParser::write_records(&transactions[..1], &mut first).unwrap();
+        let mut buffer = Vec::new();
+        BinaryParser::write_records(&transactions, &mut buffer).unwrap();
+
+        let result = probe(buffer.as_slice(), Format::Bin).unwrap();
+
+        assert_eq!(result.first_record_size, Some(first.len() as u32 - 8));
+        assert!(result.columns.is_empty());
+    }
+
+    #[test]
+    fn test_probe_bin_errors() {
+        assert!(matches!(
+            probe(&b"NOPE\x00\x00\x00\x10"[..], Format::Bin),
+            Err(ParserError::Parse(msg)) if msg.contains("Invalid magic")
+        ));
+        assert!(matches!(
+            probe(&b"YPBN\x00"[..], Format::Bin),
+            Err(ParserError::Parse(msg)) if msg == "truncated record"
+        ));
+        assert_eq!(
+            probe(&b""[..], Format::Bin).unwrap().first_record_size,
+            None
+        );
+    }
+}
@@ -0,0 +1,127 @@
+use crate::{Format, ParseOptions, ParserError, Transaction};
+use std::collections::HashMap;
+use std::io::{Read, Write};
+
+/// Формат транзакций, подключаемый к конвертеру во время выполнения
+///
+/// Встроенные форматы реализуют этот трейт через [`Format`]; сторонний
+/// крейт может реализовать его для собственного формата и
+/// зарегистрировать в [`FormatRegistry`].
+pub trait TransactionFormat {
+    /// Парсит все транзакции из потока
+    ///
+    /// # Аргументы
+    /// * `reader` - Читаемый поток
+    ///
+    /// # Возвращает
+    /// * `Ok(Vec<Transaction>)` - Вектор распарсенных транзакций
+    /// * `Err(ParserError)` - Ошибка парсинга или ввода-вывода
+    fn parse(&self, reader: &mut dyn Read) -> Result<Vec<Transaction>, ParserError>;
+
+    /// Парсит все транзакции, добавляя предупреждения о восстановимых проблемах
+    ///
+    /// Реализация по умолчанию вызывает `parse` и не формирует предупреждений.
+    ///
+    /// # Аргументы
+    /// * `reader` - Читаемый поток
+    /// * `warnings` - Список, в который добавляются предупреждения
+    ///
+    /// # Возвращает
+    /// * `Ok(Vec<Transaction>)` - Вектор распарсенных транзакций
+    /// * `Err(ParserError)` - Ошибка парсинга или ввода-вывода
+    fn parse_with_warnings(
+        &self,
+        reader: &mut dyn Read,
+        warnings: &mut Vec<String>,
+    ) -> Result<Vec<Transaction>, ParserError> {
+        let _ = warnings;
+        self.parse(reader)
+    }
+
+    /// Записывает транзакции в поток
+    ///
+    /// # Аргументы
+    /// * `records` - Срез транзакций для записи
+    /// * `writer` - Записываемый поток
+    ///
+    /// # Возвращает
+    /// * `Ok(())` - Успешная запись
+    /// * `Err(ParserError)` - Ошибка записи
+    fn write(&self, records: &[Transaction], writer: &mut dyn Write) -> Result<(), ParserError>;
+}
+
+impl TransactionFormat for Format {
+    fn parse(&self, reader: &mut dyn Read) -> Result<Vec<Transaction>, ParserError> {
+        self.parse_records(reader, &ParseOptions::default())
+    }
+
+    fn parse_with_warnings(
+        &self,
+        reader: &mut dyn Read,
+        warnings: &mut Vec<String>,
+    ) -> Result<Vec<Transaction>, ParserError> {
+        self.parse_records_with_warnings(reader, &ParseOptions::default(), warnings)
+    }
+
+    fn write(
+        &self,
+        records: &[Transaction],
+        mut writer: &mut dyn Write,
+    ) -> Result<(), ParserError> {
+        self.write_records(records, &mut writer)
+    }
+}
+
+/// Реестр форматов по имени
+///
+/// Значение по умолчанию содержит встроенные форматы под именами
+/// `csv`, `txt` и `bin`. Регистрация под существующим именем заменяет
+/// формат.
+///
+/// # Пример
+/// ```
+/// use parser_lib::{Format, FormatRegistry};
+///
+/// let mut registry = FormatRegistry::default();
+/// registry.register("ypbank-csv", Box::new(Format::Csv));
+///
+/// assert!(registry.get("ypbank-csv").is_some());
+/// assert!(registry.get("csv").is_some());
+/// assert!(registry.get("mt940").is_none());
+/// ```
+pub struct FormatRegistry {
+    formats: HashMap<String, Box<dyn TransactionFormat>>,
+}
+
+impl Default for FormatRegistry {
+    fn default() -> Self {
+        let mut registry = FormatRegistry {
+            formats: HashMap::new(),
+        };
+        for format in [Format::Csv, Format::Txt, Format::Bin] {
+            registry.register(format.extension(), Box::new(format));
+        }
+        registry
+    }
+}
+
+impl FormatRegistry {
+    /// Регистрирует формат под указанным именем
+    ///
+    /// # Аргументы
+    /// * `name` - Имя формата, используемое при конвертации
+    /// * `format` - Реализация формата
+    pub fn register(&mut self, name: impl Into<String>, format: Box<dyn TransactionFormat>) {
+        self.formats.insert(name.into(), format);
+    }
+
+    /// Возвращает формат по имени
+    pub fn get(&self, name: &str) -> Option<&dyn TransactionFormat> {
+        self.formats.get(name).map(|format| format.as_ref())
+    }
+
+    /// Возвращает формат по имени или ошибку для незарегистрированного имени
+    pub(crate) fn resolve(&self, name: &str) -> Result<&dyn TransactionFormat, ParserError> {
+        self.get(name).ok_or(ParserError::UnsupportedFormat)
+    }
+}
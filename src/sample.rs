@@ -0,0 +1,116 @@
+use crate::{Transaction, TransactionStatus, TransactionType};
+
+/// Начальная метка времени генерируемых транзакций (2023-01-01 00:00:00 UTC)
+const BASE_TIMESTAMP: u64 = 1672531200000;
+
+/// Генерирует детерминированный набор корректных транзакций
+///
+/// Одинаковые `n` и `seed` всегда дают одинаковый результат, поэтому
+/// функция подходит для тестовых фикстур и демонстрационных данных.
+/// Записи содержат смесь типов и статусов и соблюдают бизнес-правила:
+/// - у депозитов `from_user_id` равен 0
+/// - у выводов `to_user_id` равен 0
+/// - у переводов оба ID ненулевые и различны
+/// - сумма всегда положительна
+///
+/// # Аргументы
+/// * `n` - Количество транзакций
+/// * `seed` - Начальное значение генератора
+///
+/// # Пример
+/// ```
+/// use parser_lib::generate_sample;
+///
+/// let first = generate_sample(10, 42);
+/// let second = generate_sample(10, 42);
+///
+/// assert_eq!(first.len(), 10);
+/// assert_eq!(first, second);
+/// ```
+pub fn generate_sample(n: usize, seed: u64) -> Vec<Transaction> {
+    let mut rng = SplitMix64::new(seed);
+    let mut timestamp = BASE_TIMESTAMP;
+
+    (0..n)
+        .map(|i| {
+            let tx_type = match rng.next() % 3 {
+                0 => TransactionType::Deposit,
+                1 => TransactionType::Transfer,
+                _ => TransactionType::Withdrawal,
+            };
+
+            let user = 500 + rng.next() % 100;
+            let (from_user_id, to_user_id) = match tx_type {
+                TransactionType::Deposit => (0, user),
+                TransactionType::Withdrawal => (user, 0),
+                TransactionType::Transfer => (user, 600 + rng.next() % 100),
+            };
+
+            let status = match rng.next() % 3 {
+                0 => TransactionStatus::Success,
+                1 => TransactionStatus::Failure,
+                _ => TransactionStatus::Pending,
+            };
+
+            timestamp += 1000 + rng.next() % 3_600_000;
+
+            Transaction {
+                tx_id: 1000 + i as u64,
+                tx_type,
+                from_user_id,
+                to_user_id,
+                amount: 1 + (rng.next() % 1_000_000) as i64,
+                timestamp,
+                status,
+                description: format!("Sample {:?} #{}, batch {}", tx_type, i + 1, seed),
+                currency: None,
+            }
+        })
+        .collect()
+}
+
+/// Простой детерминированный генератор псевдослучайных чисел SplitMix64
+struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    fn new(seed: u64) -> Self {
+        SplitMix64 { state: seed }
+    }
+
+    fn next(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_sample_is_deterministic() {
+        assert_eq!(generate_sample(20, 7), generate_sample(20, 7));
+        assert_ne!(generate_sample(20, 7), generate_sample(20, 8));
+    }
+
+    #[test]
+    fn test_generate_sample_respects_id_rules() {
+        for tx in generate_sample(200, 1) {
+            assert!(tx.amount > 0);
+            match tx.tx_type {
+                TransactionType::Deposit => assert_eq!(tx.from_user_id, 0),
+                TransactionType::Withdrawal => assert_eq!(tx.to_user_id, 0),
+                TransactionType::Transfer => {
+                    assert_ne!(tx.from_user_id, 0);
+                    assert_ne!(tx.to_user_id, 0);
+                    assert_ne!(tx.from_user_id, tx.to_user_id);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,189 @@
+use crate::{Field, TransactionStatus, TransactionType};
+use serde_json::{Value, json};
+
+const TX_TYPES: [TransactionType; 3] = [
+    TransactionType::Deposit,
+    TransactionType::Transfer,
+    TransactionType::Withdrawal,
+];
+
+const STATUSES: [TransactionStatus; 4] = [
+    TransactionStatus::Success,
+    TransactionStatus::Failure,
+    TransactionStatus::Pending,
+    TransactionStatus::Reversed,
+];
+
+/// Возвращает JSON Schema записи транзакции
+///
+/// Схема описывает поля в нотации заголовков форматов (`TX_ID`, `AMOUNT`, ...),
+/// их типы, допустимые значения перечислений и нулевые или ненулевые ID
+/// участников в зависимости от типа. Свойства строятся по [`Field::ALL`],
+/// поэтому новое поле без описания в схеме не скомпилируется.
+///
+/// Диапазон AMOUNT совпадает с принимаемым бинарным форматом и режимом
+/// `signed_amounts`: любое `i64`. Положительность суммы, которую требуют
+/// CSV и текстовый формат по умолчанию, указана в описании поля.
+///
+/// # Пример
+/// ```
+/// use parser_lib::transaction_json_schema;
+///
+/// let schema: serde_json::Value = serde_json::from_str(&transaction_json_schema()).unwrap();
+/// assert_eq!(schema["properties"]["STATUS"]["enum"][0], "SUCCESS");
+/// ```
+pub fn transaction_json_schema() -> String {
+    let properties: serde_json::Map<String, Value> = Field::ALL
+        .iter()
+        .map(|field| (field.name().to_string(), field_schema(*field)))
+        .collect();
+    let required: Vec<&str> = Field::ALL
+        .iter()
+        .filter(|field| **field != Field::Currency)
+        .map(|field| field.name())
+        .collect();
+
+    let schema = json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": "YPBank transaction",
+        "description": "Transaction record shared by the CSV, text and binary formats",
+        "type": "object",
+        "properties": properties,
+        "required": required,
+        "additionalProperties": false,
+        "allOf": [
+            actor_rule(TransactionType::Deposit, json!({ "const": 0 }), json!({ "minimum": 1 })),
+            actor_rule(TransactionType::Transfer, json!({ "minimum": 1 }), json!({ "minimum": 1 })),
+            actor_rule(TransactionType::Withdrawal, json!({ "minimum": 1 }), json!({ "const": 0 })),
+        ],
+    });
+
+    serde_json::to_string_pretty(&schema).expect("schema is always serializable")
+}
+
+/// Схема отдельного поля
+fn field_schema(field: Field) -> Value {
+    match field {
+        Field::TxId => json!({
+            "type": "integer",
+            "minimum": 0,
+            "maximum": u64::MAX,
+            "description": "Unique transaction identifier",
+        }),
+        Field::TxType => json!({
+            "type": "string",
+            "enum": TX_TYPES.map(type_name),
+        }),
+        Field::FromUserId => json!({
+            "type": "integer",
+            "minimum": 0,
+            "maximum": u64::MAX,
+            "description": "Sender; 0 means the system",
+        }),
+        Field::ToUserId => json!({
+            "type": "integer",
+            "minimum": 0,
+            "maximum": u64::MAX,
+            "description": "Recipient; 0 means the system",
+        }),
+        Field::Amount => json!({
+            "type": "integer",
+            "minimum": i64::MIN,
+            "maximum": i64::MAX,
+            "description": "Amount in minor currency units; the CSV and text formats \
+                            require a positive amount unless signed amounts are enabled",
+        }),
+        Field::Timestamp => json!({
+            "type": "integer",
+            "minimum": 0,
+            "maximum": u64::MAX,
+            "description": "Milliseconds since the Unix epoch",
+        }),
+        Field::Status => json!({
+            "type": "string",
+            "enum": STATUSES.map(status_name),
+        }),
+        Field::Description => json!({
+            "type": "string",
+        }),
+        Field::Currency => json!({
+            "type": "string",
+            "pattern": "^[A-Z]{3}$",
+            "description": "Optional ISO 4217 currency code",
+        }),
+    }
+}
+
+/// Условие на ID участников для одного типа транзакции
+fn actor_rule(tx_type: TransactionType, from: Value, to: Value) -> Value {
+    json!({
+        "if": {
+            "properties": { Field::TxType.name(): { "const": type_name(tx_type) } },
+            "required": [Field::TxType.name()],
+        },
+        "then": {
+            "properties": {
+                Field::FromUserId.name(): from,
+                Field::ToUserId.name(): to,
+            },
+        },
+    })
+}
+
+fn type_name(tx_type: TransactionType) -> &'static str {
+    match tx_type {
+        TransactionType::Deposit => "DEPOSIT",
+        TransactionType::Transfer => "TRANSFER",
+        TransactionType::Withdrawal => "WITHDRAWAL",
+    }
+}
+
+fn status_name(status: TransactionStatus) -> &'static str {
+    match status {
+        TransactionStatus::Success => "SUCCESS",
+        TransactionStatus::Failure => "FAILURE",
+        TransactionStatus::Pending => "PENDING",
+        TransactionStatus::Reversed => "REVERSED",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_schema_is_valid_json_with_all_fields() {
+        let schema: Value = serde_json::from_str(&transaction_json_schema()).unwrap();
+
+        let properties = schema["properties"].as_object().unwrap();
+        for field in Field::ALL {
+            assert!(properties.contains_key(field.name()), "{}", field.name());
+        }
+
+        let required = schema["required"].as_array().unwrap();
+        assert_eq!(required.len(), 8);
+        assert!(!required.contains(&json!("CURRENCY")));
+    }
+
+    #[test]
+    fn test_schema_business_rules() {
+        let schema: Value = serde_json::from_str(&transaction_json_schema()).unwrap();
+
+        // Бинарный формат и signed_amounts принимают любую сумму
+        let amount = &schema["properties"]["AMOUNT"];
+        assert_eq!(amount["minimum"], i64::MIN);
+        assert_eq!(amount["maximum"], i64::MAX);
+        assert!(amount.get("exclusiveMinimum").is_none());
+        assert!(!schema["description"].as_str().unwrap().contains("differ"));
+        assert_eq!(
+            schema["properties"]["TX_TYPE"]["enum"],
+            json!(["DEPOSIT", "TRANSFER", "WITHDRAWAL"])
+        );
+
+        let deposit = &schema["allOf"][0];
+        assert_eq!(deposit["if"]["properties"]["TX_TYPE"]["const"], "DEPOSIT");
+        assert_eq!(deposit["then"]["properties"]["FROM_USER_ID"]["const"], 0);
+        let withdrawal = &schema["allOf"][2];
+        assert_eq!(withdrawal["then"]["properties"]["TO_USER_ID"]["const"], 0);
+    }
+}
@@ -0,0 +1,250 @@
+use crate::{Field, LineEnding, ParserError, TextParser, Transaction};
+use chrono::{DateTime, SecondsFormat};
+use std::io::Write;
+
+/// Часть разобранного шаблона
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    /// Текст, выводимый как есть
+    Literal(String),
+    /// Значение поля транзакции
+    Field(Field),
+    /// Сумма в основных единицах валюты (`{amount_decimal}`)
+    AmountDecimal,
+    /// Время в формате RFC 3339 (`{ts_iso}`)
+    TimestampIso,
+}
+
+/// Шаблон построчного вывода транзакций
+///
+/// Подстановки задаются именами полей в нижнем регистре (`{tx_id}`,
+/// `{tx_type}`, `{from_user_id}`, `{to_user_id}`, `{amount}`, `{timestamp}`,
+/// `{status}`, `{description}`, `{currency}`), а также `{amount_decimal}`
+/// (сумма в основных единицах, две цифры после точки) и `{ts_iso}`
+/// (время UTC в формате RFC 3339). `{{` и `}}` выводят фигурные скобки.
+///
+/// # Пример
+/// ```
+/// use parser_lib::{OutputTemplate, Transaction, TransactionStatus, TransactionType};
+///
+/// let template = OutputTemplate::parse("{tx_id}|{amount_decimal}|{status}").unwrap();
+/// let tx = Transaction {
+///     tx_id: 1001,
+///     tx_type: TransactionType::Deposit,
+///     from_user_id: 0,
+///     to_user_id: 501,
+///     amount: 50000,
+///     timestamp: 1672531200000,
+///     status: TransactionStatus::Success,
+///     description: "Test".to_string(),
+///     currency: None,
+/// };
+/// assert_eq!(template.render(&tx), "1001|500.00|SUCCESS");
+/// assert!(OutputTemplate::parse("{fee}").is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutputTemplate {
+    segments: Vec<Segment>,
+}
+
+impl OutputTemplate {
+    /// Разбирает шаблон и проверяет имена подстановок
+    ///
+    /// # Аргументы
+    /// * `template` - Текст шаблона
+    ///
+    /// # Возвращает
+    /// * `Ok(OutputTemplate)` - Разобранный шаблон
+    /// * `Err(ParserError::Parse)` - Неизвестная подстановка или непарная скобка
+    pub fn parse(template: &str) -> Result<Self, ParserError> {
+        let mut segments = Vec::new();
+        let mut literal = String::new();
+        let mut chars = template.chars();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.as_str().starts_with('{') => {
+                    chars.next();
+                    literal.push('{');
+                }
+                '}' if chars.as_str().starts_with('}') => {
+                    chars.next();
+                    literal.push('}');
+                }
+                '{' => {
+                    let rest = chars.as_str();
+                    let end = rest.find('}').ok_or_else(|| {
+                        ParserError::Parse("Unclosed '{' in output template".to_string())
+                    })?;
+                    let name = &rest[..end];
+                    chars = rest[end + 1..].chars();
+
+                    if !literal.is_empty() {
+                        segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                    }
+                    segments.push(Self::placeholder(name)?);
+                }
+                '}' => {
+                    return Err(ParserError::Parse(
+                        "Unmatched '}' in output template".to_string(),
+                    ));
+                }
+                c => literal.push(c),
+            }
+        }
+
+        if !literal.is_empty() {
+            segments.push(Segment::Literal(literal));
+        }
+        Ok(OutputTemplate { segments })
+    }
+
+    fn placeholder(name: &str) -> Result<Segment, ParserError> {
+        match name {
+            "amount_decimal" => return Ok(Segment::AmountDecimal),
+            "ts_iso" => return Ok(Segment::TimestampIso),
+            _ => {}
+        }
+        Field::ALL
+            .into_iter()
+            .find(|field| field.name().to_lowercase() == name)
+            .map(Segment::Field)
+            .ok_or_else(|| {
+                ParserError::Parse(format!(
+                    "Unknown placeholder '{{{}}}' in output template",
+                    name
+                ))
+            })
+    }
+
+    /// Подставляет поля транзакции в шаблон
+    ///
+    /// Отсутствующая валюта выводится пустой строкой, время вне
+    /// диапазона `{ts_iso}` выводится в миллисекундах.
+    pub fn render(&self, record: &Transaction) -> String {
+        let mut line = String::new();
+        for segment in &self.segments {
+            match segment {
+                Segment::Literal(text) => line.push_str(text),
+                Segment::Field(field) => line.push_str(&Self::field_value(record, *field)),
+                Segment::AmountDecimal => {
+                    let sign = if record.amount < 0 { "-" } else { "" };
+                    let amount = record.amount.unsigned_abs();
+                    line.push_str(&format!("{}{}.{:02}", sign, amount / 100, amount % 100));
+                }
+                Segment::TimestampIso => {
+                    let iso = i64::try_from(record.timestamp)
+                        .ok()
+                        .and_then(DateTime::from_timestamp_millis)
+                        .map(|ts| ts.to_rfc3339_opts(SecondsFormat::Millis, true));
+                    line.push_str(&iso.unwrap_or_else(|| record.timestamp.to_string()));
+                }
+            }
+        }
+        line
+    }
+
+    /// Записывает транзакции по одной строке на запись без заголовка
+    ///
+    /// # Аргументы
+    /// * `records` - Срез транзакций для записи
+    /// * `writer` - Записываемый поток
+    /// * `line_ending` - Окончание каждой строки
+    ///
+    /// # Возвращает
+    /// * `Ok(())` - Успешная запись
+    /// * `Err(ParserError::Io)` - Ошибка записи
+    pub fn write_records<W: Write>(
+        &self,
+        records: &[Transaction],
+        writer: &mut W,
+        line_ending: LineEnding,
+    ) -> Result<(), ParserError> {
+        for record in records {
+            write!(writer, "{}{}", self.render(record), line_ending.as_str())
+                .map_err(ParserError::Io)?;
+        }
+        Ok(())
+    }
+
+    fn field_value(record: &Transaction, field: Field) -> String {
+        match field {
+            Field::TxId => record.tx_id.to_string(),
+            Field::TxType => TextParser::tx_type_to_str(record.tx_type).to_string(),
+            Field::FromUserId => record.from_user_id.to_string(),
+            Field::ToUserId => record.to_user_id.to_string(),
+            Field::Amount => record.amount.to_string(),
+            Field::Timestamp => record.timestamp.to_string(),
+            Field::Status => TextParser::status_to_str(record.status).to_string(),
+            Field::Description => record.description.clone(),
+            Field::Currency => record.currency.clone().unwrap_or_default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{TransactionStatus, TransactionType};
+
+    fn sample(amount: i64) -> Transaction {
+        Transaction {
+            tx_id: 1001,
+            tx_type: TransactionType::Withdrawal,
+            from_user_id: 501,
+            to_user_id: 0,
+            amount,
+            timestamp: 1672531200000,
+            status: TransactionStatus::Pending,
+            description: "Cash".to_string(),
+            currency: Some("EUR".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_render_all_placeholders() {
+        let template = OutputTemplate::parse(
+            "{tx_id};{tx_type};{from_user_id};{to_user_id};{amount};{timestamp};\
+             {status};{description};{currency};{amount_decimal};{ts_iso};{{x}}",
+        )
+        .unwrap();
+
+        assert_eq!(
+            template.render(&sample(12345)),
+            "1001;WITHDRAWAL;501;0;12345;1672531200000;PENDING;Cash;EUR;123.45;\
+             2023-01-01T00:00:00.000Z;{x}"
+        );
+    }
+
+    #[test]
+    fn test_amount_decimal_sign_and_padding() {
+        let template = OutputTemplate::parse("{amount_decimal}").unwrap();
+        assert_eq!(template.render(&sample(5)), "0.05");
+        assert_eq!(template.render(&sample(-105)), "-1.05");
+        assert_eq!(template.render(&sample(i64::MIN)), "-92233720368547758.08");
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_templates() {
+        for template in ["{fee}", "{TX_ID}", "{tx_id", "tx_id}", "{}"] {
+            assert!(
+                matches!(OutputTemplate::parse(template), Err(ParserError::Parse(_))),
+                "{}",
+                template
+            );
+        }
+    }
+
+    #[test]
+    fn test_write_records_one_line_per_record() {
+        let template = OutputTemplate::parse("{tx_id}|{amount}|{status}").unwrap();
+        let mut buffer = Vec::new();
+        template
+            .write_records(&[sample(1), sample(2)], &mut buffer, LineEnding::Crlf)
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(buffer).unwrap(),
+            "1001|1|PENDING\r\n1001|2|PENDING\r\n"
+        );
+    }
+}
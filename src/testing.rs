@@ -0,0 +1,87 @@
+use crate::{Format, ParseOptions, Transaction};
+
+/// Проверяет цикл запись-чтение транзакций в заданном формате
+///
+/// Записывает транзакции в буфер, читает их обратно и сравнивает каждую
+/// пару предикатом `eq`. Для форматов без потерь достаточно `|a, b| a == b`;
+/// для форматов с потерями (см. [`Format::lossy_fields`]) предикат
+/// сравнивает только сохраняемые поля.
+///
+/// # Аргументы
+/// * `format` - Проверяемый формат
+/// * `records` - Исходные транзакции
+/// * `eq` - Предикат равенства исходной и прочитанной транзакции
+///
+/// # Паника
+/// Если запись или чтение завершились ошибкой, изменилось количество
+/// записей или предикат вернул `false`.
+///
+/// # Пример
+/// ```
+/// use parser_lib::{assert_roundtrip, generate_sample, Format};
+///
+/// let transactions = generate_sample(10, 7);
+/// assert_roundtrip(Format::Csv, &transactions, |a, b| a == b);
+/// assert_roundtrip(Format::Bin, &transactions, |a, b| {
+///     a.tx_id == b.tx_id && a.amount == b.amount && a.description.trim() == b.description
+/// });
+/// ```
+pub fn assert_roundtrip<F>(format: Format, records: &[Transaction], eq: F)
+where
+    F: Fn(&Transaction, &Transaction) -> bool,
+{
+    let mut buffer = Vec::new();
+    if let Err(e) = format.write_records(records, &mut buffer) {
+        panic!("{:?}: write failed: {}", format, e);
+    }
+
+    let parsed = match format.parse_records(buffer.as_slice(), &ParseOptions::default()) {
+        Ok(parsed) => parsed,
+        Err(e) => panic!("{:?}: reading back failed: {}", format, e),
+    };
+
+    assert_eq!(
+        parsed.len(),
+        records.len(),
+        "{:?}: record count changed after roundtrip",
+        format
+    );
+
+    for (index, (original, parsed)) in records.iter().zip(&parsed).enumerate() {
+        assert!(
+            eq(original, parsed),
+            "{:?}: record {} changed after roundtrip\n  original: {:?}\n  parsed:   {:?}",
+            format,
+            index,
+            original,
+            parsed
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate_sample;
+
+    #[test]
+    fn test_roundtrip_lossy_format_with_predicate() {
+        let mut transactions = generate_sample(3, 1);
+        transactions[0].description = "  padded  ".to_string();
+
+        assert_roundtrip(Format::Bin, &transactions, |a, b| {
+            Transaction {
+                description: a.description.trim().to_string(),
+                ..a.clone()
+            } == *b
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "record 0 changed after roundtrip")]
+    fn test_roundtrip_reports_changed_record() {
+        let transactions = generate_sample(1, 1);
+
+        assert_roundtrip(Format::Txt, &transactions, |a, b| a.amount != b.amount);
+    }
+}
@@ -0,0 +1,413 @@
+use crate::{Transaction, TransactionStatus, TransactionType};
+
+/// Обнуляет "системные" ID пользователей в соответствии с типом транзакции
+///
+/// Некоторые источники данных подставляют вместо системного участника
+/// искусственные идентификаторы. Функция приводит записи к каноническим
+/// инвариантам перед записью:
+/// - для депозитов `from_user_id` становится 0
+/// - для выводов `to_user_id` становится 0
+/// - переводы не изменяются
+///
+/// # Аргументы
+/// * `transactions` - Транзакции для нормализации
+///
+/// # Пример
+/// ```
+/// use parser_lib::{zero_synthetic_ids, Transaction, TransactionStatus, TransactionType};
+///
+/// let mut transactions = vec![Transaction {
+///     tx_id: 1,
+///     tx_type: TransactionType::Deposit,
+///     from_user_id: 1000,
+///     to_user_id: 501,
+///     amount: 100,
+///     timestamp: 0,
+///     status: TransactionStatus::Success,
+///     description: String::new(),
+///     currency: None,
+/// }];
+///
+/// zero_synthetic_ids(&mut transactions);
+/// assert_eq!(transactions[0].from_user_id, 0);
+/// ```
+pub fn zero_synthetic_ids(transactions: &mut [Transaction]) {
+    for transaction in transactions {
+        match transaction.tx_type {
+            TransactionType::Deposit => transaction.from_user_id = 0,
+            TransactionType::Withdrawal => transaction.to_user_id = 0,
+            TransactionType::Transfer => {}
+        }
+    }
+}
+
+/// Применяет функцию к каждой транзакции
+///
+/// # Аргументы
+/// * `transactions` - Транзакции для изменения
+/// * `f` - Функция, изменяющая транзакцию на месте
+///
+/// # Пример
+/// ```
+/// use parser_lib::{map_transactions, Transaction, TransactionStatus, TransactionType};
+///
+/// let mut transactions = vec![Transaction {
+///     tx_id: 1,
+///     tx_type: TransactionType::Deposit,
+///     from_user_id: 0,
+///     to_user_id: 501,
+///     amount: 100,
+///     timestamp: 0,
+///     status: TransactionStatus::Success,
+///     description: "test".to_string(),
+///     currency: None,
+/// }];
+///
+/// map_transactions(&mut transactions, |tx| tx.description = tx.description.to_uppercase());
+/// assert_eq!(transactions[0].description, "TEST");
+/// ```
+pub fn map_transactions<F: FnMut(&mut Transaction)>(transactions: &mut [Transaction], f: F) {
+    transactions.iter_mut().for_each(f);
+}
+
+/// Отбирает транзакции заданного типа, сохраняя их порядок
+///
+/// # Аргументы
+/// * `transactions` - Исходные транзакции
+/// * `tx_type` - Тип транзакций, которые нужно оставить
+///
+/// # Возвращает
+/// Новый вектор с копиями подходящих транзакций
+///
+/// # Пример
+/// ```
+/// use parser_lib::{filter_by_type, generate_sample, TransactionType};
+///
+/// let transactions = generate_sample(20, 1);
+/// let deposits = filter_by_type(&transactions, TransactionType::Deposit);
+/// assert!(deposits.iter().all(|tx| tx.tx_type == TransactionType::Deposit));
+/// ```
+pub fn filter_by_type(transactions: &[Transaction], tx_type: TransactionType) -> Vec<Transaction> {
+    transactions
+        .iter()
+        .filter(|tx| tx.tx_type == tx_type)
+        .cloned()
+        .collect()
+}
+
+/// Отбирает транзакции с временной меткой в полуинтервале `[from_ms, to_ms)`
+///
+/// Нижняя граница включается, верхняя — нет, поэтому соседние окна
+/// `[a, b)` и `[b, c)` не пересекаются. При `from_ms >= to_ms` результат пуст.
+///
+/// # Аргументы
+/// * `transactions` - Исходные транзакции
+/// * `from_ms` - Начало окна в миллисекундах Unix (включительно)
+/// * `to_ms` - Конец окна в миллисекундах Unix (не включительно)
+///
+/// # Возвращает
+/// Ссылки на подходящие транзакции в исходном порядке
+///
+/// # Пример
+/// ```
+/// use parser_lib::{generate_sample, select_time_window};
+///
+/// let transactions = generate_sample(10, 1);
+/// let first = transactions[0].timestamp;
+/// let window = select_time_window(&transactions, first, first + 1);
+/// assert!(window.iter().all(|tx| tx.timestamp == first));
+/// ```
+pub fn select_time_window(
+    transactions: &[Transaction],
+    from_ms: u64,
+    to_ms: u64,
+) -> Vec<&Transaction> {
+    transactions
+        .iter()
+        .filter(|tx| (from_ms..to_ms).contains(&tx.timestamp))
+        .collect()
+}
+
+/// Подсчитывает транзакции по статусам
+///
+/// # Аргументы
+/// * `transactions` - Транзакции для подсчёта
+///
+/// # Возвращает
+/// Пары (статус, количество) в порядке `Success`, `Failure`, `Pending`, `Reversed`;
+/// статусы без транзакций присутствуют с нулевым количеством
+///
+/// # Пример
+/// ```
+/// use parser_lib::{count_by_status, generate_sample};
+///
+/// let transactions = generate_sample(20, 1);
+/// let total: usize = count_by_status(&transactions).iter().map(|(_, n)| n).sum();
+/// assert_eq!(total, 20);
+/// ```
+pub fn count_by_status(transactions: &[Transaction]) -> [(TransactionStatus, usize); 4] {
+    let mut counts = [
+        (TransactionStatus::Success, 0),
+        (TransactionStatus::Failure, 0),
+        (TransactionStatus::Pending, 0),
+        (TransactionStatus::Reversed, 0),
+    ];
+    for transaction in transactions {
+        for (status, count) in counts.iter_mut() {
+            if *status == transaction.status {
+                *count += 1;
+            }
+        }
+    }
+    counts
+}
+
+/// Удаляет пробельные символы в начале и конце описаний
+///
+/// Форматы сохраняют описание как есть; обрезка выполняется только
+/// явным вызовом этой функции, одинаково для любого формата.
+///
+/// # Аргументы
+/// * `transactions` - Транзакции для изменения
+///
+/// # Пример
+/// ```
+/// use parser_lib::{trim_descriptions, generate_sample};
+///
+/// let mut transactions = generate_sample(1, 1);
+/// transactions[0].description = "  x  ".to_string();
+///
+/// trim_descriptions(&mut transactions);
+/// assert_eq!(transactions[0].description, "x");
+/// ```
+pub fn trim_descriptions(transactions: &mut [Transaction]) {
+    for transaction in transactions {
+        let trimmed = transaction.description.trim();
+        if trimmed.len() != transaction.description.len() {
+            transaction.description = trimmed.to_string();
+        }
+    }
+}
+
+/// Округляет временные метки вниз до кратного заданной гранулярности
+///
+/// Убирает субсекундный "дрожащий" разброс перед дедупликацией и сравнением.
+/// Гранулярность `86_400_000` группирует транзакции по дням, `0` ничего не меняет.
+///
+/// # Аргументы
+/// * `transactions` - Транзакции для изменения
+/// * `granularity_ms` - Шаг округления в миллисекундах
+///
+/// # Пример
+/// ```
+/// use parser_lib::{round_timestamps, Transaction, TransactionStatus, TransactionType};
+///
+/// let mut transactions = vec![Transaction {
+///     tx_id: 1,
+///     tx_type: TransactionType::Deposit,
+///     from_user_id: 0,
+///     to_user_id: 501,
+///     amount: 100,
+///     timestamp: 1672531200999,
+///     status: TransactionStatus::Success,
+///     description: String::new(),
+///     currency: None,
+/// }];
+///
+/// round_timestamps(&mut transactions, 1000);
+/// assert_eq!(transactions[0].timestamp, 1672531200000);
+/// ```
+pub fn round_timestamps(transactions: &mut [Transaction], granularity_ms: u64) {
+    if granularity_ms == 0 {
+        return;
+    }
+    for transaction in transactions {
+        transaction.timestamp -= transaction.timestamp % granularity_ms;
+    }
+}
+
+/// Стабильно сортирует транзакции по `tx_id`
+///
+/// Записи с одинаковым ID сохраняют исходный взаимный порядок, поэтому
+/// после сортировки двух переупорядоченных, но равных наборов их можно
+/// сравнивать попарно.
+///
+/// # Аргументы
+/// * `transactions` - Транзакции для сортировки
+///
+/// # Пример
+/// ```
+/// use parser_lib::{generate_sample, sort_by_id};
+///
+/// let mut transactions = generate_sample(5, 1);
+/// transactions.reverse();
+/// sort_by_id(&mut transactions);
+/// assert!(transactions.windows(2).all(|pair| pair[0].tx_id <= pair[1].tx_id));
+/// ```
+pub fn sort_by_id(transactions: &mut [Transaction]) {
+    transactions.sort_by_key(|tx| tx.tx_id);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_transaction(tx_type: TransactionType, from: u64, to: u64) -> Transaction {
+        Transaction {
+            tx_id: 1001,
+            tx_type,
+            from_user_id: from,
+            to_user_id: to,
+            amount: 50000,
+            timestamp: 1672531200000,
+            status: TransactionStatus::Success,
+            description: "Test".to_string(),
+            currency: None,
+        }
+    }
+
+    #[test]
+    fn test_select_time_window_boundaries() {
+        let transactions: Vec<Transaction> = [999, 1000, 1500, 1999, 2000]
+            .into_iter()
+            .map(|timestamp| Transaction {
+                timestamp,
+                ..create_test_transaction(TransactionType::Deposit, 0, 501)
+            })
+            .collect();
+
+        let selected: Vec<u64> = select_time_window(&transactions, 1000, 2000)
+            .iter()
+            .map(|tx| tx.timestamp)
+            .collect();
+        assert_eq!(selected, vec![1000, 1500, 1999]);
+
+        assert!(select_time_window(&transactions, 2000, 2000).is_empty());
+        assert!(select_time_window(&transactions, 2000, 1000).is_empty());
+        assert_eq!(select_time_window(&transactions, 0, u64::MAX).len(), 5);
+    }
+
+    #[test]
+    fn test_zero_synthetic_ids_deposit() {
+        let mut transactions = vec![create_test_transaction(TransactionType::Deposit, 1000, 501)];
+
+        zero_synthetic_ids(&mut transactions);
+
+        assert_eq!(transactions[0].from_user_id, 0);
+        assert_eq!(transactions[0].to_user_id, 501);
+    }
+
+    #[test]
+    fn test_zero_synthetic_ids_withdrawal_and_transfer() {
+        let mut transactions = vec![
+            create_test_transaction(TransactionType::Withdrawal, 501, 2000),
+            create_test_transaction(TransactionType::Transfer, 501, 502),
+        ];
+
+        zero_synthetic_ids(&mut transactions);
+
+        assert_eq!(transactions[0].from_user_id, 501);
+        assert_eq!(transactions[0].to_user_id, 0);
+        assert_eq!(transactions[1].from_user_id, 501);
+        assert_eq!(transactions[1].to_user_id, 502);
+    }
+
+    #[test]
+    fn test_filter_by_type_keeps_order() {
+        let mut transactions = vec![
+            create_test_transaction(TransactionType::Deposit, 0, 501),
+            create_test_transaction(TransactionType::Transfer, 501, 502),
+            create_test_transaction(TransactionType::Deposit, 0, 502),
+        ];
+        transactions[2].tx_id = 1003;
+
+        let deposits = filter_by_type(&transactions, TransactionType::Deposit);
+        assert_eq!(deposits.len(), 2);
+        assert_eq!(deposits[1].tx_id, 1003);
+        assert!(filter_by_type(&transactions, TransactionType::Withdrawal).is_empty());
+    }
+
+    #[test]
+    fn test_round_timestamps_to_day() {
+        const DAY_MS: u64 = 86_400_000;
+        let mut transactions = vec![
+            create_test_transaction(TransactionType::Deposit, 0, 501),
+            create_test_transaction(TransactionType::Deposit, 0, 501),
+            create_test_transaction(TransactionType::Deposit, 0, 501),
+        ];
+        transactions[0].timestamp = 1672531200000;
+        transactions[1].timestamp = 1672531200000 + DAY_MS - 1;
+        transactions[2].timestamp = 1672531200000 + DAY_MS + 12_345;
+
+        round_timestamps(&mut transactions, DAY_MS);
+
+        assert_eq!(transactions[0].timestamp, 1672531200000);
+        assert_eq!(transactions[1].timestamp, 1672531200000);
+        assert_eq!(transactions[2].timestamp, 1672531200000 + DAY_MS);
+    }
+
+    #[test]
+    fn test_round_timestamps_zero_is_noop() {
+        let mut transactions = vec![create_test_transaction(TransactionType::Deposit, 0, 501)];
+        transactions[0].timestamp = 1672531200123;
+
+        round_timestamps(&mut transactions, 0);
+
+        assert_eq!(transactions[0].timestamp, 1672531200123);
+    }
+
+    #[test]
+    fn test_sort_by_id_is_stable() {
+        let mut transactions = vec![
+            create_test_transaction(TransactionType::Deposit, 0, 501),
+            create_test_transaction(TransactionType::Deposit, 0, 502),
+            create_test_transaction(TransactionType::Deposit, 0, 503),
+        ];
+        transactions[0].tx_id = 3;
+        transactions[1].tx_id = 1;
+        transactions[2].tx_id = 3;
+
+        sort_by_id(&mut transactions);
+
+        let order: Vec<(u64, u64)> = transactions
+            .iter()
+            .map(|tx| (tx.tx_id, tx.to_user_id))
+            .collect();
+        assert_eq!(order, vec![(1, 502), (3, 501), (3, 503)]);
+    }
+
+    #[test]
+    fn test_count_by_status() {
+        let mut transactions = vec![
+            create_test_transaction(TransactionType::Deposit, 0, 501),
+            create_test_transaction(TransactionType::Deposit, 0, 502),
+            create_test_transaction(TransactionType::Transfer, 501, 502),
+        ];
+        transactions[1].status = TransactionStatus::Failure;
+
+        assert_eq!(
+            count_by_status(&transactions),
+            [
+                (TransactionStatus::Success, 2),
+                (TransactionStatus::Failure, 1),
+                (TransactionStatus::Pending, 0),
+                (TransactionStatus::Reversed, 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_trim_descriptions() {
+        let mut transactions = vec![
+            create_test_transaction(TransactionType::Deposit, 0, 501),
+            create_test_transaction(TransactionType::Deposit, 0, 502),
+        ];
+        transactions[0].description = "  x  ".to_string();
+        transactions[1].description = "\tinner  spaces\n".to_string();
+
+        trim_descriptions(&mut transactions);
+
+        assert_eq!(transactions[0].description, "x");
+        assert_eq!(transactions[1].description, "inner  spaces");
+    }
+}
@@ -1,10 +1,77 @@
+use crate::validation::{check_positive_amount, check_user_ids};
 use crate::{
-    ParseFromRead, ParserError, TextTransactions, Transaction, TransactionStatus, TransactionType,
-    WriteTo,
+    DuplicatePolicy, Field, Format, LineEnding, ParseOptions, ParserError, Quoting, Rejected,
+    Transaction, TransactionStatus, TransactionType, check_currency_code, normalize_line_endings,
+    parse_field,
 };
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
 use std::collections::HashMap;
 use std::io::{Read, Write};
 
+/// Ключ описания, закодированного в base64
+const DESCRIPTION_B64: &str = "DESCRIPTION_B64";
+
+/// Настройки записи текстового формата
+///
+/// Значение по умолчанию соответствует поведению `write_records`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextWriteOptions {
+    /// Порядок полей внутри записи
+    ///
+    /// Должен содержать каждое поле ровно один раз. Парсер не зависит
+    /// от порядка полей, поэтому чтение таких файлов не меняется.
+    pub field_order: Vec<Field>,
+
+    /// Записывать описание в base64 под ключом `DESCRIPTION_B64`
+    ///
+    /// Гарантирует точное восстановление любого описания, включая
+    /// переводы строк и управляющие символы. Парсер распознаёт оба ключа.
+    pub base64_description: bool,
+
+    /// Окончание строк записей и разделяющих их пустых строк
+    pub line_ending: LineEnding,
+
+    /// Завершать вывод комментарием `# Total: N records`
+    ///
+    /// Позволяет быстро сверить число записей. Парсер пропускает
+    /// комментарии, поэтому повторное чтение файла не меняется.
+    pub emit_footer: bool,
+}
+
+impl Default for TextWriteOptions {
+    fn default() -> Self {
+        TextWriteOptions {
+            field_order: Field::ALL.to_vec(),
+            base64_description: false,
+            line_ending: LineEnding::Lf,
+            emit_footer: false,
+        }
+    }
+}
+
+impl TextWriteOptions {
+    /// Проверяет, что порядок полей содержит каждое поле ровно один раз
+    ///
+    /// # Возвращает
+    /// * `Ok(())` - Порядок полей корректен
+    /// * `Err(ParserError::Validation)` - Поле пропущено или повторяется
+    pub fn validate(&self) -> Result<(), ParserError> {
+        for field in Field::ALL {
+            let count = self.field_order.iter().filter(|&&f| f == field).count();
+            if count != 1 {
+                return Err(ParserError::Validation(format!(
+                    "field_order must contain {} exactly once, found {} times",
+                    field.name(),
+                    count
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
 /// Парсер текстового (key-value) формата транзакций
 ///
 /// Текстовый формат имеет следующую структуру:
@@ -25,49 +92,279 @@ impl TextParser {
     /// * `Err(ParserError)` - Ошибка парсинга или ввода-вывода
     ///
     pub fn parse_records<R: Read>(reader: R) -> Result<Vec<Transaction>, ParserError> {
-        let content = std::io::read_to_string(reader).map_err(ParserError::Io)?;
+        Self::parse_records_with(reader, &ParseOptions::default())
+    }
+
+    /// Парсит текстовые записи транзакций с дополнительными настройками
+    ///
+    /// # Аргументы
+    /// * `reader` - Читаемый поток (например, файл или буфер)
+    /// * `options` - Настройки парсинга и правила валидации
+    ///
+    /// # Возвращает
+    /// * `Ok(Vec<Transaction>)` - Вектор распарсенных транзакций
+    /// * `Err(ParserError)` - Ошибка парсинга, валидации или ввода-вывода
+    pub fn parse_records_with<R: Read>(
+        reader: R,
+        options: &ParseOptions,
+    ) -> Result<Vec<Transaction>, ParserError> {
+        Self::parse_records_with_warnings(reader, options, &mut Vec::new())
+    }
+
+    /// Парсит текстовые записи, собирая предупреждения о восстановимых проблемах
+    ///
+    /// Предупреждение выдаётся для неизвестного ключа, который пропускается,
+    /// и для повторного ключа, разрешённого политикой `FirstWins` или `LastWins`.
+    ///
+    /// # Аргументы
+    /// * `reader` - Читаемый поток (например, файл или буфер)
+    /// * `options` - Настройки парсинга и правила валидации
+    /// * `warnings` - Список, в который добавляются предупреждения
+    ///
+    /// # Возвращает
+    /// * `Ok(Vec<Transaction>)` - Вектор распарсенных транзакций
+    /// * `Err(ParserError)` - Ошибка парсинга, валидации или ввода-вывода
+    pub fn parse_records_with_warnings<R: Read>(
+        reader: R,
+        options: &ParseOptions,
+        warnings: &mut Vec<String>,
+    ) -> Result<Vec<Transaction>, ParserError> {
+        Self::parse_blocks(reader, options, warnings, None)
+    }
+
+    /// Парсит записи текстового формата, собирая ошибки всех некорректных записей
+    ///
+    /// В отличие от `parse_records`, разбор не останавливается на первой
+    /// ошибке: для каждой записи сообщается не более одной ошибки, после
+    /// чего разбор продолжается со следующей записи.
+    ///
+    /// # Аргументы
+    /// * `reader` - Читаемый поток с текстовыми данными
+    ///
+    /// # Возвращает
+    /// * `Ok(Vec<Transaction>)` - Все записи корректны
+    /// * `Err(Vec<ParserError>)` - Ошибки всех некорректных записей в порядке следования
+    pub fn parse_collect_errors<R: Read>(reader: R) -> Result<Vec<Transaction>, Vec<ParserError>> {
+        let mut errors = Vec::new();
+        let result = Self::parse_blocks(
+            reader,
+            &ParseOptions::default(),
+            &mut Vec::new(),
+            Some(&mut errors),
+        );
+
+        let mut errors: Vec<ParserError> = errors.into_iter().map(|r| r.error).collect();
+        match result {
+            Ok(records) if errors.is_empty() => Ok(records),
+            Ok(_) => Err(errors),
+            Err(e) => {
+                errors.push(e);
+                Err(errors)
+            }
+        }
+    }
+
+    /// Разбирает блоки записей
+    ///
+    /// Предупреждения о восстановимых проблемах добавляются в `warnings`.
+    /// Если передан `errors`, ошибка записи добавляется в него вместе
+    /// с текстом блока записи, остаток записи пропускается и разбор
+    /// продолжается; иначе первая ошибка возвращается сразу.
+    pub(crate) fn parse_blocks<R: Read>(
+        reader: R,
+        options: &ParseOptions,
+        warnings: &mut Vec<String>,
+        mut errors: Option<&mut Vec<Rejected>>,
+    ) -> Result<Vec<Transaction>, ParserError> {
+        let content = options.read_text(reader)?;
+        let content = normalize_line_endings(&content, Quoting::Backslash);
 
         let mut records = Vec::new();
         let mut current_record: HashMap<String, String> = HashMap::new();
+        let mut field_lines: HashMap<String, usize> = HashMap::new();
+        let mut failure: Option<ParserError> = None;
+        let mut block = String::new();
         let mut line_number = 0;
+        let mut lines = content.lines();
+
+        loop {
+            let line = lines.next();
+            if let Some(line) = line {
+                line_number += 1;
+                if !line.trim().is_empty() {
+                    block.push_str(line);
+                    block.push('\n');
+                    if failure.is_none() {
+                        failure = Self::parse_line_into(
+                            line,
+                            line_number,
+                            &mut current_record,
+                            &mut field_lines,
+                            options,
+                            warnings,
+                        )
+                        .err();
+                    }
+                    continue;
+                }
+            }
 
-        for line in content.lines() {
-            line_number += 1;
-
-            let trimmed = line.trim();
-            if trimmed.is_empty() {
-                if !current_record.is_empty() {
-                    let record = Self::parse_record(&current_record, line_number)?;
-                    records.push(record);
-                    current_record.clear();
+            // Пустая строка или конец данных завершают блок записи
+            let result = match failure.take() {
+                Some(e) => Err(e),
+                None if current_record.is_empty() => Ok(()),
+                None => {
+                    options.check_record_limit(records.len())?;
+                    Self::parse_checked(&current_record, &field_lines, options, line_number)
+                        .map(|record| records.push(record))
+                }
+            };
+            current_record.clear();
+            field_lines.clear();
+            let raw = std::mem::take(&mut block);
+            if let Err(error) = result {
+                match errors.as_deref_mut() {
+                    Some(errors) => errors.push(Rejected { error, raw }),
+                    None => return Err(error),
                 }
-                continue;
             }
 
-            if trimmed.starts_with('#') {
-                continue;
+            if line.is_none() {
+                return Ok(records);
             }
+        }
+    }
 
-            match Self::parse_key_value(trimmed, line_number) {
-                Ok((key, value)) => {
-                    if current_record.contains_key(&key) {
-                        return Err(ParserError::Parse(format!(
-                            "Line {}: duplicate field '{}'",
-                            line_number, key
-                        )));
-                    }
-                    current_record.insert(key, value);
+    /// Добавляет пару "KEY: VALUE" строки в поля текущей записи
+    ///
+    /// Комментарии пропускаются; повторный ключ обрабатывается
+    /// по `options.text.duplicate_policy`. Неизвестный ключ и повторный
+    /// ключ, разрешённый политикой, добавляют предупреждение в `warnings`.
+    /// Номер строки сохранённого значения записывается в `field_lines`.
+    fn parse_line_into(
+        line: &str,
+        line_number: usize,
+        current_record: &mut HashMap<String, String>,
+        field_lines: &mut HashMap<String, usize>,
+        options: &ParseOptions,
+        warnings: &mut Vec<String>,
+    ) -> Result<(), ParserError> {
+        let trimmed = line.trim();
+        if trimmed.starts_with('#') {
+            return Ok(());
+        }
+
+        let (key, value) = Self::parse_key_value(trimmed, line_number)?;
+        if !Self::is_known_key(&key) {
+            warnings.push(format!(
+                "Line {}: ignored unknown field '{}'",
+                line_number, key
+            ));
+            return Ok(());
+        }
+        if current_record.contains_key(&key) {
+            match options.text.duplicate_policy {
+                DuplicatePolicy::Error => {
+                    return Err(ParserError::DuplicateField {
+                        field: key,
+                        line: line_number,
+                    });
+                }
+                DuplicatePolicy::FirstWins => {
+                    warnings.push(format!(
+                        "Line {}: ignored repeated field '{}', keeping the first value",
+                        line_number, key
+                    ));
+                    return Ok(());
+                }
+                DuplicatePolicy::LastWins => {
+                    warnings.push(format!(
+                        "Line {}: repeated field '{}' overrides the earlier value",
+                        line_number, key
+                    ));
                 }
-                Err(e) => return Err(e),
             }
         }
+        field_lines.insert(key.clone(), line_number);
+        current_record.insert(key, value);
+        Ok(())
+    }
+
+    /// Проверяет, что ключ относится к полям записи текстового формата
+    fn is_known_key(key: &str) -> bool {
+        key == DESCRIPTION_B64 || Field::ALL.iter().any(|field| field.name() == key)
+    }
+
+    /// Разбирает запись и проверяет её правилами валидации
+    ///
+    /// Нарушение правила сообщается с номером строки поля из `field_lines`.
+    fn parse_checked(
+        fields: &HashMap<String, String>,
+        field_lines: &HashMap<String, usize>,
+        options: &ParseOptions,
+        line_number: usize,
+    ) -> Result<Transaction, ParserError> {
+        let record = Self::parse_record(fields, line_number)?;
+        Self::check_rules(&record, field_lines, options, line_number)?;
+        Ok(record)
+    }
+
+    /// Парсит один блок "KEY: VALUE" в транзакцию
+    ///
+    /// Комментарии внутри блока пропускаются. Блок не должен содержать
+    /// пустых строк, разделяющих несколько записей.
+    ///
+    /// # Аргументы
+    /// * `block` - Текст одной записи
+    ///
+    /// # Возвращает
+    /// * `Ok(Transaction)` - Распарсенная транзакция
+    /// * `Err(ParserError)` - Ошибка парсинга или валидации
+    ///
+    /// # Пример
+    /// ```
+    /// use parser_lib::TextParser;
+    ///
+    /// let block = "TX_ID: 1001\nTX_TYPE: DEPOSIT\nFROM_USER_ID: 0\nTO_USER_ID: 501\n\
+    ///              AMOUNT: 50000\nTIMESTAMP: 1672531200000\nSTATUS: SUCCESS\n\
+    ///              DESCRIPTION: \"Test\"";
+    ///
+    /// let tx = TextParser::parse_one(block).unwrap();
+    /// assert_eq!(tx.tx_id, 1001);
+    /// ```
+    pub fn parse_one(block: &str) -> Result<Transaction, ParserError> {
+        Self::parse_one_with(block, &ParseOptions::default())
+    }
+
+    /// Парсит один блок "KEY: VALUE" в транзакцию с дополнительными настройками
+    ///
+    /// Блок разбирается тем же кодом, что и потоки в `parse_records_with`,
+    /// поэтому политика повторяющихся ключей и правила валидации
+    /// применяются одинаково.
+    ///
+    /// # Аргументы
+    /// * `block` - Текст одной записи
+    /// * `options` - Настройки парсинга и правила валидации
+    ///
+    /// # Возвращает
+    /// * `Ok(Transaction)` - Распарсенная транзакция
+    /// * `Err(ParserError)` - Ошибка парсинга или валидации
+    pub fn parse_one_with(block: &str, options: &ParseOptions) -> Result<Transaction, ParserError> {
+        let block = normalize_line_endings(block, Quoting::Backslash);
+        let block = block.trim();
 
-        if !current_record.is_empty() {
-            let record = Self::parse_record(&current_record, line_number)?;
-            records.push(record);
+        if let Some(index) = block.lines().position(|line| line.trim().is_empty()) {
+            return Err(ParserError::Parse(format!(
+                "Line {}: expected a single record, found a record separator",
+                index + 1
+            )));
         }
 
-        Ok(records)
+        let mut records = Self::parse_blocks(block.as_bytes(), options, &mut Vec::new(), None)?;
+        records.pop().ok_or(ParserError::MissingField {
+            field: Field::TxId,
+            line: Some(block.lines().count()),
+        })
     }
 
     /// Записывает транзакции в текстовый формат в записываемый поток
@@ -81,7 +378,7 @@ impl TextParser {
     /// * `Err(ParserError)` - Ошибка записи
     ///
     /// # Пример
-    /// ```
+    /// ```no_run
     /// use parser_lib::{TextParser, Transaction, TransactionType, TransactionStatus};
     /// use std::fs::File;
     /// use std::io::BufWriter;
@@ -96,6 +393,7 @@ impl TextParser {
     ///     timestamp: 1672531200000,
     ///     status: TransactionStatus::Success,
     ///     description: "Test".to_string(),
+    ///     currency: None,
     /// }];
     ///
     /// let file = File::create("output.txt")?;
@@ -108,35 +406,101 @@ impl TextParser {
         records: &[Transaction],
         writer: &mut W,
     ) -> Result<(), ParserError> {
+        Self::write_records_with(records, writer, &TextWriteOptions::default())
+    }
+
+    /// Записывает транзакции в текстовый формат с дополнительными настройками
+    ///
+    /// # Аргументы
+    /// * `records` - Список транзакций для записи
+    /// * `writer` - Записываемый поток (например, файл или буфер)
+    /// * `options` - Настройки записи
+    ///
+    /// # Возвращает
+    /// * `Ok(())` - Успешная запись
+    /// * `Err(ParserError)` - Ошибка записи или некорректные настройки
+    pub fn write_records_with<W: Write>(
+        records: &[Transaction],
+        writer: &mut W,
+        options: &TextWriteOptions,
+    ) -> Result<(), ParserError> {
+        options.validate()?;
+        let eol = options.line_ending.as_str();
+
         for (i, record) in records.iter().enumerate() {
             if i > 0 {
-                writeln!(writer).map_err(ParserError::Io)?;
+                write!(writer, "{}", eol).map_err(ParserError::Io)?;
             }
 
-            writeln!(writer, "# Record {} ({:?})", i + 1, record.tx_type)
+            write!(writer, "# Record {} ({:?}){}", i + 1, record.tx_type, eol)
                 .map_err(ParserError::Io)?;
 
-            writeln!(writer, "TX_ID: {}", record.tx_id).map_err(ParserError::Io)?;
-            writeln!(writer, "TX_TYPE: {}", Self::tx_type_to_str(record.tx_type))
-                .map_err(ParserError::Io)?;
-            writeln!(writer, "FROM_USER_ID: {}", record.from_user_id).map_err(ParserError::Io)?;
-            writeln!(writer, "TO_USER_ID: {}", record.to_user_id).map_err(ParserError::Io)?;
-            writeln!(writer, "AMOUNT: {}", record.amount).map_err(ParserError::Io)?;
-            writeln!(writer, "TIMESTAMP: {}", record.timestamp).map_err(ParserError::Io)?;
-            writeln!(writer, "STATUS: {}", Self::status_to_str(record.status))
-                .map_err(ParserError::Io)?;
-            writeln!(
-                writer,
-                "DESCRIPTION: \"{}\"",
-                Self::escape_description(&record.description)
-            )
-            .map_err(ParserError::Io)?;
+            for field in &options.field_order {
+                let value = match field {
+                    Field::TxId => record.tx_id.to_string(),
+                    Field::TxType => Self::tx_type_to_str(record.tx_type).to_string(),
+                    Field::FromUserId => record.from_user_id.to_string(),
+                    Field::ToUserId => record.to_user_id.to_string(),
+                    Field::Amount => record.amount.to_string(),
+                    Field::Timestamp => record.timestamp.to_string(),
+                    Field::Status => Self::status_to_str(record.status).to_string(),
+                    Field::Description if options.base64_description => {
+                        write!(
+                            writer,
+                            "{}: {}{}",
+                            DESCRIPTION_B64,
+                            BASE64.encode(&record.description),
+                            eol
+                        )
+                        .map_err(ParserError::Io)?;
+                        continue;
+                    }
+                    Field::Description => {
+                        format!("\"{}\"", Self::escape_description(&record.description))
+                    }
+                    Field::Currency => match &record.currency {
+                        Some(code) => code.clone(),
+                        None => continue,
+                    },
+                };
+
+                write!(writer, "{}: {}{}", field.name(), value, eol).map_err(ParserError::Io)?;
+            }
+        }
+
+        if options.emit_footer {
+            if !records.is_empty() {
+                write!(writer, "{}", eol).map_err(ParserError::Io)?;
+            }
+            write!(writer, "# Total: {} records{}", records.len(), eol).map_err(ParserError::Io)?;
         }
 
         Ok(())
     }
 
-    fn parse_key_value(line: &str, line_number: usize) -> Result<(String, String), ParserError> {
+    fn check_rules(
+        record: &Transaction,
+        field_lines: &HashMap<String, usize>,
+        options: &ParseOptions,
+        line_number: usize,
+    ) -> Result<(), ParserError> {
+        options.rules.check_fields(record).map_err(|(field, msg)| {
+            let line = field_lines
+                .get(field.name())
+                .or_else(|| match field {
+                    Field::Description => field_lines.get(DESCRIPTION_B64),
+                    _ => None,
+                })
+                .copied()
+                .unwrap_or(line_number);
+            ParserError::Validation(format!("Line {}: {}", line, msg))
+        })
+    }
+
+    pub(crate) fn parse_key_value(
+        line: &str,
+        line_number: usize,
+    ) -> Result<(String, String), ParserError> {
         let parts: Vec<&str> = line.splitn(2, ':').collect();
 
         if parts.len() != 2 {
@@ -164,35 +528,30 @@ impl TextParser {
         line_number: usize,
     ) -> Result<Transaction, ParserError> {
         let required_fields = [
-            "TX_ID",
-            "TX_TYPE",
-            "FROM_USER_ID",
-            "TO_USER_ID",
-            "AMOUNT",
-            "TIMESTAMP",
-            "STATUS",
-            "DESCRIPTION",
+            Field::TxId,
+            Field::TxType,
+            Field::FromUserId,
+            Field::ToUserId,
+            Field::Amount,
+            Field::Timestamp,
+            Field::Status,
         ];
 
-        for &field in &required_fields {
-            if !fields.contains_key(field) {
-                return Err(ParserError::Parse(format!(
-                    "Missing required field: {}",
-                    field
-                )));
-            }
+        for field in required_fields {
+            Self::get_field(fields, field, line_number)?;
         }
 
-        let tx_id = Self::parse_u64_field(fields, "TX_ID", line_number)?;
+        let tx_id = Self::parse_u64_field(fields, Field::TxId, line_number)?;
         let tx_type = Self::parse_tx_type(fields, line_number)?;
-        let from_user_id = Self::parse_u64_field(fields, "FROM_USER_ID", line_number)?;
-        let to_user_id = Self::parse_u64_field(fields, "TO_USER_ID", line_number)?;
-        let amount = Self::parse_i64_field(fields, "AMOUNT", line_number)?;
-        let timestamp = Self::parse_u64_field(fields, "TIMESTAMP", line_number)?;
+        let from_user_id = Self::parse_u64_field(fields, Field::FromUserId, line_number)?;
+        let to_user_id = Self::parse_u64_field(fields, Field::ToUserId, line_number)?;
+        let amount = Self::parse_i64_field(fields, Field::Amount, line_number)?;
+        let timestamp = Self::parse_u64_field(fields, Field::Timestamp, line_number)?;
         let status = Self::parse_status(fields, line_number)?;
         let description = Self::parse_description(fields, line_number)?;
+        let currency = Self::parse_currency(fields, line_number)?;
 
-        Self::validate_record(tx_type, from_user_id, to_user_id, amount, line_number)?;
+        check_user_ids(tx_type, from_user_id, to_user_id, Some(line_number))?;
 
         Ok(Transaction {
             tx_id,
@@ -203,50 +562,58 @@ impl TextParser {
             timestamp,
             status,
             description,
+            currency,
+        })
+    }
+
+    /// Разбирает необязательное поле `CURRENCY`
+    fn parse_currency(
+        fields: &HashMap<String, String>,
+        line_number: usize,
+    ) -> Result<Option<String>, ParserError> {
+        let Some(value) = fields.get("CURRENCY") else {
+            return Ok(None);
+        };
+
+        check_currency_code(value)
+            .map_err(|msg| ParserError::Parse(format!("Line {}: {}", line_number, msg)))?;
+
+        Ok(Some(value.clone()))
+    }
+
+    /// Возвращает значение обязательного поля записи
+    fn get_field(
+        fields: &HashMap<String, String>,
+        field: Field,
+        line_number: usize,
+    ) -> Result<&String, ParserError> {
+        fields.get(field.name()).ok_or(ParserError::MissingField {
+            field,
+            line: Some(line_number),
         })
     }
 
     fn parse_u64_field(
         fields: &HashMap<String, String>,
-        field_name: &str,
+        field: Field,
         line_number: usize,
     ) -> Result<u64, ParserError> {
-        let value = fields
-            .get(field_name)
-            .ok_or_else(|| ParserError::Parse(format!("Field {} not found", field_name)))?;
+        let value = Self::get_field(fields, field, line_number)?;
 
-        value.parse::<u64>().map_err(|e| {
-            ParserError::Parse(format!(
-                "Line {}: invalid {} '{}': {}",
-                line_number, field_name, value, e
-            ))
-        })
+        parse_field(value, field.name(), line_number)
     }
 
     fn parse_i64_field(
         fields: &HashMap<String, String>,
-        field_name: &str,
+        field: Field,
         line_number: usize,
     ) -> Result<i64, ParserError> {
-        let value = fields
-            .get(field_name)
-            .ok_or_else(|| ParserError::Parse(format!("Field {} not found", field_name)))?;
+        let value = Self::get_field(fields, field, line_number)?;
 
         let clean_value = value.split('#').next().unwrap_or(value).trim();
 
-        let amount = clean_value.parse::<i64>().map_err(|e| {
-            ParserError::Parse(format!(
-                "Line {}: invalid {} '{}': {}",
-                line_number, field_name, clean_value, e
-            ))
-        })?;
-
-        if amount <= 0 {
-            return Err(ParserError::Parse(format!(
-                "Line {}: {} must be positive, got {}",
-                line_number, field_name, amount
-            )));
-        }
+        let amount: i64 = parse_field(clean_value, field.name(), line_number)?;
+        check_positive_amount(amount, Format::Txt, Some(line_number))?;
 
         Ok(amount)
     }
@@ -255,18 +622,19 @@ impl TextParser {
         fields: &HashMap<String, String>,
         line_number: usize,
     ) -> Result<TransactionType, ParserError> {
-        let value = fields
-            .get("TX_TYPE")
-            .ok_or_else(|| ParserError::Parse("Field TX_TYPE not found".to_string()))?;
+        let value = Self::get_field(fields, Field::TxType, line_number)?;
 
         match value.to_uppercase().as_str() {
             "DEPOSIT" => Ok(TransactionType::Deposit),
             "TRANSFER" => Ok(TransactionType::Transfer),
             "WITHDRAWAL" => Ok(TransactionType::Withdrawal),
-            other => Err(ParserError::Parse(format!(
-                "Line {}: invalid TX_TYPE '{}', must be DEPOSIT, TRANSFER, or WITHDRAWAL",
-                line_number, other
-            ))),
+            other => Err(ParserError::InvalidEnum {
+                field: Field::TxType,
+                value: other.to_string(),
+                line: Some(line_number),
+                record: None,
+                offset: None,
+            }),
         }
     }
 
@@ -274,18 +642,20 @@ impl TextParser {
         fields: &HashMap<String, String>,
         line_number: usize,
     ) -> Result<TransactionStatus, ParserError> {
-        let value = fields
-            .get("STATUS")
-            .ok_or_else(|| ParserError::Parse("Field STATUS not found".to_string()))?;
+        let value = Self::get_field(fields, Field::Status, line_number)?;
 
         match value.to_uppercase().as_str() {
             "SUCCESS" => Ok(TransactionStatus::Success),
             "FAILURE" => Ok(TransactionStatus::Failure),
             "PENDING" => Ok(TransactionStatus::Pending),
-            other => Err(ParserError::Parse(format!(
-                "Line {}: invalid STATUS '{}', must be SUCCESS, FAILURE, or PENDING",
-                line_number, other
-            ))),
+            "REVERSED" => Ok(TransactionStatus::Reversed),
+            other => Err(ParserError::InvalidEnum {
+                field: Field::Status,
+                value: other.to_string(),
+                line: Some(line_number),
+                record: None,
+                offset: None,
+            }),
         }
     }
 
@@ -293,9 +663,17 @@ impl TextParser {
         fields: &HashMap<String, String>,
         line_number: usize,
     ) -> Result<String, ParserError> {
-        let value = fields
-            .get("DESCRIPTION")
-            .ok_or_else(|| ParserError::Parse("Field DESCRIPTION not found".to_string()))?;
+        if let Some(encoded) = fields.get(DESCRIPTION_B64) {
+            if fields.contains_key(Field::Description.name()) {
+                return Err(ParserError::Parse(format!(
+                    "Line {}: DESCRIPTION and {} cannot both be present",
+                    line_number, DESCRIPTION_B64
+                )));
+            }
+            return Self::decode_description(encoded, line_number);
+        }
+
+        let value = Self::get_field(fields, Field::Description, line_number)?;
 
         let trimmed = value.trim();
 
@@ -319,50 +697,24 @@ impl TextParser {
         Ok(unescaped)
     }
 
-    fn validate_record(
-        tx_type: TransactionType,
-        from_user_id: u64,
-        to_user_id: u64,
-        _amount: i64,
-        line_number: usize,
-    ) -> Result<(), ParserError> {
-        match tx_type {
-            TransactionType::Deposit => {
-                if from_user_id != 0 {
-                    return Err(ParserError::Parse(format!(
-                        "Line {}: DEPOSIT must have FROM_USER_ID = 0, got {}",
-                        line_number, from_user_id
-                    )));
-                }
-            }
-            TransactionType::Withdrawal => {
-                if to_user_id != 0 {
-                    return Err(ParserError::Parse(format!(
-                        "Line {}: WITHDRAWAL must have TO_USER_ID = 0, got {}",
-                        line_number, to_user_id
-                    )));
-                }
-            }
-            TransactionType::Transfer => {
-                if from_user_id == 0 {
-                    return Err(ParserError::Parse(format!(
-                        "Line {}: TRANSFER cannot have FROM_USER_ID = 0",
-                        line_number
-                    )));
-                }
-                if to_user_id == 0 {
-                    return Err(ParserError::Parse(format!(
-                        "Line {}: TRANSFER cannot have TO_USER_ID = 0",
-                        line_number
-                    )));
-                }
-            }
-        }
+    /// Декодирует значение `DESCRIPTION_B64` в строку UTF-8
+    fn decode_description(encoded: &str, line_number: usize) -> Result<String, ParserError> {
+        let bytes = BASE64.decode(encoded).map_err(|e| {
+            ParserError::Parse(format!(
+                "Line {}: invalid {} '{}': {}",
+                line_number, DESCRIPTION_B64, encoded, e
+            ))
+        })?;
 
-        Ok(())
+        String::from_utf8(bytes).map_err(|e| {
+            ParserError::Parse(format!(
+                "Line {}: {} is not valid UTF-8: {}",
+                line_number, DESCRIPTION_B64, e
+            ))
+        })
     }
 
-    fn tx_type_to_str(tx_type: TransactionType) -> &'static str {
+    pub(crate) fn tx_type_to_str(tx_type: TransactionType) -> &'static str {
         match tx_type {
             TransactionType::Deposit => "DEPOSIT",
             TransactionType::Transfer => "TRANSFER",
@@ -370,11 +722,12 @@ impl TextParser {
         }
     }
 
-    fn status_to_str(status: TransactionStatus) -> &'static str {
+    pub(crate) fn status_to_str(status: TransactionStatus) -> &'static str {
         match status {
             TransactionStatus::Success => "SUCCESS",
             TransactionStatus::Failure => "FAILURE",
             TransactionStatus::Pending => "PENDING",
+            TransactionStatus::Reversed => "REVERSED",
         }
     }
 
@@ -387,35 +740,286 @@ impl TextParser {
     }
 }
 
-// Реализуем трейт ParseFromRead для TextTransactions
-impl<R: Read> ParseFromRead<R> for TextTransactions {
-    fn parse(reader: &mut R) -> Result<Self, ParserError> {
-        let transactions = TextParser::parse_records(reader)?;
-        Ok(TextTransactions(transactions))
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_write_with_custom_field_order() {
+        let transaction = Transaction {
+            tx_id: 1001,
+            tx_type: TransactionType::Deposit,
+            from_user_id: 0,
+            to_user_id: 501,
+            amount: 50000,
+            timestamp: 1672531200000,
+            status: TransactionStatus::Success,
+            description: "Custom order".to_string(),
+            currency: None,
+        };
+
+        let mut field_order: Vec<Field> = Field::ALL[1..].to_vec();
+        field_order.push(Field::TxId);
+        let options = TextWriteOptions {
+            field_order,
+            ..Default::default()
+        };
+
+        let mut buffer = Vec::new();
+        TextParser::write_records_with(std::slice::from_ref(&transaction), &mut buffer, &options)
+            .unwrap();
+
+        let output = String::from_utf8(buffer).unwrap();
+        let keys: Vec<&str> = output
+            .lines()
+            .filter(|line| !line.starts_with('#'))
+            .filter_map(|line| line.split(':').next())
+            .collect();
+        assert_eq!(keys.first(), Some(&"TX_TYPE"));
+        assert_eq!(keys.last(), Some(&"TX_ID"));
+
+        let parsed = TextParser::parse_records(Cursor::new(&output)).unwrap();
+        assert_eq!(parsed, vec![transaction]);
     }
-}
 
-// Реализуем трейт WriteTo для TextTransactions
-impl<W: Write> WriteTo<W> for TextTransactions {
-    fn write(&self, writer: &mut W) -> Result<(), ParserError> {
-        TextParser::write_records(&self.0, writer)
+    #[test]
+    fn test_footer_counts_records_and_is_ignored_on_parse() {
+        let transactions: Vec<Transaction> = (1..=3)
+            .map(|tx_id| Transaction {
+                tx_id,
+                tx_type: TransactionType::Deposit,
+                from_user_id: 0,
+                to_user_id: 501,
+                amount: 50000,
+                timestamp: 1672531200000,
+                status: TransactionStatus::Success,
+                description: "Footer".to_string(),
+                currency: None,
+            })
+            .collect();
+        let options = TextWriteOptions {
+            emit_footer: true,
+            ..Default::default()
+        };
+
+        let mut buffer = Vec::new();
+        TextParser::write_records_with(&transactions, &mut buffer, &options).unwrap();
+
+        let output = String::from_utf8(buffer).unwrap();
+        assert_eq!(output.lines().last(), Some("# Total: 3 records"));
+
+        let parsed = TextParser::parse_records(Cursor::new(&output)).unwrap();
+        assert_eq!(parsed, transactions);
     }
-}
 
-// Реализуем трейт WriteTo для среза TextTransactions
-impl<W: Write> WriteTo<W> for [TextTransactions] {
-    fn write(&self, writer: &mut W) -> Result<(), ParserError> {
-        for transactions in self {
-            transactions.write(writer)?;
-        }
-        Ok(())
+    #[test]
+    fn test_write_rejects_invalid_field_order() {
+        let options = TextWriteOptions {
+            field_order: vec![Field::TxId, Field::TxId],
+            ..Default::default()
+        };
+
+        let result = TextParser::write_records_with(&[], &mut Vec::new(), &options);
+        assert!(matches!(result, Err(ParserError::Validation(_))));
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::io::Cursor;
+    #[test]
+    fn test_base64_description_roundtrip() {
+        let transaction = Transaction {
+            tx_id: 1001,
+            tx_type: TransactionType::Deposit,
+            from_user_id: 0,
+            to_user_id: 501,
+            amount: 50000,
+            timestamp: 1672531200000,
+            status: TransactionStatus::Success,
+            description: "First line\nSecond \"line\"\r\n\tend\\".to_string(),
+            currency: None,
+        };
+        let options = TextWriteOptions {
+            base64_description: true,
+            ..Default::default()
+        };
+
+        let mut buffer = Vec::new();
+        TextParser::write_records_with(std::slice::from_ref(&transaction), &mut buffer, &options)
+            .unwrap();
+
+        let output = String::from_utf8(buffer).unwrap();
+        assert!(output.contains("DESCRIPTION_B64: "));
+        assert!(!output.contains("DESCRIPTION: "));
+
+        let parsed = TextParser::parse_records(Cursor::new(&output)).unwrap();
+        assert_eq!(parsed, vec![transaction]);
+    }
+
+    #[test]
+    fn test_base64_description_errors() {
+        let base = "TX_ID: 1001\nTX_TYPE: DEPOSIT\nFROM_USER_ID: 0\nTO_USER_ID: 501\n\
+                    AMOUNT: 50000\nTIMESTAMP: 1672531200000\nSTATUS: SUCCESS\n";
+
+        let invalid = format!("{}DESCRIPTION_B64: not base64!", base);
+        assert!(matches!(
+            TextParser::parse_one(&invalid),
+            Err(ParserError::Parse(msg)) if msg.contains("invalid DESCRIPTION_B64")
+        ));
+
+        let both = format!("{}DESCRIPTION: \"Test\"\nDESCRIPTION_B64: VGVzdA==", base);
+        assert!(matches!(
+            TextParser::parse_one(&both),
+            Err(ParserError::Parse(msg)) if msg.contains("cannot both be present")
+        ));
+
+        assert!(matches!(
+            TextParser::parse_one(base),
+            Err(ParserError::MissingField {
+                field: Field::Description,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_parse_collect_errors_reports_every_bad_record() {
+        let text = r#"TX_ID: 1
+TX_TYPE: DEPOSIT
+FROM_USER_ID: 0
+TO_USER_ID: 501
+AMOUNT: 100
+TIMESTAMP: 1672531200000
+STATUS: SUCCESS
+DESCRIPTION: "Valid"
+
+TX_ID: 2
+TX_TYPE: REFUND
+FROM_USER_ID: 0
+TO_USER_ID: 501
+AMOUNT: 100
+TIMESTAMP: 1672531200000
+STATUS: SUCCESS
+DESCRIPTION: "Bad type"
+
+TX_ID: 3
+TX_ID: 3
+TX_TYPE: DEPOSIT
+DESCRIPTION: "Duplicate key"
+
+TX_ID: 4
+TX_TYPE: DEPOSIT
+FROM_USER_ID: 0
+TO_USER_ID: 501
+TIMESTAMP: 1672531200000
+STATUS: SUCCESS
+DESCRIPTION: "No amount""#;
+
+        let errors = TextParser::parse_collect_errors(Cursor::new(text)).unwrap_err();
+
+        assert_eq!(errors.len(), 3);
+        assert!(matches!(errors[0], ParserError::InvalidEnum { .. }));
+        assert!(matches!(
+            errors[1],
+            ParserError::DuplicateField { line: 20, .. }
+        ));
+        assert!(matches!(
+            errors[2],
+            ParserError::MissingField {
+                field: Field::Amount,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_parse_one_block() {
+        let block = r#"# Single record
+TX_ID: 1003
+TX_TYPE: WITHDRAWAL
+FROM_USER_ID: 502
+TO_USER_ID: 0
+AMOUNT: 1000
+TIMESTAMP: 1672538400000
+STATUS: PENDING
+DESCRIPTION: "ATM withdrawal"
+"#;
+
+        let tx = TextParser::parse_one(block).unwrap();
+
+        assert_eq!(tx.tx_id, 1003);
+        assert!(matches!(tx.tx_type, TransactionType::Withdrawal));
+        assert_eq!(tx.amount, 1000);
+        assert_eq!(tx.description, "ATM withdrawal");
+
+        let two_records = format!("{}\n{}", block, block);
+        assert!(TextParser::parse_one(&two_records).is_err());
+    }
+
+    #[test]
+    fn test_parse_one_agrees_with_parse_records_with() {
+        let block = "TX_ID: 1001\nTX_TYPE: DEPOSIT\nFROM_USER_ID: 0\nTO_USER_ID: 501\n\
+                     AMOUNT: 100\nAMOUNT: 200\nTIMESTAMP: 1672531200000\nSTATUS: SUCCESS\n\
+                     DESCRIPTION: \"Repeated amount\"";
+
+        assert!(matches!(
+            TextParser::parse_one(block),
+            Err(ParserError::DuplicateField { line: 6, .. })
+        ));
+
+        let mut options = ParseOptions::default();
+        options.text.duplicate_policy = DuplicatePolicy::LastWins;
+        let tx = TextParser::parse_one_with(block, &options).unwrap();
+        let records = TextParser::parse_records_with(Cursor::new(block), &options).unwrap();
+        assert_eq!(tx.amount, 200);
+        assert_eq!(records, vec![tx]);
+
+        options.rules.max_description_len = Some(4);
+        assert!(matches!(
+            TextParser::parse_one_with(block, &options),
+            Err(ParserError::Validation(_))
+        ));
+
+        assert!(matches!(
+            TextParser::parse_one("# only a comment"),
+            Err(ParserError::MissingField {
+                field: Field::TxId,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_embedded_cr_in_description_roundtrips() {
+        let transaction = Transaction {
+            tx_id: 1001,
+            tx_type: TransactionType::Deposit,
+            from_user_id: 0,
+            to_user_id: 501,
+            amount: 50000,
+            timestamp: 1672531200000,
+            status: TransactionStatus::Success,
+            description: "a\rb".to_string(),
+            currency: None,
+        };
+
+        let mut buffer = Vec::new();
+        TextParser::write_records(std::slice::from_ref(&transaction), &mut buffer).unwrap();
+
+        let parsed = TextParser::parse_records(Cursor::new(&buffer)).unwrap();
+        assert_eq!(parsed, vec![transaction]);
+    }
+
+    #[test]
+    fn test_parse_with_cr_line_endings() {
+        let text = "TX_ID: 1001\rTX_TYPE: DEPOSIT\rFROM_USER_ID: 0\rTO_USER_ID: 501\r\
+                    AMOUNT: 50000\rTIMESTAMP: 1672531200000\rSTATUS: SUCCESS\r\
+                    DESCRIPTION: \"Test\"\r";
+
+        let records = TextParser::parse_records(Cursor::new(text)).unwrap();
+
+        assert_eq!(records.len(), 1);
+        assert!(matches!(records[0].status, TransactionStatus::Success));
+        assert_eq!(records[0].description, "Test");
+    }
 
     #[test]
     fn test_parse_valid_text() {
@@ -514,6 +1118,49 @@ DESCRIPTION: "Test transfer"
         assert_eq!(transactions[1].tx_id, 1002);
     }
 
+    #[test]
+    fn test_parse_with_max_description_len() {
+        let text = "TX_ID: 1001\nTX_TYPE: DEPOSIT\nFROM_USER_ID: 0\nTO_USER_ID: 501\nAMOUNT: 50000\nTIMESTAMP: 1672531200000\nSTATUS: SUCCESS\nDESCRIPTION: \"Long description\"";
+        let mut options = ParseOptions::default();
+        options.rules.max_description_len = Some(4);
+
+        let result = TextParser::parse_records_with(Cursor::new(text), &options);
+        assert!(matches!(result, Err(ParserError::Validation(msg)) if msg.contains("too long")));
+
+        let result = TextParser::parse_records_with(Cursor::new(text), &ParseOptions::default());
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_rule_violation_reports_line_of_field() {
+        let text = "TX_ID: 1001\nTX_TYPE: DEPOSIT\nFROM_USER_ID: 0\nTO_USER_ID: 501\n\
+                    AMOUNT: 50000\nTIMESTAMP: 1672531200000\nSTATUS: SUCCESS\n\
+                    DESCRIPTION: \"Ok\"\n\n\
+                    TX_ID: 1002\nDESCRIPTION: \"Long description\"\nTX_TYPE: DEPOSIT\n\
+                    FROM_USER_ID: 0\nTO_USER_ID: 501\nAMOUNT: 50000\n\
+                    TIMESTAMP: 1672531200000\nSTATUS: SUCCESS\n";
+        let mut options = ParseOptions::default();
+        options.rules.max_description_len = Some(4);
+
+        let result = TextParser::parse_records_with(Cursor::new(text), &options);
+        assert!(
+            matches!(&result, Err(ParserError::Validation(msg)) if msg.starts_with("Line 11: DESCRIPTION too long")),
+            "{:?}",
+            result
+        );
+
+        let encoded = text.replace(
+            "DESCRIPTION: \"Long description\"",
+            "DESCRIPTION_B64: TG9uZyBkZXNjcmlwdGlvbg==",
+        );
+        let result = TextParser::parse_records_with(Cursor::new(encoded), &options);
+        assert!(
+            matches!(&result, Err(ParserError::Validation(msg)) if msg.starts_with("Line 11: ")),
+            "{:?}",
+            result
+        );
+    }
+
     #[test]
     fn test_parse_missing_field() {
         let text = r#"TX_ID: 1001
@@ -528,10 +1175,13 @@ DESCRIPTION: "Test""#;
         let cursor = Cursor::new(text);
         let result = TextParser::parse_records(cursor);
 
-        assert!(matches!(result, Err(ParserError::Parse(_))));
-        if let Err(ParserError::Parse(msg)) = result {
-            assert!(msg.contains("missing") || msg.contains("STATUS"));
-        }
+        assert!(matches!(
+            result,
+            Err(ParserError::MissingField {
+                field: Field::Status,
+                ..
+            })
+        ));
     }
 
     #[test]
@@ -549,12 +1199,71 @@ DESCRIPTION: "Test""#;
         let cursor = Cursor::new(text);
         let result = TextParser::parse_records(cursor);
 
-        assert!(matches!(result, Err(ParserError::Parse(_))));
-        if let Err(ParserError::Parse(msg)) = result {
-            assert!(msg.contains("duplicate"));
+        match result {
+            Err(ParserError::DuplicateField { field, line }) => {
+                assert_eq!(field, "TX_TYPE");
+                assert_eq!(line, 3);
+            }
+            other => panic!("Expected duplicate field, got {:?}", other),
         }
     }
 
+    #[test]
+    fn test_duplicate_policy() {
+        let text = "TX_ID: 1001\nTX_TYPE: DEPOSIT\nFROM_USER_ID: 0\nTO_USER_ID: 501\n\
+                    AMOUNT: 100\nAMOUNT: 200\nTIMESTAMP: 1672531200000\nSTATUS: SUCCESS\n\
+                    DESCRIPTION: \"Test\"";
+        let parse = |duplicate_policy| {
+            let mut options = ParseOptions::default();
+            options.text.duplicate_policy = duplicate_policy;
+            TextParser::parse_records_with(Cursor::new(text), &options)
+        };
+
+        assert!(matches!(
+            parse(DuplicatePolicy::Error),
+            Err(ParserError::DuplicateField { ref field, line: 6 }) if field == "AMOUNT"
+        ));
+        assert_eq!(parse(DuplicatePolicy::FirstWins).unwrap()[0].amount, 100);
+        assert_eq!(parse(DuplicatePolicy::LastWins).unwrap()[0].amount, 200);
+    }
+
+    #[test]
+    fn test_parse_records_with_warnings() {
+        let text = "TX_ID: 1001\nTX_TYPE: DEPOSIT\nFROM_USER_ID: 0\nTO_USER_ID: 501\n\
+                    AMOUNT: 100\nAMOUNT: 200\nTIMESTAMP: 1672531200000\nSTATUS: SUCCESS\n\
+                    BRANCH: 12\nDESCRIPTION: \"Test\"";
+        let parse = |duplicate_policy| {
+            let mut options = ParseOptions::default();
+            options.text.duplicate_policy = duplicate_policy;
+            let mut warnings = Vec::new();
+            let records =
+                TextParser::parse_records_with_warnings(Cursor::new(text), &options, &mut warnings)
+                    .unwrap();
+            (records[0].amount, warnings)
+        };
+
+        assert_eq!(
+            parse(DuplicatePolicy::FirstWins),
+            (
+                100,
+                vec![
+                    "Line 6: ignored repeated field 'AMOUNT', keeping the first value".to_string(),
+                    "Line 9: ignored unknown field 'BRANCH'".to_string(),
+                ]
+            )
+        );
+        assert_eq!(
+            parse(DuplicatePolicy::LastWins),
+            (
+                200,
+                vec![
+                    "Line 6: repeated field 'AMOUNT' overrides the earlier value".to_string(),
+                    "Line 9: ignored unknown field 'BRANCH'".to_string(),
+                ]
+            )
+        );
+    }
+
     #[test]
     fn test_parse_invalid_tx_type() {
         let text = r#"TX_ID: 1001
@@ -569,7 +1278,13 @@ DESCRIPTION: "Test""#;
         let cursor = Cursor::new(text);
         let result = TextParser::parse_records(cursor);
 
-        assert!(matches!(result, Err(ParserError::Parse(_))));
+        match result {
+            Err(ParserError::InvalidEnum { field, value, .. }) => {
+                assert_eq!(field, Field::TxType);
+                assert_eq!(value, "INVALID");
+            }
+            other => panic!("Expected invalid TX_TYPE, got {:?}", other),
+        }
     }
 
     #[test]
@@ -624,6 +1339,7 @@ DESCRIPTION: "Test with \"quotes\" inside""#;
                 timestamp: 1672531200000,
                 status: TransactionStatus::Success,
                 description: "Initial deposit".to_string(),
+                currency: None,
             },
             Transaction {
                 tx_id: 1002,
@@ -634,6 +1350,7 @@ DESCRIPTION: "Test with \"quotes\" inside""#;
                 timestamp: 1672534800000,
                 status: TransactionStatus::Failure,
                 description: r#"Transfer with "quotes" and special chars"#.to_string(),
+                currency: None,
             },
         ];
 
@@ -667,6 +1384,7 @@ DESCRIPTION: "Test with \"quotes\" inside""#;
                 timestamp: 1672531200000,
                 status: TransactionStatus::Success,
                 description: "Test deposit with \"special\" chars".to_string(),
+                currency: None,
             },
             Transaction {
                 tx_id: 9876543210,
@@ -677,6 +1395,7 @@ DESCRIPTION: "Test with \"quotes\" inside""#;
                 timestamp: 1672534800000,
                 status: TransactionStatus::Pending,
                 description: "Test withdrawal".to_string(),
+                currency: None,
             },
         ];
 
@@ -701,6 +1420,38 @@ DESCRIPTION: "Test with \"quotes\" inside""#;
         }
     }
 
+    #[test]
+    fn test_roundtrip_with_and_without_currency() {
+        let without = Transaction {
+            tx_id: 1001,
+            tx_type: TransactionType::Deposit,
+            from_user_id: 0,
+            to_user_id: 501,
+            amount: 50000,
+            timestamp: 1672531200000,
+            status: TransactionStatus::Success,
+            description: "No currency".to_string(),
+            currency: None,
+        };
+        let with = Transaction {
+            tx_id: 1002,
+            description: "Priced in euro".to_string(),
+            currency: Some("EUR".to_string()),
+            ..without.clone()
+        };
+        let original = vec![without, with];
+
+        let mut buffer = Vec::new();
+        TextParser::write_records(&original, &mut buffer).unwrap();
+
+        let output = String::from_utf8(buffer.clone()).unwrap();
+        assert_eq!(output.matches("CURRENCY:").count(), 1);
+        assert!(output.contains("CURRENCY: EUR"));
+
+        let parsed = TextParser::parse_records(Cursor::new(&buffer)).unwrap();
+        assert_eq!(parsed, original);
+    }
+
     #[test]
     fn test_invalid_key_value_format() {
         let text = r#"TX_ID 1001  # Нет двоеточия
@@ -766,10 +1517,9 @@ DESCRIPTION: "Test""#;
         let cursor = Cursor::new(text);
         let result = TextParser::parse_records(cursor);
 
-        assert!(matches!(result, Err(ParserError::Parse(_))));
-        if let Err(ParserError::Parse(msg)) = result {
-            assert!(msg.contains("positive"));
-        }
+        assert!(
+            matches!(result, Err(ParserError::BusinessRule { rule, .. }) if rule.contains("positive"))
+        );
     }
 
     #[test]
@@ -786,10 +1536,9 @@ DESCRIPTION: "Test""#;
         let cursor = Cursor::new(text);
         let result = TextParser::parse_records(cursor);
 
-        assert!(matches!(result, Err(ParserError::Parse(_))));
-        if let Err(ParserError::Parse(msg)) = result {
-            assert!(msg.contains("positive"));
-        }
+        assert!(
+            matches!(result, Err(ParserError::BusinessRule { rule, .. }) if rule.contains("positive"))
+        );
     }
 
     #[test]
@@ -851,42 +1600,4 @@ DESCRIPTION: "Test""#;
         let transactions = result.unwrap();
         assert_eq!(transactions[0].description, "Test with spaces");
     }
-
-    #[test]
-    fn test_parsefromread_trait_implementation() {
-        let text = "TX_ID: 1001\nTX_TYPE: DEPOSIT\nFROM_USER_ID: 0\nTO_USER_ID: 501\nAMOUNT: 50000\nTIMESTAMP: 1672531200000\nSTATUS: SUCCESS\nDESCRIPTION: \"Test trait implementation\"";
-        let mut cursor = Cursor::new(text);
-
-        // Используем трейт ParseFromRead для TextTransactions
-        let text_transactions: TextTransactions = TextTransactions::parse(&mut cursor).unwrap();
-        let transactions = text_transactions.0; // Достаем Vec<Transaction> из обертки
-
-        assert_eq!(transactions.len(), 1);
-        assert_eq!(transactions[0].tx_id, 1001);
-        assert_eq!(transactions[0].description, "Test trait implementation");
-    }
-
-    #[test]
-    fn test_writeto_trait_implementation() {
-        let transactions = vec![Transaction {
-            tx_id: 1001,
-            tx_type: TransactionType::Deposit,
-            from_user_id: 0,
-            to_user_id: 501,
-            amount: 50000,
-            timestamp: 1672531200000,
-            status: TransactionStatus::Success,
-            description: "Test trait write".to_string(),
-        }];
-
-        let text_transactions = TextTransactions(transactions);
-        let mut buffer = Vec::new();
-
-        // Используем трейт WriteTo для TextTransactions
-        text_transactions.write(&mut buffer).unwrap();
-
-        let output = String::from_utf8(buffer).unwrap();
-        assert!(output.contains("TX_ID: 1001"));
-        assert!(output.contains("DESCRIPTION: \"Test trait write\""));
-    }
 }
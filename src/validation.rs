@@ -0,0 +1,291 @@
+use crate::{Field, Format, ParseOptions, ParserError, Transaction, TransactionType};
+
+/// Правила валидации транзакций, применяемые при парсинге
+///
+/// Дополняют встроенные бизнес-правила форматов настраиваемыми
+/// ограничениями. По умолчанию все ограничения отключены.
+///
+/// # Пример
+/// ```
+/// use parser_lib::ValidationRules;
+///
+/// let rules = ValidationRules {
+///     max_description_len: Some(1024),
+///     ..Default::default()
+/// };
+/// assert_eq!(rules.max_description_len, Some(1024));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ValidationRules {
+    /// Максимальная длина описания в байтах UTF-8
+    ///
+    /// `None` означает отсутствие ограничения.
+    pub max_description_len: Option<usize>,
+
+    /// Допустимый диапазон временных меток
+    ///
+    /// Нулевые или далёкие в будущем значения обычно означают испорченные
+    /// данные. `None` означает отсутствие проверки.
+    pub timestamp_range: Option<TimestampRange>,
+}
+
+/// Допустимый диапазон временных меток в миллисекундах Unix, включительно
+///
+/// # Пример
+/// ```
+/// use parser_lib::TimestampRange;
+///
+/// let range = TimestampRange::PLAUSIBLE;
+/// assert!(range.contains(1672531200000));
+/// assert!(!range.contains(0));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimestampRange {
+    /// Минимальная допустимая метка
+    pub min_ms: u64,
+    /// Максимальная допустимая метка
+    pub max_ms: u64,
+}
+
+impl TimestampRange {
+    /// Правдоподобное окно: с 2000-01-01 до 2100-01-01 (UTC)
+    pub const PLAUSIBLE: TimestampRange = TimestampRange {
+        min_ms: 946_684_800_000,
+        max_ms: 4_102_444_800_000,
+    };
+
+    /// Проверяет, попадает ли метка в диапазон
+    pub fn contains(&self, timestamp: u64) -> bool {
+        (self.min_ms..=self.max_ms).contains(&timestamp)
+    }
+}
+
+impl ValidationRules {
+    /// Проверяет транзакцию на соответствие правилам
+    ///
+    /// # Аргументы
+    /// * `transaction` - Проверяемая транзакция
+    ///
+    /// # Возвращает
+    /// * `Ok(())` - Транзакция соответствует всем правилам
+    /// * `Err(String)` - Описание нарушенного правила
+    pub fn check(&self, transaction: &Transaction) -> Result<(), String> {
+        self.check_fields(transaction).map_err(|(_, msg)| msg)
+    }
+
+    /// Проверяет транзакцию, сообщая поле, нарушившее правило
+    ///
+    /// Позволяет построчным форматам указать в ошибке строку самого поля.
+    pub(crate) fn check_fields(&self, transaction: &Transaction) -> Result<(), (Field, String)> {
+        if let Some(max_len) = self.max_description_len
+            && transaction.description.len() > max_len
+        {
+            return Err((
+                Field::Description,
+                format!(
+                    "DESCRIPTION too long: {} bytes, maximum is {}",
+                    transaction.description.len(),
+                    max_len
+                ),
+            ));
+        }
+
+        if let Some(range) = self.timestamp_range
+            && !range.contains(transaction.timestamp)
+        {
+            return Err((
+                Field::Timestamp,
+                format!(
+                    "TIMESTAMP {} is outside the allowed range {}..={}",
+                    transaction.timestamp, range.min_ms, range.max_ms
+                ),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// Проверяет, что сумма транзакции положительна
+///
+/// # Аргументы
+/// * `amount` - Проверяемая сумма
+/// * `format` - Формат, в сообщении которого указывается нарушение
+/// * `line` - Номер строки, если формат построчный
+///
+/// # Возвращает
+/// * `Ok(())` - Сумма положительна
+/// * `Err(ParserError::BusinessRule)` - Сумма равна нулю или отрицательна
+pub(crate) fn check_positive_amount(
+    amount: i64,
+    format: Format,
+    line: Option<usize>,
+) -> Result<(), ParserError> {
+    if amount > 0 {
+        return Ok(());
+    }
+
+    let rule = match format {
+        Format::Csv => format!("AMOUNT must be positive in CSV format, got {}", amount),
+        Format::Txt | Format::Bin => format!("AMOUNT must be positive, got {}", amount),
+    };
+    Err(ParserError::BusinessRule { rule, line })
+}
+
+/// Проверяет, что FROM_USER_ID и TO_USER_ID согласованы с типом транзакции
+///
+/// # Аргументы
+/// * `tx_type` - Тип транзакции
+/// * `from_user_id` - Отправитель
+/// * `to_user_id` - Получатель
+/// * `line` - Номер строки, если формат построчный
+///
+/// # Возвращает
+/// * `Ok(())` - Идентификаторы согласованы
+/// * `Err(ParserError::BusinessRule)` - Описание нарушенного правила
+pub(crate) fn check_user_ids(
+    tx_type: TransactionType,
+    from_user_id: u64,
+    to_user_id: u64,
+    line: Option<usize>,
+) -> Result<(), ParserError> {
+    let rule = match tx_type {
+        TransactionType::Deposit if from_user_id != 0 => {
+            format!("DEPOSIT must have FROM_USER_ID = 0, got {}", from_user_id)
+        }
+        TransactionType::Withdrawal if to_user_id != 0 => {
+            format!("WITHDRAWAL must have TO_USER_ID = 0, got {}", to_user_id)
+        }
+        TransactionType::Transfer if from_user_id == 0 => {
+            "TRANSFER cannot have FROM_USER_ID = 0".to_string()
+        }
+        TransactionType::Transfer if to_user_id == 0 => {
+            "TRANSFER cannot have TO_USER_ID = 0".to_string()
+        }
+        _ => return Ok(()),
+    };
+    Err(ParserError::BusinessRule { rule, line })
+}
+
+/// Проверяет встроенные бизнес-правила формата
+///
+/// CSV и текстовый форматы требуют положительную сумму (CSV — только без
+/// `signed_amounts`) и согласованные с TX_TYPE идентификаторы участников.
+/// Бинарный формат допускает любые значения, поэтому правил не имеет.
+///
+/// # Аргументы
+/// * `transaction` - Проверяемая транзакция
+/// * `format` - Формат, правила которого применяются
+/// * `options` - Настройки парсинга
+/// * `line` - Номер строки, если формат построчный
+///
+/// # Возвращает
+/// * `Ok(())` - Транзакция соответствует правилам формата
+/// * `Err(ParserError::BusinessRule)` - Описание нарушенного правила
+pub(crate) fn check_business_rules(
+    transaction: &Transaction,
+    format: Format,
+    options: &ParseOptions,
+    line: Option<usize>,
+) -> Result<(), ParserError> {
+    if format == Format::Bin {
+        return Ok(());
+    }
+
+    if !(format == Format::Csv && options.csv.signed_amounts) {
+        check_positive_amount(transaction.amount, format, line)?;
+    }
+    check_user_ids(
+        transaction.tx_type,
+        transaction.from_user_id,
+        transaction.to_user_id,
+        line,
+    )
+}
+
+/// Проверяет набор транзакций на правила формата и настраиваемые правила
+///
+/// # Возвращает
+/// * `Ok(())` - Все транзакции корректны
+/// * `Err(ParserError::BusinessRule)` - Нарушено встроенное правило формата
+/// * `Err(ParserError::Validation)` - Нарушено настраиваемое правило
+///
+/// Сообщение об ошибке начинается с индекса первой некорректной записи.
+pub(crate) fn validate_transactions(
+    transactions: &[Transaction],
+    format: Format,
+    options: &ParseOptions,
+) -> Result<(), ParserError> {
+    for (index, transaction) in transactions.iter().enumerate() {
+        check_business_rules(transaction, format, options, None).map_err(|e| match e {
+            ParserError::BusinessRule { rule, line } => ParserError::BusinessRule {
+                rule: format!("Record at index {}: {}", index, rule),
+                line,
+            },
+            other => other,
+        })?;
+        options.rules.check(transaction).map_err(|msg| {
+            ParserError::Validation(format!("Record at index {}: {}", index, msg))
+        })?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CsvParser;
+    use std::io::Cursor;
+
+    fn parse_with_timestamp(timestamp: u64) -> Result<Vec<Transaction>, ParserError> {
+        let csv = format!(
+            "TX_ID,TX_TYPE,FROM_USER_ID,TO_USER_ID,AMOUNT,TIMESTAMP,STATUS,DESCRIPTION\n\
+             1001,DEPOSIT,0,501,100,1672531200000,SUCCESS,\"Ok\"\n\
+             1002,DEPOSIT,0,501,100,{},SUCCESS,\"Suspicious\"",
+            timestamp
+        );
+        let mut options = ParseOptions::default();
+        options.rules.timestamp_range = Some(TimestampRange::PLAUSIBLE);
+        CsvParser::parse_records_with(Cursor::new(csv), &options)
+    }
+
+    #[test]
+    fn test_zero_timestamp_rejected() {
+        match parse_with_timestamp(0) {
+            Err(ParserError::Validation(msg)) => {
+                assert!(msg.starts_with("Line 3: TIMESTAMP 0 "), "{}", msg)
+            }
+            other => panic!("Expected validation error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_far_future_timestamp_rejected() {
+        // Год 50000
+        let far_future = 1_515_683_846_400_000;
+        match parse_with_timestamp(far_future) {
+            Err(ParserError::Validation(msg)) => {
+                assert!(msg.contains(&far_future.to_string()), "{}", msg);
+                assert!(msg.starts_with("Line 3:"), "{}", msg);
+            }
+            other => panic!("Expected validation error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_timestamp_range_disabled_by_default() {
+        let transaction = Transaction {
+            tx_id: 1,
+            tx_type: TransactionType::Deposit,
+            from_user_id: 0,
+            to_user_id: 501,
+            amount: 100,
+            timestamp: 0,
+            status: crate::TransactionStatus::Success,
+            description: String::new(),
+            currency: None,
+        };
+        assert!(ValidationRules::default().check(&transaction).is_ok());
+    }
+}
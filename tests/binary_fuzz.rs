@@ -0,0 +1,93 @@
+use parser_lib::{BinaryParser, BinaryRecord, ParserError};
+use proptest::prelude::*;
+use std::io::Cursor;
+
+const MAGIC: [u8; 4] = *b"YPBN";
+
+/// Собирает заголовок записи с заданными размерами и произвольным телом
+fn record_with_sizes(record_size: u32, desc_len: u32, tail: &[u8]) -> Vec<u8> {
+    let mut buffer = Vec::new();
+    buffer.extend_from_slice(&MAGIC);
+    buffer.extend_from_slice(&record_size.to_be_bytes());
+    buffer.extend_from_slice(&1u64.to_be_bytes());
+    buffer.push(0);
+    buffer.extend_from_slice(&0u64.to_be_bytes());
+    buffer.extend_from_slice(&501u64.to_be_bytes());
+    buffer.extend_from_slice(&100i64.to_be_bytes());
+    buffer.extend_from_slice(&0u64.to_be_bytes());
+    buffer.push(0);
+    buffer.extend_from_slice(&desc_len.to_be_bytes());
+    buffer.extend_from_slice(tail);
+    buffer
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(512))]
+
+    #[test]
+    fn from_read_never_panics_on_random_bytes(bytes in proptest::collection::vec(any::<u8>(), 0..256)) {
+        let _ = BinaryRecord::from_read(&mut Cursor::new(&bytes));
+    }
+
+    #[test]
+    fn from_read_never_panics_after_valid_magic(bytes in proptest::collection::vec(any::<u8>(), 0..256)) {
+        let mut input = MAGIC.to_vec();
+        input.extend_from_slice(&bytes);
+        let _ = BinaryRecord::from_read(&mut Cursor::new(&input));
+        let _ = BinaryParser::parse_records(Cursor::new(&input));
+    }
+
+    #[test]
+    fn from_read_rejects_truncated_large_lengths(
+        record_size in any::<u32>(),
+        desc_len in any::<u32>(),
+        tail in proptest::collection::vec(any::<u8>(), 0..64),
+    ) {
+        // Заявленные длины до 4 ГБ не должны приводить к выделению памяти
+        // под них: короткий поток обязан завершиться ошибкой
+        let input = record_with_sizes(record_size, desc_len, &tail);
+        let result = BinaryRecord::from_read(&mut Cursor::new(&input));
+
+        if desc_len as usize > tail.len() {
+            prop_assert!(result.is_err());
+        }
+    }
+
+    #[test]
+    fn from_read_accepts_any_written_description(description in "\\PC{0,64}") {
+        let record = BinaryRecord {
+            tx_id: 1,
+            tx_type: parser_lib::TransactionType::Deposit,
+            from_user_id: 0,
+            to_user_id: 501,
+            amount: 100,
+            timestamp: 0,
+            status: parser_lib::TransactionStatus::Success,
+            description,
+            currency: None,
+        };
+
+        let mut buffer = Vec::new();
+        record.write_to(&mut buffer).unwrap();
+
+        let parsed = BinaryRecord::from_read(&mut Cursor::new(&buffer));
+        prop_assert!(parsed.is_ok());
+    }
+}
+
+#[test]
+fn test_single_quote_description_does_not_panic() {
+    let input = record_with_sizes(47, 1, b"\"");
+
+    let record = BinaryRecord::from_read(&mut Cursor::new(&input)).unwrap();
+    assert_eq!(record.description, "\"");
+}
+
+#[test]
+fn test_huge_currency_length_is_rejected_without_allocation() {
+    let mut input = record_with_sizes(u32::MAX, 0, &[]);
+    input.extend_from_slice(&(u32::MAX - 50).to_be_bytes());
+
+    let result = BinaryRecord::from_read(&mut Cursor::new(&input));
+    assert!(matches!(result, Err(ParserError::Parse(_))));
+}
@@ -1,6 +1,5 @@
 use parser_lib::{
-    BinaryParser, BinaryTransactions, ParseFromRead, Transaction, TransactionStatus,
-    TransactionType,
+    BinaryParser, Format, Transaction, TransactionStatus, TransactionType, Transactions,
 };
 use std::io::Cursor;
 
@@ -16,6 +15,7 @@ fn test_binary_parser_multiple_records() {
             timestamp: 1672531200000,
             status: TransactionStatus::Success,
             description: "First".to_string(),
+            currency: None,
         },
         Transaction {
             tx_id: 1002,
@@ -26,6 +26,7 @@ fn test_binary_parser_multiple_records() {
             timestamp: 1672534800000,
             status: TransactionStatus::Failure,
             description: "Second".to_string(),
+            currency: None,
         },
     ];
 
@@ -41,7 +42,7 @@ fn test_binary_parser_multiple_records() {
 }
 
 #[test]
-fn test_binary_parsefromread_trait() {
+fn test_binary_transactions_read() {
     let records = vec![
         Transaction {
             tx_id: 1001,
@@ -52,6 +53,7 @@ fn test_binary_parsefromread_trait() {
             timestamp: 1672531200000,
             status: TransactionStatus::Success,
             description: "First".to_string(),
+            currency: None,
         },
         Transaction {
             tx_id: 1002,
@@ -62,16 +64,55 @@ fn test_binary_parsefromread_trait() {
             timestamp: 1672534800000,
             status: TransactionStatus::Failure,
             description: "Second".to_string(),
+            currency: None,
         },
     ];
 
     let mut buffer = Vec::new();
     BinaryParser::write_records(&records, &mut buffer).unwrap();
 
-    let mut cursor = Cursor::new(&buffer);
-    let bin_transactions: BinaryTransactions = ParseFromRead::parse(&mut cursor).unwrap();
+    let bin_transactions = Transactions::read(Format::Bin, buffer.as_slice()).unwrap();
 
     assert_eq!(bin_transactions.0.len(), 2);
     assert_eq!(bin_transactions.0[0].tx_id, 1001);
     assert_eq!(bin_transactions.0[1].tx_id, 1002);
 }
+
+#[test]
+fn test_binary_parse_range_reads_records_within_slice() {
+    let records: Vec<Transaction> = (1..=4)
+        .map(|i| Transaction {
+            tx_id: 1000 + i,
+            tx_type: TransactionType::Deposit,
+            from_user_id: 0,
+            to_user_id: 501,
+            amount: 100 * i as i64,
+            timestamp: 1672531200000 + i,
+            status: TransactionStatus::Success,
+            description: "x".repeat(i as usize),
+            currency: None,
+        })
+        .collect();
+
+    let encoded_len = |record: &Transaction| {
+        let mut buffer = Vec::new();
+        BinaryParser::write_records(std::slice::from_ref(record), &mut buffer).unwrap();
+        buffer.len() as u64
+    };
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let path = temp_dir.path().join("records.bin");
+    let mut file = std::fs::File::create(&path).unwrap();
+    BinaryParser::write_records(&records, &mut file).unwrap();
+    drop(file);
+
+    let start = encoded_len(&records[0]);
+    // Срез захватывает записи 2 и 3 и часть записи 4
+    let max_bytes = encoded_len(&records[1]) + encoded_len(&records[2]) + 10;
+
+    let parsed = BinaryParser::parse_range(&path, start, max_bytes).unwrap();
+    assert_eq!(parsed, records[1..3]);
+
+    let rest = BinaryParser::parse_range(&path, start, u64::MAX).unwrap();
+    assert_eq!(rest, records[1..]);
+}
@@ -1,7 +1,7 @@
 use std::fs::{self, File};
 use std::io::Write;
 use std::path::PathBuf;
-use std::process::Command;
+use std::process::{Command, Stdio};
 use tempfile::TempDir;
 
 fn build_and_get_binary() -> PathBuf {
@@ -128,3 +128,1740 @@ fn test_missing_file_error() {
     let stderr = String::from_utf8_lossy(&output.stderr);
     assert!(stderr.contains("не найден") || stderr.contains("not found"));
 }
+
+#[test]
+fn test_missing_file_error_in_english() {
+    let binary_path = build_and_get_binary();
+
+    let output = Command::new(&binary_path)
+        .args([
+            "--input",
+            "non_existent_file.csv",
+            "--input-format",
+            "csv",
+            "--output-format",
+            "txt",
+            "--lang",
+            "en",
+        ])
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(!output.status.success(), "Command should have failed");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("Error: input file 'non_existent_file.csv' not found"),
+        "Stderr: {}",
+        stderr
+    );
+    assert!(!stderr.contains("не найден"), "Stderr: {}", stderr);
+}
+
+#[test]
+fn test_oversize_description_to_binary_fails_at_parse() {
+    let binary_path = build_and_get_binary();
+    let temp_dir = TempDir::new().unwrap();
+
+    let csv_path = temp_dir.path().join("oversize.csv");
+    let mut csv_file = File::create(&csv_path).unwrap();
+    writeln!(
+        csv_file,
+        "TX_ID,TX_TYPE,FROM_USER_ID,TO_USER_ID,AMOUNT,TIMESTAMP,STATUS,DESCRIPTION"
+    )
+    .unwrap();
+    writeln!(
+        csv_file,
+        "1001,DEPOSIT,0,501,50000,1672531200000,SUCCESS,\"{}\"",
+        "x".repeat(1024 * 1024 + 1)
+    )
+    .unwrap();
+
+    let output_path = temp_dir.path().join("output.bin");
+
+    let output = Command::new(&binary_path)
+        .args([
+            "--input",
+            csv_path.to_str().unwrap(),
+            "--input-format",
+            "csv",
+            "--output-format",
+            "bin",
+            "--output",
+            output_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(!output.status.success(), "Command should have failed");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Line 2"), "Stderr: {}", stderr);
+    assert!(stderr.contains("too long"), "Stderr: {}", stderr);
+    assert!(
+        !output_path.exists(),
+        "Output must not be created on parse failure"
+    );
+}
+
+fn write_csv_input(path: &std::path::Path, rows: &[&str]) {
+    let mut csv_file = File::create(path).unwrap();
+    writeln!(
+        csv_file,
+        "TX_ID,TX_TYPE,FROM_USER_ID,TO_USER_ID,AMOUNT,TIMESTAMP,STATUS,DESCRIPTION"
+    )
+    .unwrap();
+    for row in rows {
+        writeln!(csv_file, "{}", row).unwrap();
+    }
+}
+
+fn run_append(
+    binary_path: &PathBuf,
+    input: &std::path::Path,
+    output_format: &str,
+    output: &std::path::Path,
+) {
+    let output = Command::new(binary_path)
+        .args([
+            "--input",
+            input.to_str().unwrap(),
+            "--input-format",
+            "csv",
+            "--output-format",
+            output_format,
+            "--output",
+            output.to_str().unwrap(),
+            "--append",
+        ])
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(
+        output.status.success(),
+        "Command failed:\nStdout: {}\nStderr: {}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+fn test_append_to_existing_csv_without_duplicate_header() {
+    let binary_path = build_and_get_binary();
+    let temp_dir = TempDir::new().unwrap();
+
+    let first_input = temp_dir.path().join("first.csv");
+    let second_input = temp_dir.path().join("second.csv");
+    write_csv_input(
+        &first_input,
+        &["1001,DEPOSIT,0,501,50000,1672531200000,SUCCESS,\"First\""],
+    );
+    write_csv_input(
+        &second_input,
+        &["1002,WITHDRAWAL,501,0,1000,1672534800000,PENDING,\"Second\""],
+    );
+
+    let output_path = temp_dir.path().join("accumulated.csv");
+    run_append(&binary_path, &first_input, "csv", &output_path);
+    run_append(&binary_path, &second_input, "csv", &output_path);
+
+    let content = fs::read_to_string(&output_path).unwrap();
+    assert_eq!(content.matches("TX_ID,TX_TYPE").count(), 1);
+    assert_eq!(content.lines().count(), 3);
+    assert!(content.contains("1001,DEPOSIT"));
+    assert!(content.contains("1002,WITHDRAWAL"));
+}
+
+#[test]
+fn test_append_to_existing_binary() {
+    let binary_path = build_and_get_binary();
+    let temp_dir = TempDir::new().unwrap();
+
+    let first_input = temp_dir.path().join("first.csv");
+    let second_input = temp_dir.path().join("second.csv");
+    write_csv_input(
+        &first_input,
+        &["1001,DEPOSIT,0,501,50000,1672531200000,SUCCESS,\"First\""],
+    );
+    write_csv_input(
+        &second_input,
+        &["1002,WITHDRAWAL,501,0,1000,1672534800000,PENDING,\"Second\""],
+    );
+
+    let output_path = temp_dir.path().join("accumulated.bin");
+    run_append(&binary_path, &first_input, "bin", &output_path);
+    run_append(&binary_path, &second_input, "bin", &output_path);
+
+    let file = File::open(&output_path).unwrap();
+    let transactions = parser_lib::BinaryParser::parse_records(file).unwrap();
+    assert_eq!(transactions.len(), 2);
+    assert_eq!(transactions[0].tx_id, 1001);
+    assert_eq!(transactions[1].tx_id, 1002);
+}
+
+#[test]
+fn test_lossy_output_format_warning() {
+    let binary_path = build_and_get_binary();
+    let temp_dir = TempDir::new().unwrap();
+
+    let csv_path = temp_dir.path().join("input.csv");
+    write_csv_input(
+        &csv_path,
+        &["1001,DEPOSIT,0,501,50000,1672531200000,SUCCESS,\"Test\""],
+    );
+
+    let run = |output_format: &str| {
+        let output_path = temp_dir.path().join(format!("output.{}", output_format));
+        let output = Command::new(&binary_path)
+            .args([
+                "--input",
+                csv_path.to_str().unwrap(),
+                "--input-format",
+                "csv",
+                "--output-format",
+                output_format,
+                "--output",
+                output_path.to_str().unwrap(),
+            ])
+            .output()
+            .expect("Failed to execute command");
+        assert!(output.status.success());
+        String::from_utf8_lossy(&output.stderr).to_string()
+    };
+
+    let stderr = run("bin");
+    assert!(stderr.contains("Предупреждение"), "Stderr: {}", stderr);
+    assert!(stderr.contains("DESCRIPTION"), "Stderr: {}", stderr);
+
+    let stderr = run("csv");
+    assert!(!stderr.contains("Предупреждение"), "Stderr: {}", stderr);
+}
+
+#[test]
+fn test_max_records_limit() {
+    let binary_path = build_and_get_binary();
+    let temp_dir = TempDir::new().unwrap();
+
+    let csv_path = temp_dir.path().join("input.csv");
+    write_csv_input(
+        &csv_path,
+        &[
+            "1001,DEPOSIT,0,501,100,1672531200000,SUCCESS,\"One\"",
+            "1002,DEPOSIT,0,501,100,1672531200000,SUCCESS,\"Two\"",
+        ],
+    );
+
+    let output = Command::new(&binary_path)
+        .args([
+            "--input",
+            csv_path.to_str().unwrap(),
+            "--input-format",
+            "csv",
+            "--output-format",
+            "txt",
+            "--max-records",
+            "1",
+        ])
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("record limit exceeded"),
+        "Stderr: {}",
+        stderr
+    );
+}
+
+#[test]
+fn test_input_limits_enforced_on_same_format_with_skip_validation() {
+    let binary_path = build_and_get_binary();
+    let temp_dir = TempDir::new().unwrap();
+
+    let csv_path = temp_dir.path().join("input.csv");
+    write_csv_input(
+        &csv_path,
+        &[
+            "1001,DEPOSIT,0,501,100,1672531200000,SUCCESS,\"One\"",
+            "1002,DEPOSIT,0,501,100,1672531200000,SUCCESS,\"Two\"",
+        ],
+    );
+
+    for limit in [["--max-records", "1"], ["--max-line-bytes", "20"]] {
+        let output = Command::new(&binary_path)
+            .args([
+                "--input",
+                csv_path.to_str().unwrap(),
+                "--input-format",
+                "csv",
+                "--output-format",
+                "csv",
+                "--skip-validation",
+            ])
+            .args(limit)
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(!output.status.success(), "{:?}: {:?}", limit, output);
+    }
+}
+
+#[test]
+fn test_max_line_bytes_limit() {
+    let binary_path = build_and_get_binary();
+    let temp_dir = TempDir::new().unwrap();
+
+    let csv_path = temp_dir.path().join("input.csv");
+    let long_row = format!(
+        "1001,DEPOSIT,0,501,100,1672531200000,SUCCESS,\"{}\"",
+        "x".repeat(200)
+    );
+    write_csv_input(&csv_path, &[&long_row]);
+
+    let run = |limit: &str| {
+        Command::new(&binary_path)
+            .args([
+                "--input",
+                csv_path.to_str().unwrap(),
+                "--input-format",
+                "csv",
+                "--output-format",
+                "txt",
+                "--max-line-bytes",
+                limit,
+            ])
+            .output()
+            .expect("Failed to execute command")
+    };
+
+    let output = run("128");
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("line too long"), "Stderr: {}", stderr);
+
+    let output = run("1024");
+    assert!(output.status.success(), "Command failed: {:?}", output);
+}
+
+#[test]
+fn test_require_nonempty_rejects_empty_input() {
+    let binary_path = build_and_get_binary();
+    let temp_dir = TempDir::new().unwrap();
+    let input_path = temp_dir.path().join("empty.csv");
+    File::create(&input_path).unwrap();
+
+    let run = |extra: &[&str]| {
+        Command::new(&binary_path)
+            .args([
+                "--input",
+                input_path.to_str().unwrap(),
+                "--input-format",
+                "csv",
+                "--output-format",
+                "txt",
+                "--lang",
+                "en",
+            ])
+            .args(extra)
+            .output()
+            .expect("Failed to execute command")
+    };
+
+    let output = run(&[]);
+    assert!(output.status.success(), "Command failed: {:?}", output);
+    assert!(output.stdout.is_empty());
+
+    let output = run(&["--require-nonempty"]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("empty input"), "Stderr: {}", stderr);
+}
+
+#[test]
+fn test_csv_from_stdin_to_txt_stdout() {
+    let binary_path = build_and_get_binary();
+
+    let mut child = Command::new(&binary_path)
+        .args([
+            "--input",
+            "-",
+            "--input-format",
+            "csv",
+            "--output-format",
+            "txt",
+        ])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("Failed to execute command");
+
+    {
+        let mut stdin = child.stdin.take().unwrap();
+        writeln!(
+            stdin,
+            "TX_ID,TX_TYPE,FROM_USER_ID,TO_USER_ID,AMOUNT,TIMESTAMP,STATUS,DESCRIPTION"
+        )
+        .unwrap();
+        writeln!(
+            stdin,
+            "1001,DEPOSIT,0,501,50000,1672531200000,SUCCESS,\"From stdin\""
+        )
+        .unwrap();
+    }
+
+    let output = child.wait_with_output().unwrap();
+    assert!(
+        output.status.success(),
+        "Stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("TX_ID: 1001"), "Stdout: {}", stdout);
+    assert!(
+        stdout.contains("DESCRIPTION: \"From stdin\""),
+        "Stdout: {}",
+        stdout
+    );
+}
+
+#[test]
+fn test_binary_from_stdin() {
+    let binary_path = build_and_get_binary();
+    let temp_dir = TempDir::new().unwrap();
+
+    let csv_path = temp_dir.path().join("input.csv");
+    write_csv_input(
+        &csv_path,
+        &["1001,DEPOSIT,0,501,50000,1672531200000,SUCCESS,\"Binary stdin\""],
+    );
+    let bin_path = temp_dir.path().join("input.bin");
+
+    let status = Command::new(&binary_path)
+        .args([
+            "--input",
+            csv_path.to_str().unwrap(),
+            "--input-format",
+            "csv",
+            "--output-format",
+            "bin",
+            "--output",
+            bin_path.to_str().unwrap(),
+        ])
+        .status()
+        .expect("Failed to execute command");
+    assert!(status.success());
+
+    let output = Command::new(&binary_path)
+        .args(["--input-format", "bin", "--output-format", "csv"])
+        .stdin(File::open(&bin_path).unwrap())
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("\"Binary stdin\""), "Stdout: {}", stdout);
+}
+
+fn write_txt_expected(path: &std::path::Path, amount: i64) {
+    fs::write(
+        path,
+        format!(
+            "TX_ID: 1001\nTX_TYPE: DEPOSIT\nFROM_USER_ID: 0\nTO_USER_ID: 501\n\
+             AMOUNT: {}\nTIMESTAMP: 1672531200000\nSTATUS: SUCCESS\nDESCRIPTION: \"Expected\"\n",
+            amount
+        ),
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_compare_output_against_expected() {
+    let binary_path = build_and_get_binary();
+    let temp_dir = TempDir::new().unwrap();
+
+    let csv_path = temp_dir.path().join("input.csv");
+    write_csv_input(
+        &csv_path,
+        &["1001,DEPOSIT,0,501,50000,1672531200000,SUCCESS,\"Expected\""],
+    );
+
+    let matching = temp_dir.path().join("matching.txt");
+    write_txt_expected(&matching, 50000);
+    let mismatched = temp_dir.path().join("mismatched.txt");
+    write_txt_expected(&mismatched, 60000);
+
+    let run = |expected: &std::path::Path| {
+        Command::new(&binary_path)
+            .args([
+                "--input",
+                csv_path.to_str().unwrap(),
+                "--input-format",
+                "csv",
+                "--output-format",
+                "txt",
+                "--expected",
+                expected.to_str().unwrap(),
+                "--expected-format",
+                "txt",
+            ])
+            .output()
+            .expect("Failed to execute command")
+    };
+
+    let output = run(&matching);
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "Stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let output = run(&mismatched);
+    assert_eq!(output.status.code(), Some(2));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("AMOUNT: 50000 != 60000"),
+        "Stdout: {}",
+        stdout
+    );
+}
+
+#[test]
+fn test_same_format_conversion_is_byte_identical() {
+    let binary_path = build_and_get_binary();
+    let temp_dir = TempDir::new().unwrap();
+
+    // Описание с пробелами по краям нормализуется при чтении,
+    // поэтому перекодирование изменило бы байты
+    let record = parser_lib::BinaryRecord {
+        tx_id: 1001,
+        tx_type: parser_lib::TransactionType::Deposit,
+        from_user_id: 0,
+        to_user_id: 501,
+        amount: 50000,
+        timestamp: 1672531200000,
+        status: parser_lib::TransactionStatus::Success,
+        description: "  padded  ".to_string(),
+        currency: None,
+    };
+    let mut input_bytes = Vec::new();
+    record.write_to(&mut input_bytes).unwrap();
+
+    let input_path = temp_dir.path().join("input.bin");
+    fs::write(&input_path, &input_bytes).unwrap();
+    let output_path = temp_dir.path().join("output.bin");
+
+    let output = Command::new(&binary_path)
+        .args([
+            "--input",
+            input_path.to_str().unwrap(),
+            "--input-format",
+            "bin",
+            "--output-format",
+            "bin",
+            "--output",
+            output_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(
+        output.status.success(),
+        "Stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(fs::read(&output_path).unwrap(), input_bytes);
+}
+
+#[test]
+fn test_same_format_csv_with_read_warnings_is_reencoded() {
+    let binary_path = build_and_get_binary();
+    let temp_dir = TempDir::new().unwrap();
+
+    let header = "TX_ID,TX_TYPE,FROM_USER_ID,TO_USER_ID,AMOUNT,TIMESTAMP,STATUS,DESCRIPTION";
+    let input_path = temp_dir.path().join("input.csv");
+    write_csv_input(
+        &input_path,
+        &[
+            "1001,DEPOSIT,0,501,50000,1672531200000,SUCCESS,\"One\",,",
+            header,
+            "1002,DEPOSIT,0,502,1000,1672531300000,SUCCESS,\"Two\"",
+        ],
+    );
+
+    let run = |input: &std::path::Path, output_path: &std::path::Path, extra: &[&str]| {
+        let output = Command::new(&binary_path)
+            .args([
+                "--input",
+                input.to_str().unwrap(),
+                "--input-format",
+                "csv",
+                "--output-format",
+                "csv",
+                "--output",
+                output_path.to_str().unwrap(),
+            ])
+            .args(extra)
+            .output()
+            .expect("Failed to execute command");
+        assert!(output.status.success(), "Статус: {:?}", output);
+        fs::read_to_string(output_path).unwrap()
+    };
+
+    let tolerant = run(
+        &input_path,
+        &temp_dir.path().join("tolerant.csv"),
+        &["--tolerate-repeated-header"],
+    );
+    assert_eq!(tolerant.matches("TX_ID,").count(), 1, "{}", tolerant);
+    assert!(!tolerant.contains(",,"), "{}", tolerant);
+
+    // Только лишние пустые поля: предупреждение тоже отключает копирование байтов
+    let trailing_path = temp_dir.path().join("trailing.csv");
+    write_csv_input(
+        &trailing_path,
+        &["1001,DEPOSIT,0,501,50000,1672531200000,SUCCESS,\"One\",,"],
+    );
+    let written = run(
+        &trailing_path,
+        &temp_dir.path().join("trailing_out.csv"),
+        &[],
+    );
+    assert!(!written.contains(",,"), "{}", written);
+    let records = parser_lib::CsvParser::parse_records(written.as_bytes()).unwrap();
+    assert_eq!(records.len(), 1);
+}
+
+#[test]
+fn test_csv_output_without_header() {
+    let binary_path = build_and_get_binary();
+    let temp_dir = TempDir::new().unwrap();
+
+    let txt_path = temp_dir.path().join("input.txt");
+    fs::write(
+        &txt_path,
+        "TX_ID: 1001\nTX_TYPE: DEPOSIT\nFROM_USER_ID: 0\nTO_USER_ID: 501\n\
+         AMOUNT: 50000\nTIMESTAMP: 1672531200000\nSTATUS: SUCCESS\nDESCRIPTION: \"No header\"\n",
+    )
+    .unwrap();
+
+    let output = Command::new(&binary_path)
+        .args([
+            "--input",
+            txt_path.to_str().unwrap(),
+            "--input-format",
+            "txt",
+            "--output-format",
+            "csv",
+            "--no-header",
+        ])
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(!stdout.contains("TX_ID"), "Stdout: {}", stdout);
+    assert_eq!(
+        stdout.trim_end(),
+        "1001,DEPOSIT,0,501,50000,1672531200000,SUCCESS,\"No header\""
+    );
+}
+
+#[test]
+fn test_verbose_reports_bytes_written() {
+    let binary_path = build_and_get_binary();
+    let temp_dir = TempDir::new().unwrap();
+
+    let input_path = temp_dir.path().join("input.csv");
+    write_csv_input(
+        &input_path,
+        &[
+            "1001,DEPOSIT,0,501,50000,1672531200000,SUCCESS,\"First\"",
+            "1002,WITHDRAWAL,501,0,1000,1672534800000,PENDING,\"Second\"",
+        ],
+    );
+    let output_path = temp_dir.path().join("output.bin");
+
+    let output = Command::new(&binary_path)
+        .args([
+            "--input",
+            input_path.to_str().unwrap(),
+            "--input-format",
+            "csv",
+            "--output-format",
+            "bin",
+            "--output",
+            output_path.to_str().unwrap(),
+            "--verbose",
+        ])
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success());
+    let file_size = fs::metadata(&output_path).unwrap().len();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains(&format!("Записано {} байт, 2 транзакций", file_size)),
+        "Stderr: {}",
+        stderr
+    );
+}
+
+#[test]
+fn test_verbose_messages_follow_lang() {
+    let binary_path = build_and_get_binary();
+    let temp_dir = TempDir::new().unwrap();
+
+    let input_path = temp_dir.path().join("input.csv");
+    write_csv_input(
+        &input_path,
+        &[
+            "1001,DEPOSIT,0,501,50000,1672531200000,SUCCESS,\"First\"",
+            "1002,WITHDRAWAL,501,0,1000,1672534800000,PENDING,\"Second\"",
+        ],
+    );
+    let output_path = temp_dir.path().join("output.bin");
+
+    let output = Command::new(&binary_path)
+        .args([
+            "--input",
+            input_path.to_str().unwrap(),
+            "--input-format",
+            "csv",
+            "--output-format",
+            "bin",
+            "--output",
+            output_path.to_str().unwrap(),
+            "--verbose",
+            "--lang",
+            "en",
+        ])
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success());
+    let file_size = fs::metadata(&output_path).unwrap().len();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains(&format!("Wrote {} bytes, 2 transactions", file_size)),
+        "Stderr: {}",
+        stderr
+    );
+    assert!(
+        stderr.contains("Conversion completed successfully!"),
+        "Stderr: {}",
+        stderr
+    );
+    assert!(
+        !stderr
+            .chars()
+            .any(|c| ('\u{0400}'..='\u{04FF}').contains(&c)),
+        "Stderr: {}",
+        stderr
+    );
+}
+
+#[test]
+fn test_split_by_type_writes_file_per_type() {
+    let binary_path = build_and_get_binary();
+    let temp_dir = TempDir::new().unwrap();
+
+    let input_path = temp_dir.path().join("mixed.csv");
+    write_csv_input(
+        &input_path,
+        &[
+            "1001,DEPOSIT,0,501,50000,1672531200000,SUCCESS,\"First\"",
+            "1002,DEPOSIT,0,502,20000,1672534800000,SUCCESS,\"Second\"",
+            "1003,WITHDRAWAL,501,0,1000,1672538400000,PENDING,\"Third\"",
+        ],
+    );
+    let split_dir = temp_dir.path().join("split");
+
+    let output = Command::new(&binary_path)
+        .args([
+            "--input",
+            input_path.to_str().unwrap(),
+            "--input-format",
+            "csv",
+            "--output-format",
+            "csv",
+            "--split-by-type",
+            split_dir.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(
+        output.status.success(),
+        "Stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let header = "TX_ID,TX_TYPE,FROM_USER_ID,TO_USER_ID,AMOUNT,TIMESTAMP,STATUS,DESCRIPTION";
+    for (name, count, tx_type) in [
+        ("deposits.csv", 2, "DEPOSIT"),
+        ("transfers.csv", 0, "TRANSFER"),
+        ("withdrawals.csv", 1, "WITHDRAWAL"),
+    ] {
+        let content = fs::read_to_string(split_dir.join(name)).unwrap();
+        let lines: Vec<&str> = content.lines().collect();
+        assert_eq!(lines[0], header, "{}", name);
+        assert_eq!(lines.len() - 1, count, "{}", name);
+        assert!(
+            lines[1..].iter().all(|line| line.contains(tx_type)),
+            "{}",
+            name
+        );
+    }
+}
+
+#[test]
+fn test_dry_run_does_not_create_output() {
+    let binary_path = build_and_get_binary();
+    let temp_dir = TempDir::new().unwrap();
+
+    let input_path = temp_dir.path().join("input.csv");
+    write_csv_input(
+        &input_path,
+        &[
+            "1001,DEPOSIT,0,501,50000,1672531200000,SUCCESS,\"First\"",
+            "1002,WITHDRAWAL,501,0,1000,1672534800000,PENDING,\"Second\"",
+        ],
+    );
+    let output_path = temp_dir.path().join("output.bin");
+
+    let output = Command::new(&binary_path)
+        .args([
+            "--input",
+            input_path.to_str().unwrap(),
+            "--input-format",
+            "csv",
+            "--output-format",
+            "bin",
+            "--output",
+            output_path.to_str().unwrap(),
+            "--dry-run",
+        ])
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(output.status.code(), Some(0));
+    assert!(!output_path.exists(), "--dry-run не должен создавать файл");
+    assert!(output.stdout.is_empty());
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("Прочитано записей: 2"),
+        "Stderr: {}",
+        stderr
+    );
+    assert!(stderr.contains("Записей к выводу: 2"), "Stderr: {}", stderr);
+    assert!(
+        stderr.contains(&output_path.display().to_string()),
+        "Stderr: {}",
+        stderr
+    );
+}
+
+#[test]
+fn test_audit_log_records_conversion() {
+    use sha2::{Digest, Sha256};
+
+    let binary_path = build_and_get_binary();
+    let temp_dir = TempDir::new().unwrap();
+
+    let input_path = temp_dir.path().join("input.csv");
+    write_csv_input(
+        &input_path,
+        &[
+            "1001,DEPOSIT,0,501,50000,1672531200000,SUCCESS,\"First\"",
+            "1002,WITHDRAWAL,501,0,1000,1672534800000,PENDING,\"Second\"",
+        ],
+    );
+    let output_path = temp_dir.path().join("output.txt");
+    let audit_path = temp_dir.path().join("audit.jsonl");
+
+    for _ in 0..2 {
+        let output = Command::new(&binary_path)
+            .args([
+                "--input",
+                input_path.to_str().unwrap(),
+                "--input-format",
+                "csv",
+                "--output-format",
+                "txt",
+                "--output",
+                output_path.to_str().unwrap(),
+                "--audit-log",
+                audit_path.to_str().unwrap(),
+            ])
+            .output()
+            .expect("Failed to execute command");
+        assert_eq!(output.status.code(), Some(0));
+    }
+
+    let audit = fs::read_to_string(&audit_path).unwrap();
+    let lines: Vec<&str> = audit.lines().collect();
+    assert_eq!(lines.len(), 2, "Каждый запуск дописывает одну строку");
+
+    let entry: serde_json::Value = serde_json::from_str(lines[1]).unwrap();
+    assert_eq!(entry["records"], 2);
+    assert_eq!(entry["input_format"], "csv");
+    assert_eq!(entry["output_format"], "txt");
+    assert_eq!(entry["output"], output_path.display().to_string());
+
+    let hash = entry["sha256"].as_str().unwrap();
+    assert!(!hash.is_empty());
+    let expected = format!("{:x}", Sha256::digest(fs::read(&output_path).unwrap()));
+    assert_eq!(hash, expected);
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_audit_log_skipped_when_output_write_fails() {
+    let binary_path = build_and_get_binary();
+    let temp_dir = TempDir::new().unwrap();
+
+    let input_path = temp_dir.path().join("input.csv");
+    write_csv_input(
+        &input_path,
+        &["1001,DEPOSIT,0,501,50000,1672531200000,SUCCESS,\"First\""],
+    );
+    let audit_path = temp_dir.path().join("audit.jsonl");
+
+    for output_format in ["csv", "txt"] {
+        // /dev/full принимает открытие, но любая запись завершается ENOSPC
+        let output = Command::new(&binary_path)
+            .args([
+                "--input",
+                input_path.to_str().unwrap(),
+                "--input-format",
+                "csv",
+                "--output-format",
+                output_format,
+                "--output",
+                "/dev/full",
+                "--audit-log",
+                audit_path.to_str().unwrap(),
+            ])
+            .output()
+            .expect("Failed to execute command");
+        assert!(!output.status.success(), "{}: {:?}", output_format, output);
+    }
+
+    assert!(!audit_path.exists(), "Строка аудита записана без вывода");
+}
+
+#[test]
+fn test_progress_file_ends_with_final_record() {
+    let binary_path = build_and_get_binary();
+    let temp_dir = TempDir::new().unwrap();
+
+    let input_path = temp_dir.path().join("input.csv");
+    write_csv_input(
+        &input_path,
+        &[
+            "1001,DEPOSIT,0,501,50000,1672531200000,SUCCESS,\"First\"",
+            "1002,WITHDRAWAL,501,0,1000,1672534800000,PENDING,\"Second\"",
+            "1003,TRANSFER,501,502,2500,1672538400000,FAILURE,\"Third\"",
+        ],
+    );
+    let output_path = temp_dir.path().join("output.bin");
+    let progress_path = temp_dir.path().join("progress.jsonl");
+
+    let output = Command::new(&binary_path)
+        .args([
+            "--input",
+            input_path.to_str().unwrap(),
+            "--input-format",
+            "csv",
+            "--output-format",
+            "bin",
+            "--output",
+            output_path.to_str().unwrap(),
+            "--progress-file",
+            progress_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+    assert!(output.status.success(), "Command failed: {:?}", output);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        !stderr.contains("\"phase\""),
+        "Прогресс не должен попадать в stderr"
+    );
+
+    let progress = fs::read_to_string(&progress_path).unwrap();
+    let last: serde_json::Value = serde_json::from_str(progress.lines().last().unwrap()).unwrap();
+    assert_eq!(last["phase"], "done");
+    assert_eq!(last["records"], 3);
+    assert_eq!(last["bytes"], fs::metadata(&output_path).unwrap().len());
+    assert!(last["elapsed_ms"].is_u64());
+}
+
+#[test]
+fn test_input_dir_converts_each_file() {
+    let binary_path = build_and_get_binary();
+    let temp_dir = TempDir::new().unwrap();
+
+    let input_dir = temp_dir.path().join("in");
+    fs::create_dir(&input_dir).unwrap();
+    write_csv_input(
+        &input_dir.join("january.csv"),
+        &["1001,DEPOSIT,0,501,50000,1672531200000,SUCCESS,\"First\""],
+    );
+    write_csv_input(
+        &input_dir.join("february.csv"),
+        &[
+            "2001,DEPOSIT,0,502,1000,1675209600000,SUCCESS,\"One\"",
+            "2002,WITHDRAWAL,502,0,500,1675213200000,PENDING,\"Two\"",
+        ],
+    );
+    fs::write(input_dir.join("notes.txt"), "не CSV").unwrap();
+    let output_dir = temp_dir.path().join("out");
+
+    let output = Command::new(&binary_path)
+        .args([
+            "--input-dir",
+            input_dir.to_str().unwrap(),
+            "--input-format",
+            "csv",
+            "--output-dir",
+            output_dir.to_str().unwrap(),
+            "--output-format",
+            "bin",
+        ])
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "Stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let mut produced: Vec<String> = fs::read_dir(&output_dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+        .collect();
+    produced.sort();
+    assert_eq!(produced, vec!["february.bin", "january.bin"]);
+
+    let february = parser_lib::BinaryParser::parse_records(
+        File::open(output_dir.join("february.bin")).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(february.len(), 2);
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("Сконвертировано файлов: 2, с ошибками: 0"),
+        "Stdout: {}",
+        stdout
+    );
+}
+
+#[test]
+fn test_input_dir_continues_past_failures_unless_fail_fast() {
+    let binary_path = build_and_get_binary();
+    let temp_dir = TempDir::new().unwrap();
+
+    let input_dir = temp_dir.path().join("in");
+    fs::create_dir(&input_dir).unwrap();
+    fs::write(input_dir.join("a_broken.csv"), "NOT,A,HEADER\n").unwrap();
+    write_csv_input(
+        &input_dir.join("b_good.csv"),
+        &["1001,DEPOSIT,0,501,50000,1672531200000,SUCCESS,\"First\""],
+    );
+
+    let run = |output_dir: &std::path::Path, fail_fast: bool| {
+        let mut command = Command::new(&binary_path);
+        command.args([
+            "--input-dir",
+            input_dir.to_str().unwrap(),
+            "--input-format",
+            "csv",
+            "--output-dir",
+            output_dir.to_str().unwrap(),
+            "--output-format",
+            "txt",
+        ]);
+        if fail_fast {
+            command.arg("--fail-fast");
+        }
+        command.output().expect("Failed to execute command")
+    };
+
+    let output_dir = temp_dir.path().join("out");
+    let output = run(&output_dir, false);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(output_dir.join("b_good.txt").exists());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("a_broken.csv"), "Stderr: {}", stderr);
+
+    let fail_fast_dir = temp_dir.path().join("out_fail_fast");
+    let output = run(&fail_fast_dir, true);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(!fail_fast_dir.join("b_good.txt").exists());
+}
+
+#[test]
+fn test_count_by_status_prints_counts() {
+    let binary_path = build_and_get_binary();
+    let temp_dir = TempDir::new().unwrap();
+    let input_path = temp_dir.path().join("mixed.csv");
+    write_csv_input(
+        &input_path,
+        &[
+            "1001,DEPOSIT,0,501,50000,1672531200000,SUCCESS,\"One\"",
+            "1002,TRANSFER,501,502,1000,1672531300000,FAILURE,\"Two\"",
+            "1003,WITHDRAWAL,502,0,500,1672531400000,FAILURE,\"Three\"",
+            "1004,DEPOSIT,0,503,700,1672531500000,SUCCESS,\"Four\"",
+            "1005,DEPOSIT,0,504,900,1672531600000,SUCCESS,\"Five\"",
+        ],
+    );
+
+    let output = Command::new(&binary_path)
+        .args([
+            "--input",
+            input_path.to_str().unwrap(),
+            "--input-format",
+            "csv",
+            "--output-format",
+            "csv",
+            "--count-by-status",
+        ])
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "SUCCESS: 3\nFAILURE: 2\nPENDING: 0\nREVERSED: 0\n"
+    );
+}
+
+#[test]
+fn test_text_b64_desc_roundtrip() {
+    let binary_path = build_and_get_binary();
+    let temp_dir = TempDir::new().unwrap();
+    let input_path = temp_dir.path().join("input.csv");
+    let output_path = temp_dir.path().join("output.txt");
+    write_csv_input(
+        &input_path,
+        &["1001,DEPOSIT,0,501,50000,1672531200000,SUCCESS,\"Quoted \"\"text\"\", commas\""],
+    );
+
+    let output = Command::new(&binary_path)
+        .args([
+            "--input",
+            input_path.to_str().unwrap(),
+            "--input-format",
+            "csv",
+            "--output-format",
+            "txt",
+            "--output",
+            output_path.to_str().unwrap(),
+            "--text-b64-desc",
+        ])
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success(), "Command failed: {:?}", output);
+    let text = fs::read_to_string(&output_path).unwrap();
+    assert!(text.contains("DESCRIPTION_B64: "));
+
+    let parsed = parser_lib::TextParser::parse_records(text.as_bytes()).unwrap();
+    assert_eq!(parsed[0].description, "Quoted \"text\", commas");
+}
+
+#[test]
+fn test_footer_reports_record_count() {
+    let binary_path = build_and_get_binary();
+    let temp_dir = TempDir::new().unwrap();
+    let input_path = temp_dir.path().join("input.csv");
+    let output_path = temp_dir.path().join("output.txt");
+    write_csv_input(
+        &input_path,
+        &[
+            "1001,DEPOSIT,0,501,50000,1672531200000,SUCCESS,\"First\"",
+            "1002,WITHDRAWAL,501,0,1000,1672531300000,SUCCESS,\"Second\"",
+        ],
+    );
+
+    let output = Command::new(&binary_path)
+        .args([
+            "--input",
+            input_path.to_str().unwrap(),
+            "--input-format",
+            "csv",
+            "--output-format",
+            "txt",
+            "--output",
+            output_path.to_str().unwrap(),
+            "--footer",
+        ])
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success(), "Command failed: {:?}", output);
+    let text = fs::read_to_string(&output_path).unwrap();
+    assert_eq!(text.lines().last(), Some("# Total: 2 records"));
+
+    let parsed = parser_lib::TextParser::parse_records(text.as_bytes()).unwrap();
+    assert_eq!(parsed.len(), 2);
+}
+
+#[test]
+fn test_template_renders_pipe_delimited_lines() {
+    let binary_path = build_and_get_binary();
+    let temp_dir = TempDir::new().unwrap();
+    let input_path = temp_dir.path().join("input.csv");
+    let output_path = temp_dir.path().join("output.psv");
+    write_csv_input(
+        &input_path,
+        &[
+            "1001,DEPOSIT,0,501,50000,1672531200000,SUCCESS,\"First\"",
+            "1002,WITHDRAWAL,501,0,1005,1672531300000,PENDING,\"Second\"",
+        ],
+    );
+
+    let output = Command::new(&binary_path)
+        .args([
+            "--input",
+            input_path.to_str().unwrap(),
+            "--input-format",
+            "csv",
+            "--output",
+            output_path.to_str().unwrap(),
+            "--template",
+            "{tx_id}|{tx_type}|{amount_decimal}|{status}|{ts_iso}",
+        ])
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success(), "Command failed: {:?}", output);
+    assert_eq!(
+        fs::read_to_string(&output_path).unwrap(),
+        "1001|DEPOSIT|500.00|SUCCESS|2023-01-01T00:00:00.000Z\n\
+         1002|WITHDRAWAL|10.05|PENDING|2023-01-01T00:01:40.000Z\n"
+    );
+
+    let output = Command::new(&binary_path)
+        .args([
+            "--input",
+            input_path.to_str().unwrap(),
+            "--input-format",
+            "csv",
+            "--template",
+            "{tx_id}|{fee}",
+        ])
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Unknown placeholder '{fee}'"));
+}
+
+#[test]
+fn test_trim_descriptions_is_opt_in() {
+    let binary_path = build_and_get_binary();
+    let temp_dir = TempDir::new().unwrap();
+    let input_path = temp_dir.path().join("input.csv");
+    write_csv_input(
+        &input_path,
+        &["1001,DEPOSIT,0,501,50000,1672531200000,SUCCESS,\"  x  \""],
+    );
+
+    let convert = |extra: &[&str]| {
+        let output = Command::new(&binary_path)
+            .args([
+                "--input",
+                input_path.to_str().unwrap(),
+                "--input-format",
+                "csv",
+                "--output-format",
+                "txt",
+            ])
+            .args(extra)
+            .output()
+            .expect("Failed to execute command");
+        assert!(output.status.success(), "Command failed: {:?}", output);
+        String::from_utf8_lossy(&output.stdout).to_string()
+    };
+
+    assert!(convert(&[]).contains("DESCRIPTION: \"  x  \""));
+    assert!(convert(&["--trim-descriptions"]).contains("DESCRIPTION: \"x\""));
+}
+
+#[test]
+fn test_repair_binary_fixes_size_header() {
+    let binary_path = build_and_get_binary();
+    let temp_dir = TempDir::new().unwrap();
+    let input_path = temp_dir.path().join("broken.bin");
+    let output_path = temp_dir.path().join("fixed.bin");
+
+    let transactions = parser_lib::generate_sample(4, 3);
+    let mut data = Vec::new();
+    parser_lib::BinaryParser::write_records(&transactions, &mut data).unwrap();
+    let size = u32::from_be_bytes(data[4..8].try_into().unwrap());
+    data[4..8].copy_from_slice(&(size + 1).to_be_bytes());
+    fs::write(&input_path, &data).unwrap();
+    assert!(parser_lib::BinaryParser::parse_records(data.as_slice()).is_err());
+
+    let output = Command::new(&binary_path)
+        .args([
+            "--input",
+            input_path.to_str().unwrap(),
+            "--input-format",
+            "bin",
+            "--output-format",
+            "bin",
+            "--output",
+            output_path.to_str().unwrap(),
+            "--repair-binary",
+            "--lang",
+            "en",
+        ])
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success(), "Command failed: {:?}", output);
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Record size headers corrected: 1"));
+
+    let repaired = fs::read(&output_path).unwrap();
+    let parsed = parser_lib::BinaryParser::parse_records(repaired.as_slice()).unwrap();
+    assert_eq!(parsed, transactions);
+}
+
+#[test]
+fn test_canonicalize_repairs_binary_input() {
+    let binary_path = build_and_get_binary();
+    let temp_dir = TempDir::new().unwrap();
+    let input_path = temp_dir.path().join("input.bin");
+
+    let transaction = parser_lib::Transaction {
+        tx_id: 1001,
+        tx_type: parser_lib::TransactionType::Deposit,
+        from_user_id: 42,
+        to_user_id: 501,
+        amount: -50000,
+        timestamp: 1672531200000,
+        status: parser_lib::TransactionStatus::Success,
+        description: "Stray sender".to_string(),
+        currency: None,
+    };
+    let mut file = File::create(&input_path).unwrap();
+    parser_lib::BinaryParser::write_records(&[transaction], &mut file).unwrap();
+    drop(file);
+
+    let output = Command::new(&binary_path)
+        .args([
+            "--input",
+            input_path.to_str().unwrap(),
+            "--input-format",
+            "bin",
+            "--output-format",
+            "csv",
+            "--canonicalize",
+        ])
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success(), "Command failed: {:?}", output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("1001,DEPOSIT,0,501,50000,1672531200000,SUCCESS"));
+}
+
+#[test]
+fn test_verify_hash_on_passthrough_copy() {
+    use sha2::{Digest, Sha256};
+
+    let binary_path = build_and_get_binary();
+    let temp_dir = TempDir::new().unwrap();
+    let input_path = temp_dir.path().join("input.bin");
+    let output_path = temp_dir.path().join("output.bin");
+
+    let transaction = parser_lib::Transaction {
+        tx_id: 1001,
+        tx_type: parser_lib::TransactionType::Deposit,
+        from_user_id: 0,
+        to_user_id: 501,
+        amount: 50000,
+        timestamp: 1672531200000,
+        status: parser_lib::TransactionStatus::Success,
+        description: "Initial deposit".to_string(),
+        currency: None,
+    };
+    let mut file = File::create(&input_path).unwrap();
+    parser_lib::BinaryParser::write_records(&[transaction], &mut file).unwrap();
+    drop(file);
+
+    let expected = format!("{:x}", Sha256::digest(fs::read(&input_path).unwrap()));
+    let run = |hash: &str| {
+        Command::new(&binary_path)
+            .args([
+                "--input",
+                input_path.to_str().unwrap(),
+                "--input-format",
+                "bin",
+                "--output-format",
+                "bin",
+                "--output",
+                output_path.to_str().unwrap(),
+                "--verify-hash",
+                hash,
+            ])
+            .output()
+            .expect("Failed to execute command")
+    };
+
+    let output = run(&expected.to_uppercase());
+    assert!(output.status.success(), "Command failed: {:?}", output);
+
+    let output = run(&"0".repeat(64));
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains(&expected), "stderr: {}", stderr);
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_failed_flush_is_reported_before_verify_hash() {
+    let binary_path = build_and_get_binary();
+    let temp_dir = TempDir::new().unwrap();
+    let csv_path = temp_dir.path().join("input.csv");
+    write_csv_input(
+        &csv_path,
+        &["1001,DEPOSIT,0,501,100,1672531200000,SUCCESS,\"One\""],
+    );
+
+    for output_format in ["txt", "bin"] {
+        let mut expected = Vec::new();
+        let transactions =
+            parser_lib::CsvParser::parse_records(File::open(&csv_path).unwrap()).unwrap();
+        parser_lib::Format::from_extension(output_format)
+            .unwrap()
+            .write_records(&transactions, &mut expected)
+            .unwrap();
+        let hash = {
+            use sha2::{Digest, Sha256};
+            format!("{:x}", Sha256::digest(&expected))
+        };
+
+        // /dev/full принимает открытие, но любая запись завершается ENOSPC
+        let output = Command::new(&binary_path)
+            .args([
+                "--input",
+                csv_path.to_str().unwrap(),
+                "--input-format",
+                "csv",
+                "--output-format",
+                output_format,
+                "--output",
+                "/dev/full",
+                "--verify-hash",
+                &hash,
+            ])
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(!output.status.success(), "{}: {:?}", output_format, output);
+    }
+}
+
+#[test]
+fn test_explain_prints_plan_without_writing() {
+    let binary_path = build_and_get_binary();
+    let temp_dir = TempDir::new().unwrap();
+    let csv_path = temp_dir.path().join("input.csv");
+    let output_path = temp_dir.path().join("output.bin");
+    write_csv_input(
+        &csv_path,
+        &["1001,DEPOSIT,0,501,100,1672531200000,SUCCESS,\" One \""],
+    );
+
+    let output = Command::new(&binary_path)
+        .args([
+            "--input",
+            csv_path.to_str().unwrap(),
+            "--input-format",
+            "csv",
+            "--output-format",
+            "bin",
+            "--output",
+            output_path.to_str().unwrap(),
+            "--trim-descriptions",
+            "--explain",
+            "--lang",
+            "en",
+        ])
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success(), "Command failed: {:?}", output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Processing plan:"), "Stdout: {}", stdout);
+    assert!(
+        stdout.contains("3. transform: trim descriptions"),
+        "Stdout: {}",
+        stdout
+    );
+    assert!(stdout.contains("as Bin"), "Stdout: {}", stdout);
+    assert!(!output_path.exists(), "--explain не должен создавать файл");
+}
+
+#[test]
+fn test_eol_crlf_output_reparses_identically() {
+    let binary_path = build_and_get_binary();
+    let temp_dir = TempDir::new().unwrap();
+    let csv_path = temp_dir.path().join("input.csv");
+    write_csv_input(
+        &csv_path,
+        &[
+            "1001,DEPOSIT,0,501,100,1672531200000,SUCCESS,\"One\"",
+            "1002,TRANSFER,501,502,50,1672531201000,PENDING,\"Two\"",
+        ],
+    );
+    let original = parser_lib::CsvParser::parse_records(File::open(&csv_path).unwrap()).unwrap();
+
+    for format in ["csv", "txt"] {
+        let output = Command::new(&binary_path)
+            .args([
+                "--input",
+                csv_path.to_str().unwrap(),
+                "--input-format",
+                "csv",
+                "--output-format",
+                format,
+                "--eol",
+                "crlf",
+            ])
+            .output()
+            .expect("Failed to execute command");
+
+        assert!(output.status.success(), "Command failed: {:?}", output);
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        assert!(stdout.contains("\r\n"), "{}: {:?}", format, stdout);
+        assert_eq!(
+            stdout.matches('\n').count(),
+            stdout.matches("\r\n").count(),
+            "{}: bare LF in {:?}",
+            format,
+            stdout
+        );
+
+        let reparsed = match format {
+            "csv" => parser_lib::CsvParser::parse_records(stdout.as_bytes()),
+            _ => parser_lib::TextParser::parse_records(stdout.as_bytes()),
+        }
+        .unwrap();
+        assert_eq!(reparsed, original, "{}", format);
+    }
+}
+
+#[test]
+fn test_between_selects_half_open_window() {
+    let binary_path = build_and_get_binary();
+    let temp_dir = TempDir::new().unwrap();
+    let csv_path = temp_dir.path().join("input.csv");
+    write_csv_input(
+        &csv_path,
+        &[
+            "1001,DEPOSIT,0,501,100,1672531199999,SUCCESS,\"Before\"",
+            "1002,DEPOSIT,0,501,100,1672531200000,SUCCESS,\"Start\"",
+            "1003,DEPOSIT,0,501,100,1672531259999,SUCCESS,\"Inside\"",
+            "1004,DEPOSIT,0,501,100,1672531260000,SUCCESS,\"End\"",
+        ],
+    );
+
+    let output = Command::new(&binary_path)
+        .args([
+            "--input",
+            csv_path.to_str().unwrap(),
+            "--input-format",
+            "csv",
+            "--output-format",
+            "csv",
+            "--between",
+            "1672531200000,1672531260000",
+        ])
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success(), "Command failed: {:?}", output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let ids: Vec<&str> = stdout
+        .lines()
+        .skip(1)
+        .map(|line| line.split(',').next().unwrap())
+        .collect();
+    assert_eq!(ids, vec!["1002", "1003"]);
+
+    let output = Command::new(&binary_path)
+        .args([
+            "--input",
+            csv_path.to_str().unwrap(),
+            "--input-format",
+            "csv",
+            "--output-format",
+            "csv",
+            "--between",
+            "2000,1000",
+        ])
+        .output()
+        .expect("Failed to execute command");
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_csv_map_translates_nonstandard_csv() {
+    let binary_path = build_and_get_binary();
+    let temp_dir = TempDir::new().unwrap();
+    let csv_path = temp_dir.path().join("bank_export.csv");
+    let map_path = temp_dir.path().join("map.json");
+
+    fs::write(
+        &csv_path,
+        "ID,KIND,SRC,DST,SUM,TS,STATE,MEMO\n\
+         1001,CR,0,501,100,1672531200000,OK,\"Salary\"\n\
+         1002,XFER,501,502,50,1672531201000,WAIT,\"Rent\"\n",
+    )
+    .unwrap();
+    fs::write(
+        &map_path,
+        r#"{
+            "columns": {
+                "ID": "TX_ID", "KIND": "TX_TYPE", "SRC": "FROM_USER_ID", "DST": "TO_USER_ID",
+                "SUM": "AMOUNT", "TS": "TIMESTAMP", "STATE": "STATUS", "MEMO": "DESCRIPTION"
+            },
+            "types": { "CR": "DEPOSIT", "XFER": "TRANSFER" },
+            "statuses": { "OK": "SUCCESS", "WAIT": "PENDING" }
+        }"#,
+    )
+    .unwrap();
+
+    let run = |map: &std::path::Path| {
+        Command::new(&binary_path)
+            .args([
+                "--input",
+                csv_path.to_str().unwrap(),
+                "--input-format",
+                "csv",
+                "--output-format",
+                "csv",
+                "--csv-map",
+                map.to_str().unwrap(),
+            ])
+            .output()
+            .expect("Failed to execute command")
+    };
+
+    let output = run(&map_path);
+    assert!(output.status.success(), "Command failed: {:?}", output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.starts_with("TX_ID,TX_TYPE,FROM_USER_ID"),
+        "{}",
+        stdout
+    );
+    assert!(stdout.contains("1001,DEPOSIT,0,501,100,1672531200000,SUCCESS,\"Salary\""));
+    assert!(stdout.contains("1002,TRANSFER,501,502,50,1672531201000,PENDING,\"Rent\""));
+
+    let partial = fs::read_to_string(&map_path)
+        .unwrap()
+        .replace(r#""XFER": "TRANSFER""#, r#""XF": "TRANSFER""#);
+    fs::write(&map_path, partial).unwrap();
+    let output = run(&map_path);
+    assert!(!output.status.success(), "unmapped XFER must still fail");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("XFER"), "Stderr: {}", stderr);
+}
+
+#[test]
+fn test_emit_header_only_without_input() {
+    let binary_path = build_and_get_binary();
+
+    let output = Command::new(&binary_path)
+        .arg("--emit-header-only")
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success(), "Command failed: {:?}", output);
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "TX_ID,TX_TYPE,FROM_USER_ID,TO_USER_ID,AMOUNT,TIMESTAMP,STATUS,DESCRIPTION\n"
+    );
+
+    let output = Command::new(&binary_path)
+        .args(["--emit-header-only", "--output-format", "txt"])
+        .output()
+        .expect("Failed to execute command");
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_input_list_concatenates_listed_files() {
+    let binary_path = build_and_get_binary();
+    let temp_dir = TempDir::new().unwrap();
+
+    let data_dir = temp_dir.path().join("data");
+    fs::create_dir(&data_dir).unwrap();
+    write_csv_input(
+        &data_dir.join("first.csv"),
+        &["1001,DEPOSIT,0,501,50000,1672531200000,SUCCESS,\"First\""],
+    );
+    write_csv_input(
+        &data_dir.join("second.csv"),
+        &[
+            "2001,DEPOSIT,0,502,1000,1675209600000,SUCCESS,\"One\"",
+            "2002,WITHDRAWAL,502,0,500,1675213200000,PENDING,\"Two\"",
+        ],
+    );
+    let list_path = temp_dir.path().join("inputs.list");
+    fs::write(
+        &list_path,
+        "# входные файлы\ndata/first.csv\n\ndata/second.csv csv\n",
+    )
+    .unwrap();
+    let output_path = temp_dir.path().join("all.txt");
+
+    let output = Command::new(&binary_path)
+        .args([
+            "--input-list",
+            list_path.to_str().unwrap(),
+            "--input-format",
+            "csv",
+            "--output-format",
+            "txt",
+            "--output",
+            output_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+    assert!(output.status.success(), "Статус: {:?}", output);
+
+    let records = parser_lib::TextParser::parse_records(File::open(&output_path).unwrap()).unwrap();
+    let ids: Vec<u64> = records.iter().map(|tx| tx.tx_id).collect();
+    assert_eq!(ids, vec![1001, 2001, 2002]);
+
+    fs::write(&list_path, "data/first.csv\ndata/missing.csv\n").unwrap();
+    let output = Command::new(&binary_path)
+        .args([
+            "--input-list",
+            list_path.to_str().unwrap(),
+            "--input-format",
+            "csv",
+            "--output-format",
+            "txt",
+            "--lang",
+            "en",
+        ])
+        .output()
+        .expect("Failed to execute command");
+    assert!(!output.status.success(), "Статус: {:?}", output);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("missing.csv"), "{}", stderr);
+}
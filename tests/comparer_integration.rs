@@ -297,6 +297,36 @@ fn test_comparer_missing_file_exit_code_1() {
     );
 }
 
+#[test]
+fn test_comparer_missing_file_in_english() {
+    let binary_path = build_and_get_binary("comparer");
+
+    let output = Command::new(&binary_path)
+        .args([
+            "--file1",
+            "non_existent1.csv",
+            "--format1",
+            "csv",
+            "--file2",
+            "non_existent2.csv",
+            "--format2",
+            "csv",
+            "--lang",
+            "en",
+        ])
+        .output()
+        .expect("Failed to execute command");
+
+    assert_eq!(output.status.code(), Some(1));
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("Error: file 'non_existent1.csv' not found"),
+        "Stderr: {}",
+        stderr
+    );
+}
+
 #[test]
 fn test_comparer_ignore_description_exit_code_0() {
     let binary_path = build_and_get_binary("comparer");
@@ -435,6 +465,111 @@ fn test_comparer_ignore_status_exit_code_0() {
     );
 }
 
+#[test]
+fn test_comparer_ignore_field_list_exit_code_0() {
+    let binary_path = build_and_get_binary("comparer");
+    let temp_dir = TempDir::new().unwrap();
+
+    let csv1_path = temp_dir.path().join("file1.csv");
+    let csv2_path = temp_dir.path().join("file2.csv");
+
+    let csv1_content = "TX_ID,TX_TYPE,FROM_USER_ID,TO_USER_ID,AMOUNT,TIMESTAMP,STATUS,DESCRIPTION\n\
+                        1001,TRANSFER,501,502,50000,1672531200000,SUCCESS,\"Test\"";
+
+    let csv2_content = "TX_ID,TX_TYPE,FROM_USER_ID,TO_USER_ID,AMOUNT,TIMESTAMP,STATUS,DESCRIPTION\n\
+                        1001,TRANSFER,777,502,50000,1672538400000,SUCCESS,\"Test\"";
+
+    fs::write(&csv1_path, csv1_content).unwrap();
+    fs::write(&csv2_path, csv2_content).unwrap();
+
+    let run = |ignore: Option<&str>| {
+        let mut command = Command::new(&binary_path);
+        command.args([
+            "--file1",
+            csv1_path.to_str().unwrap(),
+            "--format1",
+            "csv",
+            "--file2",
+            csv2_path.to_str().unwrap(),
+            "--format2",
+            "csv",
+        ]);
+        if let Some(fields) = ignore {
+            command.args(["--ignore", fields]);
+        }
+        command.output().expect("Failed to execute command")
+    };
+
+    let output = run(Some("timestamp,from_user_id"));
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "С --ignore timestamp,from_user_id файлы должны считаться идентичными. Статус: {:?}",
+        output.status
+    );
+
+    let output = run(Some("timestamp"));
+    assert_eq!(
+        output.status.code(),
+        Some(2),
+        "Различие в FROM_USER_ID должно учитываться. Статус: {:?}",
+        output.status
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("FROM_USER_ID"));
+    assert!(!stdout.contains("TIMESTAMP"));
+}
+
+#[test]
+fn test_comparer_sort_before_compare_exit_code_0() {
+    let binary_path = build_and_get_binary("comparer");
+    let temp_dir = TempDir::new().unwrap();
+
+    let csv_path = temp_dir.path().join("file1.csv");
+    let txt_path = temp_dir.path().join("file2.txt");
+
+    let csv_content = "TX_ID,TX_TYPE,FROM_USER_ID,TO_USER_ID,AMOUNT,TIMESTAMP,STATUS,DESCRIPTION\n\
+                       1001,DEPOSIT,0,501,50000,1672531200000,SUCCESS,\"First\"\n\
+                       1002,TRANSFER,501,502,15000,1672534800000,FAILURE,\"Second\"";
+
+    let txt_content = "TX_ID: 1002\nTX_TYPE: TRANSFER\nFROM_USER_ID: 501\nTO_USER_ID: 502\n\
+                       AMOUNT: 15000\nTIMESTAMP: 1672534800000\nSTATUS: FAILURE\nDESCRIPTION: \"Second\"\n\n\
+                       TX_ID: 1001\nTX_TYPE: DEPOSIT\nFROM_USER_ID: 0\nTO_USER_ID: 501\n\
+                       AMOUNT: 50000\nTIMESTAMP: 1672531200000\nSTATUS: SUCCESS\nDESCRIPTION: \"First\"\n";
+
+    fs::write(&csv_path, csv_content).unwrap();
+    fs::write(&txt_path, txt_content).unwrap();
+
+    let run = |sort: bool| {
+        let mut command = Command::new(&binary_path);
+        command.args([
+            "--file1",
+            csv_path.to_str().unwrap(),
+            "--format1",
+            "csv",
+            "--file2",
+            txt_path.to_str().unwrap(),
+            "--format2",
+            "txt",
+        ]);
+        if sort {
+            command.arg("--sort-before-compare");
+        }
+        command.output().expect("Failed to execute command")
+    };
+
+    let output = run(true);
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "С --sort-before-compare переупорядоченные файлы должны совпасть. Stdout: {}",
+        String::from_utf8_lossy(&output.stdout)
+    );
+
+    let output = run(false);
+    assert_eq!(output.status.code(), Some(2));
+}
+
 #[test]
 fn test_comparer_empty_files_exit_code_0() {
     let binary_path = build_and_get_binary("comparer");
@@ -578,3 +713,107 @@ fn test_comparer_verbose_output() {
         "Должна быть статистика в verbose режиме"
     );
 }
+
+#[test]
+fn test_comparer_infers_formats_from_extensions() {
+    let binary_path = build_and_get_binary("comparer");
+    let temp_dir = TempDir::new().unwrap();
+
+    let csv_path = temp_dir.path().join("file1.csv");
+    let bin_path = temp_dir.path().join("file2.bin");
+    let records = vec![parser_lib::Transaction {
+        tx_id: 1001,
+        tx_type: parser_lib::TransactionType::Deposit,
+        from_user_id: 0,
+        to_user_id: 501,
+        amount: 50000,
+        timestamp: 1672531200000,
+        status: parser_lib::TransactionStatus::Success,
+        description: "Test".to_string(),
+        currency: None,
+    }];
+    parser_lib::write_file(&csv_path, &records).unwrap();
+    parser_lib::write_file(&bin_path, &records).unwrap();
+
+    let output = Command::new(&binary_path)
+        .args([
+            "--file1",
+            csv_path.to_str().unwrap(),
+            "--file2",
+            bin_path.to_str().unwrap(),
+        ])
+        .output()
+        .expect("Failed to execute command");
+    assert_eq!(output.status.code(), Some(0), "Статус: {:?}", output);
+
+    let unknown_path = temp_dir.path().join("file2.dat");
+    fs::write(&unknown_path, "").unwrap();
+    let output = Command::new(&binary_path)
+        .args([
+            "--file1",
+            csv_path.to_str().unwrap(),
+            "--file2",
+            unknown_path.to_str().unwrap(),
+            "--lang",
+            "en",
+        ])
+        .output()
+        .expect("Failed to execute command");
+    assert_eq!(output.status.code(), Some(1), "Статус: {:?}", output);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("cannot infer the format"), "{}", stderr);
+}
+
+#[test]
+fn test_comparer_byte_compare() {
+    let binary_path = build_and_get_binary("comparer");
+    let temp_dir = TempDir::new().unwrap();
+
+    let record = |description: &str| parser_lib::Transaction {
+        tx_id: 1001,
+        tx_type: parser_lib::TransactionType::Deposit,
+        from_user_id: 0,
+        to_user_id: 501,
+        amount: 50000,
+        timestamp: 1672531200000,
+        status: parser_lib::TransactionStatus::Success,
+        description: description.to_string(),
+        currency: None,
+    };
+    let run = |file1: &PathBuf, file2: &PathBuf| {
+        Command::new(&binary_path)
+            .args([
+                "--file1",
+                file1.to_str().unwrap(),
+                "--file2",
+                file2.to_str().unwrap(),
+                "--byte-compare",
+                "--lang",
+                "en",
+            ])
+            .output()
+            .expect("Failed to execute command")
+    };
+
+    // Побайтово одинаковые файлы не разбираются: даже повреждённые совпадают
+    let broken1 = temp_dir.path().join("broken1.bin");
+    let broken2 = temp_dir.path().join("broken2.bin");
+    fs::write(&broken1, b"not a binary file").unwrap();
+    fs::write(&broken2, b"not a binary file").unwrap();
+    let output = run(&broken1, &broken2);
+    assert_eq!(output.status.code(), Some(0), "Статус: {:?}", output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("are byte-identical"), "{}", stdout);
+
+    // Пробелы вокруг описания убираются при чтении: байты различаются, записи равны
+    let plain = temp_dir.path().join("plain.bin");
+    let padded = temp_dir.path().join("padded.bin");
+    parser_lib::write_file(&plain, &[record("Test")]).unwrap();
+    parser_lib::write_file(&padded, &[record("  Test  ")]).unwrap();
+    let output = run(&plain, &padded);
+    assert_eq!(output.status.code(), Some(0), "Статус: {:?}", output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("differ byte-for-byte"), "{}", stdout);
+    assert!(stdout.contains("are identical"), "{}", stdout);
+    assert!(!stdout.contains("byte-identical"), "{}", stdout);
+}
@@ -0,0 +1,108 @@
+use parser_lib::{BinaryParser, Transaction, TransactionStatus, TransactionType};
+use std::fs::{self, File};
+use std::path::PathBuf;
+use std::process::Command;
+use tempfile::TempDir;
+
+fn build_and_get_binary(binary_name: &str) -> PathBuf {
+    let manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+
+    let build_status = Command::new("cargo")
+        .args(["build", "--bin", binary_name, "--quiet"])
+        .status()
+        .expect("Failed to build binary");
+
+    assert!(build_status.success(), "Failed to build {}", binary_name);
+
+    let mut binary_path = manifest_dir.join("target").join("debug").join(binary_name);
+
+    if cfg!(windows) {
+        binary_path.set_extension("exe");
+    }
+
+    assert!(
+        binary_path.exists(),
+        "Binary not found at {:?}",
+        binary_path
+    );
+    binary_path
+}
+
+fn create_transaction(tx_id: u64, description: &str) -> Transaction {
+    Transaction {
+        tx_id,
+        tx_type: TransactionType::Deposit,
+        from_user_id: 0,
+        to_user_id: 501,
+        amount: 50000,
+        timestamp: 1672531200000,
+        status: TransactionStatus::Success,
+        description: description.to_string(),
+        currency: None,
+    }
+}
+
+#[test]
+fn test_inspect_two_record_file_offsets() {
+    let binary_path = build_and_get_binary("inspect");
+    let temp_dir = TempDir::new().unwrap();
+    let bin_path = temp_dir.path().join("two.bin");
+
+    let transactions = vec![
+        create_transaction(1001, "First"),
+        create_transaction(1002, "Second"),
+    ];
+    let mut file = File::create(&bin_path).unwrap();
+    BinaryParser::write_records(&transactions, &mut file).unwrap();
+    drop(file);
+
+    let output = Command::new(&binary_path)
+        .args(["--input", bin_path.to_str().unwrap()])
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    // 4 байта магии + 4 байта размера + 46 байт полей + 5 байт "First"
+    assert!(
+        stdout.contains("Запись 1: смещение 0 (0x00000000)"),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains("Запись 2: смещение 59 (0x0000003b)"),
+        "{}",
+        stdout
+    );
+    assert!(stdout.contains("заявлен 51, вычислен 51"), "{}", stdout);
+    assert!(stdout.contains("заявлен 52, вычислен 52"), "{}", stdout);
+    assert!(stdout.contains("TX_ID: 1002"), "{}", stdout);
+    assert!(stdout.contains("00000000  59 50 42 4e"), "{}", stdout);
+    assert!(stdout.contains("Всего записей: 2"), "{}", stdout);
+}
+
+#[test]
+fn test_inspect_dumps_bytes_around_failure() {
+    let binary_path = build_and_get_binary("inspect");
+    let temp_dir = TempDir::new().unwrap();
+    let bin_path = temp_dir.path().join("broken.bin");
+
+    let mut data = Vec::new();
+    BinaryParser::write_records(&[create_transaction(1001, "First")], &mut data).unwrap();
+    data.extend_from_slice(b"XXXX");
+    fs::write(&bin_path, &data).unwrap();
+
+    let output = Command::new(&binary_path)
+        .args(["--input", bin_path.to_str().unwrap()])
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(!output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    assert!(stderr.contains("Запись 2: смещение 59"), "{}", stderr);
+    assert!(stdout.contains("Байты вокруг места ошибки"), "{}", stdout);
+    assert!(stdout.contains("58 58 58 58"), "{}", stdout);
+}
@@ -0,0 +1,157 @@
+use parser_lib::{CsvParser, Transaction, TransactionStatus, TransactionType};
+use std::fs::File;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use tempfile::TempDir;
+
+fn build_and_get_binary(binary_name: &str) -> PathBuf {
+    let manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+
+    let build_status = Command::new("cargo")
+        .args(["build", "--bin", binary_name, "--quiet"])
+        .status()
+        .expect("Failed to build binary");
+
+    assert!(build_status.success(), "Failed to build {}", binary_name);
+
+    let mut binary_path = manifest_dir.join("target").join("debug").join(binary_name);
+
+    if cfg!(windows) {
+        binary_path.set_extension("exe");
+    }
+
+    assert!(
+        binary_path.exists(),
+        "Binary not found at {:?}",
+        binary_path
+    );
+    binary_path
+}
+
+fn create_transaction(tx_id: u64, tx_type: TransactionType) -> Transaction {
+    Transaction {
+        tx_id,
+        tx_type,
+        from_user_id: if tx_type == TransactionType::Deposit {
+            0
+        } else {
+            501
+        },
+        to_user_id: if tx_type == TransactionType::Withdrawal {
+            0
+        } else {
+            502
+        },
+        amount: 1000,
+        timestamp: 1672531200000,
+        status: TransactionStatus::Success,
+        description: format!("Record {}", tx_id),
+        currency: None,
+    }
+}
+
+fn run_script(binary_path: &Path, input: &Path, script: &str) -> (String, String) {
+    let mut child = Command::new(binary_path)
+        .args(["--input", input.to_str().unwrap(), "--format", "csv"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("Failed to start interactive");
+
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(script.as_bytes())
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success());
+
+    (
+        String::from_utf8_lossy(&output.stdout).to_string(),
+        String::from_utf8_lossy(&output.stderr).to_string(),
+    )
+}
+
+#[test]
+fn test_interactive_count_after_filter() {
+    let binary_path = build_and_get_binary("interactive");
+    let temp_dir = TempDir::new().unwrap();
+    let csv_path = temp_dir.path().join("records.csv");
+
+    let transactions = vec![
+        create_transaction(1, TransactionType::Deposit),
+        create_transaction(2, TransactionType::Transfer),
+        create_transaction(3, TransactionType::Transfer),
+        create_transaction(4, TransactionType::Withdrawal),
+    ];
+    let mut file = File::create(&csv_path).unwrap();
+    CsvParser::write_records(&transactions, &mut file).unwrap();
+    drop(file);
+
+    let (stdout, _) = run_script(
+        &binary_path,
+        &csv_path,
+        "count\nfilter type=transfer\ncount\nreset\ncount\nquit\ncount\n",
+    );
+    let counts: Vec<&str> = stdout
+        .lines()
+        .filter(|line| line.starts_with("Записей:"))
+        .collect();
+
+    assert_eq!(
+        counts,
+        vec![
+            "Записей: 4",
+            "Записей: 2",
+            "Записей: 2",
+            "Записей: 4",
+            "Записей: 4"
+        ]
+    );
+}
+
+#[test]
+fn test_interactive_reports_unknown_command() {
+    let binary_path = build_and_get_binary("interactive");
+    let temp_dir = TempDir::new().unwrap();
+    let csv_path = temp_dir.path().join("records.csv");
+
+    let mut file = File::create(&csv_path).unwrap();
+    CsvParser::write_records(
+        &[create_transaction(1, TransactionType::Deposit)],
+        &mut file,
+    )
+    .unwrap();
+    drop(file);
+
+    let (stdout, stderr) = run_script(&binary_path, &csv_path, "frobnicate\nshow 1\n");
+
+    assert!(stderr.contains("неизвестная команда 'frobnicate'"));
+    assert!(stdout.contains("TX_ID: 1"));
+}
+
+#[test]
+fn test_interactive_stats_with_large_amounts() {
+    let binary_path = build_and_get_binary("interactive");
+    let temp_dir = TempDir::new().unwrap();
+    let csv_path = temp_dir.path().join("records.csv");
+
+    let mut transactions = vec![
+        create_transaction(1, TransactionType::Deposit),
+        create_transaction(2, TransactionType::Deposit),
+    ];
+    for tx in &mut transactions {
+        tx.amount = i64::MAX;
+    }
+    let mut file = File::create(&csv_path).unwrap();
+    CsvParser::write_records(&transactions, &mut file).unwrap();
+    drop(file);
+
+    let (stdout, _) = run_script(&binary_path, &csv_path, "stats\n");
+
+    let expected = format!("Deposit: 2 записей, сумма {}", 2 * i128::from(i64::MAX));
+    assert!(stdout.contains(&expected), "{}", stdout);
+}
@@ -1,6 +1,6 @@
 use parser_lib::{
-    BinaryParser, BinaryTransactions, CsvParser, CsvTransactions, ParseFromRead, TextParser,
-    TextTransactions, Transaction, TransactionStatus, TransactionType,
+    BinaryParser, CsvParser, Format, ParseOptions, TextParser, Transaction, TransactionStatus,
+    TransactionType, Transactions, generate_sample,
 };
 use std::io::Cursor;
 
@@ -49,10 +49,11 @@ fn test_binary_parsing() {
         timestamp: 1672531200000,
         status: TransactionStatus::Success,
         description: "Test".to_string(),
+        currency: None,
     };
 
     let mut buffer = Vec::new();
-    assert!(BinaryParser::write_records(&[record.clone()], &mut buffer).is_ok());
+    assert!(BinaryParser::write_records(std::slice::from_ref(&record), &mut buffer).is_ok());
 
     let mut cursor = Cursor::new(&buffer);
     let parsed = BinaryParser::parse_records(&mut cursor);
@@ -73,30 +74,130 @@ fn test_cross_format_roundtrip() {
         timestamp: 1672531200000,
         status: TransactionStatus::Success,
         description: "Test transaction".to_string(),
+        currency: None,
     };
 
     let mut csv_buffer = Vec::new();
-    CsvParser::write_records(&[original.clone()], &mut csv_buffer).unwrap();
+    CsvParser::write_records(std::slice::from_ref(&original), &mut csv_buffer).unwrap();
     let csv_cursor = Cursor::new(csv_buffer);
     let csv_result = CsvParser::parse_records(csv_cursor).unwrap();
     assert_eq!(csv_result.len(), 1);
     assert_eq!(csv_result[0].tx_id, original.tx_id);
 
     let mut text_buffer = Vec::new();
-    TextParser::write_records(&[original.clone()], &mut text_buffer).unwrap();
+    TextParser::write_records(std::slice::from_ref(&original), &mut text_buffer).unwrap();
     let text_cursor = Cursor::new(text_buffer);
     let text_result = TextParser::parse_records(text_cursor).unwrap();
     assert_eq!(text_result.len(), 1);
     assert_eq!(text_result[0].tx_id, original.tx_id);
 
     let mut bin_buffer = Vec::new();
-    BinaryParser::write_records(&[original.clone()], &mut bin_buffer).unwrap();
+    BinaryParser::write_records(std::slice::from_ref(&original), &mut bin_buffer).unwrap();
     let mut bin_cursor = Cursor::new(bin_buffer);
     let bin_result = BinaryParser::parse_records(&mut bin_cursor).unwrap();
     assert_eq!(bin_result.len(), 1);
     assert_eq!(bin_result[0].tx_id, original.tx_id);
 }
 
+#[test]
+fn test_generated_sample_roundtrips_all_formats() {
+    let original = generate_sample(50, 2024);
+
+    let mut csv_buffer = Vec::new();
+    CsvParser::write_records(&original, &mut csv_buffer).unwrap();
+    let from_csv = CsvParser::parse_records(Cursor::new(&csv_buffer)).unwrap();
+    assert_eq!(from_csv, original);
+
+    let mut txt_buffer = Vec::new();
+    TextParser::write_records(&original, &mut txt_buffer).unwrap();
+    let from_txt = TextParser::parse_records(Cursor::new(&txt_buffer)).unwrap();
+    assert_eq!(from_txt, original);
+
+    let mut bin_buffer = Vec::new();
+    BinaryParser::write_records(&original, &mut bin_buffer).unwrap();
+    let from_bin = BinaryParser::parse_records(Cursor::new(&bin_buffer)).unwrap();
+    assert_eq!(from_bin, original);
+}
+
+#[test]
+fn test_currency_roundtrips_all_formats() {
+    let mut original = generate_sample(4, 7);
+    original[1].currency = Some("USD".to_string());
+    original[3].currency = Some("JPY".to_string());
+
+    let mut csv_buffer = Vec::new();
+    CsvParser::write_records(&original, &mut csv_buffer).unwrap();
+    let from_csv = CsvParser::parse_records(Cursor::new(&csv_buffer)).unwrap();
+    assert_eq!(from_csv, original);
+
+    let mut txt_buffer = Vec::new();
+    TextParser::write_records(&original, &mut txt_buffer).unwrap();
+    let from_txt = TextParser::parse_records(Cursor::new(&txt_buffer)).unwrap();
+    assert_eq!(from_txt, original);
+
+    let mut bin_buffer = Vec::new();
+    BinaryParser::write_records(&original, &mut bin_buffer).unwrap();
+    let from_bin = BinaryParser::parse_records(Cursor::new(&bin_buffer)).unwrap();
+    assert_eq!(from_bin, original);
+}
+
+#[test]
+fn test_reversed_status_roundtrips_all_formats() {
+    let mut original = generate_sample(3, 11);
+    original[0].status = TransactionStatus::Reversed;
+    original[2].status = TransactionStatus::Reversed;
+
+    let mut csv_buffer = Vec::new();
+    CsvParser::write_records(&original, &mut csv_buffer).unwrap();
+    assert!(String::from_utf8_lossy(&csv_buffer).contains(",REVERSED,"));
+    let from_csv = CsvParser::parse_records(Cursor::new(&csv_buffer)).unwrap();
+    assert_eq!(from_csv, original);
+
+    let mut txt_buffer = Vec::new();
+    TextParser::write_records(&original, &mut txt_buffer).unwrap();
+    assert!(String::from_utf8_lossy(&txt_buffer).contains("STATUS: REVERSED"));
+    let from_txt = TextParser::parse_records(Cursor::new(&txt_buffer)).unwrap();
+    assert_eq!(from_txt, original);
+
+    let mut bin_buffer = Vec::new();
+    BinaryParser::write_records(&original[..1], &mut bin_buffer).unwrap();
+    // Байт статуса следует за заголовком (8), TX_ID, TX_TYPE, ID, суммой и меткой времени
+    assert_eq!(bin_buffer[8 + 8 + 1 + 8 + 8 + 8 + 8], 3);
+    BinaryParser::write_records(&original[1..], &mut bin_buffer).unwrap();
+    let from_bin = BinaryParser::parse_records(Cursor::new(&bin_buffer)).unwrap();
+    assert_eq!(from_bin, original);
+}
+
+#[test]
+fn test_empty_input_handling_is_uniform_across_formats() {
+    let strict = ParseOptions {
+        allow_empty: false,
+        ..Default::default()
+    };
+    let dir = tempfile::TempDir::new().unwrap();
+
+    for format in [Format::Csv, Format::Txt, Format::Bin] {
+        let path = dir.path().join(format!("empty.{}", format.extension()));
+        std::fs::write(&path, b"").unwrap();
+
+        let file = std::fs::File::open(&path).unwrap();
+        let records = format.parse_records(file, &ParseOptions::default());
+        assert_eq!(records.unwrap(), Vec::new(), "{:?}", format);
+
+        let file = std::fs::File::open(&path).unwrap();
+        let err = format.parse_records(file, &strict).unwrap_err();
+        assert_eq!(err.to_string(), "Parse error: empty input", "{:?}", format);
+
+        // Пустой список записей в непустом файле допустим и в строгом режиме
+        let mut buffer = Vec::new();
+        format.write_records(&[], &mut buffer).unwrap();
+        if !buffer.is_empty() {
+            let records = format.parse_records(Cursor::new(&buffer), &strict).unwrap();
+            assert!(records.is_empty(), "{:?}", format);
+        }
+    }
+}
+
 #[test]
 fn test_comparer_functionality() {
     let transaction = Transaction {
@@ -108,20 +209,21 @@ fn test_comparer_functionality() {
         timestamp: 1672531200000,
         status: TransactionStatus::Success,
         description: "Test".to_string(),
+        currency: None,
     };
 
     let mut csv_buffer = Vec::new();
-    CsvParser::write_records(&[transaction.clone()], &mut csv_buffer).unwrap();
+    CsvParser::write_records(std::slice::from_ref(&transaction), &mut csv_buffer).unwrap();
     let csv_cursor = Cursor::new(csv_buffer);
     let csv_result = CsvParser::parse_records(csv_cursor).unwrap();
 
     let mut text_buffer = Vec::new();
-    TextParser::write_records(&[transaction.clone()], &mut text_buffer).unwrap();
+    TextParser::write_records(std::slice::from_ref(&transaction), &mut text_buffer).unwrap();
     let text_cursor = Cursor::new(text_buffer);
     let text_result = TextParser::parse_records(text_cursor).unwrap();
 
     let mut bin_buffer = Vec::new();
-    BinaryParser::write_records(&[transaction.clone()], &mut bin_buffer).unwrap();
+    BinaryParser::write_records(std::slice::from_ref(&transaction), &mut bin_buffer).unwrap();
     let mut bin_cursor = Cursor::new(bin_buffer);
     let bin_result = BinaryParser::parse_records(&mut bin_cursor).unwrap();
 
@@ -135,12 +237,11 @@ fn test_comparer_functionality() {
 }
 
 #[test]
-fn test_parsefromread_trait() {
+fn test_transactions_read_each_format() {
     let csv_data = r#"TX_ID,TX_TYPE,FROM_USER_ID,TO_USER_ID,AMOUNT,TIMESTAMP,STATUS,DESCRIPTION
 1001,DEPOSIT,0,501,50000,1672531200000,SUCCESS,"Test""#;
 
-    let mut csv_cursor = Cursor::new(csv_data);
-    let csv_transactions: CsvTransactions = ParseFromRead::parse(&mut csv_cursor).unwrap();
+    let csv_transactions = Transactions::read(Format::Csv, Cursor::new(csv_data)).unwrap();
     assert_eq!(csv_transactions.0.len(), 1);
     assert_eq!(csv_transactions.0[0].tx_id, 1001);
 
@@ -153,8 +254,7 @@ TIMESTAMP: 1672531200000
 STATUS: SUCCESS
 DESCRIPTION: "Test""#;
 
-    let mut text_cursor = Cursor::new(text_data);
-    let text_transactions: TextTransactions = ParseFromRead::parse(&mut text_cursor).unwrap();
+    let text_transactions = Transactions::read(Format::Txt, Cursor::new(text_data)).unwrap();
     assert_eq!(text_transactions.0.len(), 1);
     assert_eq!(text_transactions.0[0].tx_id, 1002);
 
@@ -167,13 +267,64 @@ DESCRIPTION: "Test""#;
         timestamp: 1672531200000,
         status: TransactionStatus::Success,
         description: "Test".to_string(),
+        currency: None,
     };
 
     let mut bin_buffer = Vec::new();
     BinaryParser::write_records(&[transaction], &mut bin_buffer).unwrap();
 
-    let mut bin_cursor = Cursor::new(&bin_buffer);
-    let bin_transactions: BinaryTransactions = ParseFromRead::parse(&mut bin_cursor).unwrap();
+    let bin_transactions = Transactions::read(Format::Bin, Cursor::new(&bin_buffer)).unwrap();
     assert_eq!(bin_transactions.0.len(), 1);
     assert_eq!(bin_transactions.0[0].tx_id, 1003);
 }
+
+#[test]
+fn test_transactions_read_matches_per_format_parsers() {
+    let records = generate_sample(5, 42);
+
+    for format in [Format::Csv, Format::Txt, Format::Bin] {
+        let mut buffer = Vec::new();
+        format.write_records(&records, &mut buffer).unwrap();
+
+        let Transactions(parsed) = Transactions::read(format, Cursor::new(&buffer)).unwrap();
+        assert_eq!(
+            parsed,
+            format
+                .parse_records(Cursor::new(&buffer), &ParseOptions::default())
+                .unwrap(),
+            "{:?}",
+            format
+        );
+        assert_eq!(parsed, records, "{:?}", format);
+    }
+}
+
+#[test]
+fn test_invalid_tx_id_error_text_matches_across_formats() {
+    let csv_data = r#"TX_ID,TX_TYPE,FROM_USER_ID,TO_USER_ID,AMOUNT,TIMESTAMP,STATUS,DESCRIPTION
+abc,DEPOSIT,0,501,50000,1672531200000,SUCCESS,"Bad id""#;
+
+    let txt_data = r#"TX_ID: abc
+TX_TYPE: DEPOSIT
+FROM_USER_ID: 0
+TO_USER_ID: 501
+AMOUNT: 50000
+TIMESTAMP: 1672531200000
+STATUS: SUCCESS
+DESCRIPTION: "Bad id""#;
+
+    let csv_error = CsvParser::parse_records(Cursor::new(csv_data))
+        .unwrap_err()
+        .to_string();
+    let txt_error = TextParser::parse_records(Cursor::new(txt_data))
+        .unwrap_err()
+        .to_string();
+
+    let strip_line = |msg: &str| {
+        msg.split_once(": invalid")
+            .map(|(_, rest)| rest.to_string())
+    };
+
+    assert!(csv_error.contains("invalid TX_ID 'abc'"), "{}", csv_error);
+    assert_eq!(strip_line(&csv_error), strip_line(&txt_error));
+}
@@ -0,0 +1,56 @@
+use std::path::PathBuf;
+use std::process::Command;
+
+fn build_and_get_binary(binary_name: &str) -> PathBuf {
+    let manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+
+    let build_status = Command::new("cargo")
+        .args(["build", "--bin", binary_name, "--quiet"])
+        .status()
+        .expect("Failed to build binary");
+
+    assert!(build_status.success(), "Failed to build {}", binary_name);
+
+    let mut binary_path = manifest_dir.join("target").join("debug").join(binary_name);
+
+    if cfg!(windows) {
+        binary_path.set_extension("exe");
+    }
+
+    assert!(
+        binary_path.exists(),
+        "Binary not found at {:?}",
+        binary_path
+    );
+    binary_path
+}
+
+#[test]
+fn test_selftest_passes_on_builtin_formats() {
+    let binary_path = build_and_get_binary("selftest");
+
+    let output = Command::new(&binary_path)
+        .args(["--count", "30", "--seed", "7"])
+        .output()
+        .expect("Failed to execute selftest");
+
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "Stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(!stdout.contains("FAIL"));
+    for format in ["csv", "txt", "bin"] {
+        assert!(
+            stdout
+                .lines()
+                .any(|line| line.trim_start().starts_with(format) && line.contains("OK")),
+            "Missing matrix row for {}: {}",
+            format,
+            stdout
+        );
+    }
+}